@@ -679,7 +679,7 @@ impl BlockMetadataTransaction {
 pub enum ValidatorTransaction {
     ObservedJwkUpdate(JWKUpdateTransaction),
     DkgResult(DKGResultTransaction),
-    TimelockDkgResult(DKGResultTransaction),
+    TimelockDkgResult(TimelockDKGResultTransaction),
     TimelockShare(TimelockShareTransaction),
 }
 
@@ -690,7 +690,9 @@ impl ValidatorTransaction {
                 "validator_transaction__observed_jwk_update"
             },
             ValidatorTransaction::DkgResult(_) => "validator_transaction__dkg_result",
-            ValidatorTransaction::TimelockDkgResult(_) => "validator_transaction__timelock_dkg_result",
+            ValidatorTransaction::TimelockDkgResult(_) => {
+                "validator_transaction__timelock_dkg_result"
+            },
             ValidatorTransaction::TimelockShare(_) => "validator_transaction__timelock_share",
         }
     }
@@ -765,12 +767,13 @@ impl
                 timestamp: U64::from(timestamp),
                 quorum_certified_update: quorum_certified_update.into(),
             }),
-            aptos_types::validator_txn::ValidatorTransaction::TimelockDKGResult(dkg_transcript) => {
-                Self::TimelockDkgResult(DKGResultTransaction {
+            aptos_types::validator_txn::ValidatorTransaction::TimelockDKGResult(result) => {
+                Self::TimelockDkgResult(TimelockDKGResultTransaction {
                     info,
                     events,
                     timestamp: U64::from(timestamp),
-                    dkg_transcript: dkg_transcript.into(),
+                    interval: result.interval.into(),
+                    dkg_transcript: result.transcript.into(),
                 })
             },
             aptos_types::validator_txn::ValidatorTransaction::TimelockShare(share) => {
@@ -867,6 +870,17 @@ pub struct DKGResultTransaction {
     pub dkg_transcript: ExportedDKGTranscript,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TimelockDKGResultTransaction {
+    #[serde(flatten)]
+    #[oai(flatten)]
+    pub info: TransactionInfo,
+    pub events: Vec<Event>,
+    pub timestamp: U64,
+    pub interval: U64,
+    pub dkg_transcript: ExportedDKGTranscript,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct TimelockShareTransaction {
     #[serde(flatten)]
@@ -879,15 +893,19 @@ pub struct TimelockShareTransaction {
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct ExportedTimelockShare {
+    pub author: Address,
     pub interval: U64,
     pub share: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
 }
 
 impl From<TimelockShare> for ExportedTimelockShare {
     fn from(value: TimelockShare) -> Self {
         Self {
+            author: value.author.into(),
             interval: value.interval.into(),
             share: HexEncodedBytes::from(value.share),
+            signature: HexEncodedBytes::from(value.signature.to_bytes().to_vec()),
         }
     }
 }
@@ -8,6 +8,7 @@ use crate::{
     gas_schedule::NativeGasParameters,
     ver::gas_feature_versions::{
         RELEASE_V1_12, RELEASE_V1_13, RELEASE_V1_23, RELEASE_V1_26, RELEASE_V1_28, RELEASE_V1_36,
+        RELEASE_V1_39,
     },
 };
 use aptos_gas_algebra::{
@@ -168,6 +169,12 @@ crate::gas_schedule::macros::define_gas_parameters!(
         [algebra_ark_h2c_bls12381g2_xmd_sha256_sswu_per_msg_byte: InternalGasPerByte, { 8.. => "algebra.ark_h2c_bls12381g2_xmd_sha256_sswu_per_msg_byte" }, 176],
         // BLS12-381 algebra gas parameters end.
 
+        [algebra_ibe_keccak_base: InternalGas, { RELEASE_V1_39.. => "algebra.ibe.keccak_base" }, 14704],
+        [algebra_ibe_keccak_per_byte: InternalGasPerByte, { RELEASE_V1_39.. => "algebra.ibe.keccak_per_byte" }, 165],
+        [algebra_ibe_xor_per_byte: InternalGasPerByte, { RELEASE_V1_39.. => "algebra.ibe.xor_per_byte" }, 20],
+        [algebra_ibe_aggregate_per_share_inv: InternalGasPerArg, { RELEASE_V1_39.. => "algebra.ibe.aggregate_per_share_inv" }, 215450],
+        [algebra_ibe_aggregate_per_share_msm: InternalGasPerArg, { RELEASE_V1_39.. => "algebra.ibe.aggregate_per_share_msm" }, 9276463],
+
         [bls12381_base: InternalGas, "bls12381.base", 551],
 
         [bls12381_per_pubkey_deserialize: InternalGasPerArg, "bls12381.per_pubkey_deserialize", 400684],
@@ -8,6 +8,13 @@
 ///   - Changing how gas is calculated in any way
 ///
 /// Change log:
+/// - V43:
+///    - Gas parameters for hashing and XOR in the IBE natives (`algebra.ibe.keccak_base`,
+///      `algebra.ibe.keccak_per_byte`, `algebra.ibe.xor_per_byte`)
+///    - Gas parameters for the IBE share aggregation native (`algebra.ibe.aggregate_per_share_inv`,
+///      `algebra.ibe.aggregate_per_share_msm`)
+///    - IBE decryption aborts on ciphertexts longer than 32 bytes
+///
 /// - V31:
 ///    - Gas charging for modules used in type tags
 ///
@@ -72,7 +79,7 @@
 ///       global operations.
 /// - V1
 ///   - TBA
-pub const LATEST_GAS_FEATURE_VERSION: u64 = gas_feature_versions::RELEASE_V1_38;
+pub const LATEST_GAS_FEATURE_VERSION: u64 = gas_feature_versions::RELEASE_V1_39;
 
 pub mod gas_feature_versions {
     pub const RELEASE_V1_8: u64 = 11;
@@ -105,4 +112,5 @@ pub mod gas_feature_versions {
     pub const RELEASE_V1_36: u64 = 40;
     pub const RELEASE_V1_37: u64 = 41;
     pub const RELEASE_V1_38: u64 = 42;
+    pub const RELEASE_V1_39: u64 = 43;
 }
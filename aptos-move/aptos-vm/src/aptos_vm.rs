@@ -376,7 +376,7 @@ impl AptosVM {
     }
 
     #[inline(always)]
-    fn chain_id(&self) -> ChainId {
+    pub(crate) fn chain_id(&self) -> ChainId {
         self.move_vm.env.chain_id()
     }
 
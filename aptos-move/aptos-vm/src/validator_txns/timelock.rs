@@ -6,24 +6,75 @@ use crate::{
     errors::expect_only_successful_execution,
     move_vm_ext::{AptosMoveResolver, SessionId},
     system_module_names::{PUBLISH_PUBLIC_KEY, PUBLISH_SECRET_SHARE, TIMELOCK_MODULE},
+    validator_txns::timelock::{
+        ExecutionFailure::{Expected, Unexpected},
+        ExpectedFailure::*,
+    },
     AptosVM,
 };
+use aptos_crypto::blstrs::{
+    g1_proj_from_bytes, g2_proj_from_bytes, G1_PROJ_NUM_BYTES, G2_PROJ_NUM_BYTES,
+};
 use aptos_types::{
-    dkg::{DKGTranscript, TimelockShare},
+    dkg::{
+        DKGTrait, DefaultDKG, TimelockDKGResult, TimelockKeyGenConfig, TimelockShare,
+        TimelockState, TimelockValidatorShares,
+    },
     move_utils::as_move_value::AsMoveValue,
+    on_chain_config::{ConfigStorage, ConfigurationResource, OnChainConfig, ValidatorSet},
+    transaction::TransactionStatus,
+    validator_verifier::ValidatorVerifier,
 };
 use aptos_vm_logging::log_schema::AdapterLogSchema;
 use aptos_vm_types::{
     module_and_script_storage::module_storage::AptosModuleStorage, output::VMOutput,
 };
 use move_core_types::{
-    account_address::AccountAddress,
     value::{serialize_values, MoveValue},
-    vm_status::VMStatus,
+    vm_status::{AbortLocation, StatusCode, VMStatus},
 };
 use move_vm_runtime::module_traversal::{TraversalContext, TraversalStorage};
 use move_vm_types::gas::UnmeteredGasMeter;
 
+/// Shares for intervals more than this many intervals behind the current one are rejected.
+const MAX_TIMELOCK_SHARE_INTERVAL_LAG: u64 = 100;
+
+/// A share carries one G1 point per sub-share of its validator; shares with more are rejected.
+const MAX_TIMELOCK_SHARE_NUM_POINTS: usize = 1024;
+
+#[derive(Debug, Eq, PartialEq)]
+enum ExpectedFailure {
+    // Move equivalent: `errors::invalid_argument(*)`
+    ShareWrongLength = 0x010201,
+    ShareNotInG1 = 0x010202,
+    ShareIntervalNotRevealed = 0x010203,
+    ShareIntervalTooOld = 0x010204,
+    TranscriptDeserializationFailed = 0x010205,
+    EpochNotCurrent = 0x010206,
+    TranscriptVerificationFailed = 0x010207,
+    NotEnoughVotingPower = 0x010208,
+    ShareAlreadyPublished = 0x010209,
+    PublicKeyWrongLength = 0x01020A,
+    PublicKeyNotInG2 = 0x01020B,
+    KeyGenIntervalNotPending = 0x01020C,
+    ShareSignatureInvalid = 0x01020D,
+    PublicKeyShareNotFound = 0x01020E,
+    ShareVerificationFailed = 0x01020F,
+
+    // Move equivalent: `errors::invalid_state(*)`
+    MissingResourceTimelockState = 0x30201,
+    MissingResourceConfiguration = 0x30202,
+    MissingResourceValidatorSet = 0x30203,
+    MissingResourceKeyGenConfig = 0x30204,
+    InvalidKeyGenConfig = 0x30205,
+    MissingResourceValidatorShares = 0x30206,
+}
+
+enum ExecutionFailure {
+    Expected(ExpectedFailure),
+    Unexpected(VMStatus),
+}
+
 impl AptosVM {
     pub(crate) fn process_timelock_dkg_result(
         &self,
@@ -31,15 +82,90 @@ impl AptosVM {
         module_storage: &impl AptosModuleStorage,
         log_context: &AdapterLogSchema,
         session_id: SessionId,
-        dkg_transcript: DKGTranscript,
+        dkg_result: TimelockDKGResult,
     ) -> Result<(VMStatus, VMOutput), VMStatus> {
+        discard_expected_failure(self.process_timelock_dkg_result_inner(
+            resolver,
+            module_storage,
+            log_context,
+            session_id,
+            dkg_result,
+        ))
+    }
+
+    fn process_timelock_dkg_result_inner(
+        &self,
+        resolver: &impl AptosMoveResolver,
+        module_storage: &impl AptosModuleStorage,
+        log_context: &AdapterLogSchema,
+        session_id: SessionId,
+        dkg_result: TimelockDKGResult,
+    ) -> Result<(VMStatus, VMOutput), ExecutionFailure> {
+        let TimelockDKGResult {
+            interval,
+            transcript,
+        } = dkg_result;
+
+        // Load resources.
+        let timelock_state =
+            TimelockState::fetch_config(resolver).ok_or(Expected(MissingResourceTimelockState))?;
+        let config_resource = ConfigurationResource::fetch_config(resolver)
+            .ok_or(Expected(MissingResourceConfiguration))?;
+        let validator_set =
+            ValidatorSet::fetch_config(resolver).ok_or(Expected(MissingResourceValidatorSet))?;
+        let key_gen_config = TimelockKeyGenConfig::fetch_config(resolver)
+            .ok_or(Expected(MissingResourceKeyGenConfig))?
+            .config;
+
+        // Check epoch number.
+        if transcript.metadata.epoch != config_resource.epoch() {
+            return Err(Expected(EpochNotCurrent));
+        }
+        if !key_gen_config.is_valid() {
+            return Err(Expected(InvalidKeyGenConfig));
+        }
+
+        // Check that the DKG of the interval was requested and has not published a key yet.
+        let key_published = resolver
+            .fetch_config_bytes(&timelock_state.public_key_state_key(interval))
+            .is_some();
+        validate_key_gen_interval(interval, timelock_state.current_interval, key_published)
+            .map_err(Expected)?;
+
+        // Rebuild the session the validators ran the DKG for the interval in, then deserialize
+        // the transcript and verify it against it. Its dealers signed the interval, so it does
+        // not verify for another one.
+        let verifier = ValidatorVerifier::from(&validator_set);
+        let session_metadata = key_gen_config
+            .scaled_to(verifier.len() as u64)
+            .session_metadata(config_resource.epoch(), &verifier);
+        let pub_params = DefaultDKG::new_timelock_public_params(&session_metadata, interval);
+        let dkg_transcript = decode_transcript(&transcript.transcript_bytes).map_err(Expected)?;
+        verify_transcript(&pub_params, &dkg_transcript).map_err(Expected)?;
+        let public_key = dkg_transcript.dealt_public_key_bytes();
+        validate_public_key(&public_key).map_err(Expected)?;
+        // Published with the key, to verify the decryption key shares of each validator against
+        // and to aggregate them at their evaluation points once the threshold weight is reached
+        let validators = verifier.get_ordered_account_addresses();
+        let public_key_shares: Vec<Vec<u8>> = (0..validators.len())
+            .map(|player| dkg_transcript.dealt_public_key_share_bytes(&pub_params, player))
+            .collect();
+        let evaluation_points: Vec<Vec<u8>> = (0..validators.len())
+            .map(|player| pub_params.evaluation_point_bytes(player))
+            .collect();
+
+        // All checks passed, invoke VM to publish the dealt public key on chain.
         let mut gas_meter = UnmeteredGasMeter;
         let mut session = self.new_session(resolver, session_id, None);
 
         let args = vec![
-            MoveValue::Signer(AccountAddress::ONE), // Or validator address? Using ONE/Framework for now as per dkg.rs pattern
-            MoveValue::U64(dkg_transcript.metadata.epoch), // Reuse epoch as interval
-            dkg_transcript.transcript_bytes.as_move_value(),
+            MoveValue::Signer(transcript.metadata.author),
+            MoveValue::U64(interval),
+            public_key.as_move_value(),
+            MoveValue::U64(pub_params.threshold_weight() as u64),
+            MoveValue::Vector(validators.into_iter().map(MoveValue::Address).collect()),
+            public_key_shares.as_move_value(),
+            evaluation_points.as_move_value(),
         ];
 
         let traversal_storage = TraversalStorage::new();
@@ -56,13 +182,17 @@ impl AptosVM {
             .map_err(|e| {
                 expect_only_successful_execution(e, PUBLISH_PUBLIC_KEY.as_str(), log_context)
             })
-            .map_err(|r| r.unwrap_err())?;
+            .map_err(|r| Unexpected(r.unwrap_err()))?;
 
         let output = get_system_transaction_output(
             session,
             module_storage,
-            &self.storage_gas_params(log_context)?.change_set_configs,
-        )?;
+            &self
+                .storage_gas_params(log_context)
+                .map_err(Unexpected)?
+                .change_set_configs,
+        )
+        .map_err(Unexpected)?;
 
         Ok((VMStatus::Executed, output))
     }
@@ -75,11 +205,60 @@ impl AptosVM {
         session_id: SessionId,
         share: TimelockShare,
     ) -> Result<(VMStatus, VMOutput), VMStatus> {
+        discard_expected_failure(self.process_timelock_share_inner(
+            resolver,
+            module_storage,
+            log_context,
+            session_id,
+            share,
+        ))
+    }
+
+    fn process_timelock_share_inner(
+        &self,
+        resolver: &impl AptosMoveResolver,
+        module_storage: &impl AptosModuleStorage,
+        log_context: &AdapterLogSchema,
+        session_id: SessionId,
+        share: TimelockShare,
+    ) -> Result<(VMStatus, VMOutput), ExecutionFailure> {
+        let timelock_state =
+            TimelockState::fetch_config(resolver).ok_or(Expected(MissingResourceTimelockState))?;
+        validate_share(&share, timelock_state.current_interval).map_err(Expected)?;
+        let validator_set =
+            ValidatorSet::fetch_config(resolver).ok_or(Expected(MissingResourceValidatorSet))?;
+        share
+            .verify_signature(&ValidatorVerifier::from(&validator_set))
+            .map_err(|_| Expected(ShareSignatureInvalid))?;
+
+        // Check the share against the public key shares the interval's DKG dealt to its author,
+        // so that only shares that open the interval's ciphertexts get published.
+        let validator_shares = TimelockValidatorShares::fetch_config(resolver)
+            .ok_or(Expected(MissingResourceValidatorShares))?;
+        let public_key_shares = resolver
+            .fetch_config_bytes(
+                &validator_shares.public_key_share_state_key(share.interval, share.author),
+            )
+            .and_then(|bytes| bcs::from_bytes::<Vec<u8>>(&bytes).ok())
+            .ok_or(Expected(PublicKeyShareNotFound))?;
+        share
+            .verify_key_shares(&public_key_shares, self.chain_id().id())
+            .map_err(|_| Expected(ShareVerificationFailed))?;
+
+        // A share that already landed is re-submitted until the node sees it on-chain, so skip
+        // it before creating a session.
+        if resolver
+            .fetch_config_bytes(&validator_shares.share_state_key(share.interval, share.author))
+            .is_some()
+        {
+            return Err(Expected(ShareAlreadyPublished));
+        }
+
         let mut gas_meter = UnmeteredGasMeter;
         let mut session = self.new_session(resolver, session_id, None);
 
         let args = vec![
-            MoveValue::Signer(AccountAddress::ONE),
+            MoveValue::Signer(share.author),
             MoveValue::U64(share.interval),
             share.share.as_move_value(),
         ];
@@ -98,40 +277,253 @@ impl AptosVM {
             .map_err(|e| {
                 expect_only_successful_execution(e, PUBLISH_SECRET_SHARE.as_str(), log_context)
             })
-            .map_err(|r| r.unwrap_err())?;
+            .map_err(|r| Unexpected(r.unwrap_err()))?;
 
         let output = get_system_transaction_output(
             session,
             module_storage,
-            &self.storage_gas_params(log_context)?.change_set_configs,
-        )?;
+            &self
+                .storage_gas_params(log_context)
+                .map_err(Unexpected)?
+                .change_set_configs,
+        )
+        .map_err(Unexpected)?;
 
         Ok((VMStatus::Executed, output))
     }
 }
 
+/// Turns expected failures into a discarded transaction, as if they were Move aborts, so that a
+/// bad validator transaction does not fail the block.
+fn discard_expected_failure(
+    result: Result<(VMStatus, VMOutput), ExecutionFailure>,
+) -> Result<(VMStatus, VMOutput), VMStatus> {
+    match result {
+        Ok((vm_status, vm_output)) => Ok((vm_status, vm_output)),
+        Err(Expected(failure)) => Ok((
+            VMStatus::MoveAbort(AbortLocation::Script, failure as u64),
+            VMOutput::empty_with_status(TransactionStatus::Discard(StatusCode::ABORTED)),
+        )),
+        Err(Unexpected(vm_status)) => Err(vm_status),
+    }
+}
+
+/// Checks that `share` is a non-empty sequence of compressed G1 points in the prime-order subgroup
+/// (one per sub-share of its validator), for an interval whose reveal was already requested (i.e.,
+/// before `current_interval`) and not too long ago.
+fn validate_share(share: &TimelockShare, current_interval: u64) -> Result<(), ExpectedFailure> {
+    let num_points = share.share.len() / G1_PROJ_NUM_BYTES;
+    if share.share.len() % G1_PROJ_NUM_BYTES != 0
+        || num_points == 0
+        || num_points > MAX_TIMELOCK_SHARE_NUM_POINTS
+    {
+        return Err(ShareWrongLength);
+    }
+    for point in share.share.chunks_exact(G1_PROJ_NUM_BYTES) {
+        g1_proj_from_bytes(point).map_err(|_| ShareNotInG1)?;
+    }
+    if share.interval >= current_interval {
+        return Err(ShareIntervalNotRevealed);
+    }
+    if current_interval - share.interval > MAX_TIMELOCK_SHARE_INTERVAL_LAG {
+        return Err(ShareIntervalTooOld);
+    }
+    Ok(())
+}
+
+/// Checks that a DKG result may publish the key of `interval`: the current interval, or an earlier
+/// one whose key generation was requested (every interval since the first rotation) but whose key
+/// never landed, e.g., because an epoch change interrupted its DKG.
+///
+/// Together with transcripts being bound to their interval, this gives every interval a master
+/// public key of its own, which timelock encryption relies on (see
+/// `aptos_dkg::ibe::timelock_identity_point`).
+fn validate_key_gen_interval(
+    interval: u64,
+    current_interval: u64,
+    key_published: bool,
+) -> Result<(), ExpectedFailure> {
+    if interval == current_interval
+        || (0 < interval && interval < current_interval && !key_published)
+    {
+        Ok(())
+    } else {
+        Err(KeyGenIntervalNotPending)
+    }
+}
+
+/// Checks that `public_key`, the bytes published as the master public key of an interval, is a
+/// compressed G2 point in the prime-order subgroup.
+fn validate_public_key(public_key: &[u8]) -> Result<(), ExpectedFailure> {
+    if public_key.len() != G2_PROJ_NUM_BYTES {
+        return Err(PublicKeyWrongLength);
+    }
+    g2_proj_from_bytes(public_key).map_err(|_| PublicKeyNotInG2)?;
+    Ok(())
+}
+
+/// Decodes the bytes of a timelock DKG result as a DKG transcript.
+///
+/// The timelock DKG result carries the whole aggregated transcript, from which only the dealt
+/// public key gets published.
+fn decode_transcript(
+    transcript_bytes: &[u8],
+) -> Result<<DefaultDKG as DKGTrait>::Transcript, ExpectedFailure> {
+    bcs::from_bytes(transcript_bytes).map_err(|_| TranscriptDeserializationFailed)
+}
+
+/// Checks that `transcript` verifies for the session of `pub_params`, and that its dealers hold a
+/// quorum of the voting power, so that no single validator can choose the dealt secret.
+fn verify_transcript(
+    pub_params: &<DefaultDKG as DKGTrait>::PublicParams,
+    transcript: &<DefaultDKG as DKGTrait>::Transcript,
+) -> Result<(), ExpectedFailure> {
+    DefaultDKG::verify_transcript(pub_params, transcript)
+        .map_err(|_| TranscriptVerificationFailed)?;
+    DefaultDKG::verify_transcript_extra(transcript, &pub_params.verifier, true, None)
+        .map_err(|_| NotEnoughVotingPower)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aptos_types::dkg::{DKGTranscript, DKGTranscriptMetadata, TimelockShare};
+    use aptos_crypto::{bls12381, SigningKey, Uniform};
+    use aptos_types::dkg::TimelockShare;
     use move_core_types::account_address::AccountAddress;
 
-    // These tests verify that the structure of the dispatcher allows meaningful processing.
-    // They mock the VM/Storage interaction to focus on valid input handling.
+    /// A compressed G1 point with the given x-coordinate, which is 1 or 4.
+    fn compressed_g1_with_x(x: u8) -> Vec<u8> {
+        let mut bytes = vec![0u8; G1_PROJ_NUM_BYTES];
+        bytes[0] = 0x80;
+        bytes[G1_PROJ_NUM_BYTES - 1] = x;
+        bytes
+    }
 
     #[test]
-    fn test_timelock_dkg_result_dispatch() {
-        // This is a minimal smoke test ensuring compilation and basic function signature handling
-        let transcript = DKGTranscript {
-            metadata: DKGTranscriptMetadata {
-                epoch: 10,
-                author: AccountAddress::ONE,
-            },
-            transcript_bytes: vec![1, 2, 3],
+    fn test_validate_share() {
+        let point = bls12381::PublicKey::from(&bls12381::PrivateKey::generate_for_testing())
+            .to_bytes()
+            .to_vec();
+        let share = |interval: u64, share: Vec<u8>| {
+            TimelockShare::sign(
+                AccountAddress::ONE,
+                interval,
+                share,
+                &bls12381::PrivateKey::generate_for_testing(),
+            )
+            .unwrap()
         };
 
-        // Note: Fully mocking AptosVM session creation for unit tests is complex and often done
-        // at integration level. Here we assert types exist and are importable.
-        assert_eq!(transcript.metadata.epoch, 10);
+        assert_eq!(validate_share(&share(9, point.clone()), 10), Ok(()));
+        // One point per sub-share
+        assert_eq!(validate_share(&share(9, point.repeat(3)), 10), Ok(()));
+        assert_eq!(
+            validate_share(&share(9, point[..47].to_vec()), 10),
+            Err(ShareWrongLength)
+        );
+        assert_eq!(validate_share(&share(9, vec![]), 10), Err(ShareWrongLength));
+        assert_eq!(
+            validate_share(
+                &share(9, point.repeat(MAX_TIMELOCK_SHARE_NUM_POINTS + 1)),
+                10
+            ),
+            Err(ShareWrongLength)
+        );
+        assert_eq!(
+            validate_share(&share(9, vec![0u8; 1024]), 10),
+            Err(ShareWrongLength)
+        );
+        // x = 1 is not on the curve
+        assert_eq!(
+            validate_share(&share(9, compressed_g1_with_x(1)), 10),
+            Err(ShareNotInG1)
+        );
+        // x = 4 is on the curve but outside the prime-order subgroup
+        assert_eq!(
+            validate_share(&share(9, compressed_g1_with_x(4)), 10),
+            Err(ShareNotInG1)
+        );
+        assert_eq!(
+            validate_share(
+                &share(9, [point.clone(), compressed_g1_with_x(4)].concat()),
+                10
+            ),
+            Err(ShareNotInG1)
+        );
+        assert_eq!(
+            validate_share(&share(10, point.clone()), 10),
+            Err(ShareIntervalNotRevealed)
+        );
+        assert_eq!(
+            validate_share(&share(0, point.clone()), MAX_TIMELOCK_SHARE_INTERVAL_LAG),
+            Ok(())
+        );
+        assert_eq!(
+            validate_share(&share(0, point), MAX_TIMELOCK_SHARE_INTERVAL_LAG + 1),
+            Err(ShareIntervalTooOld)
+        );
+    }
+
+    #[test]
+    fn test_validate_key_gen_interval() {
+        assert_eq!(validate_key_gen_interval(0, 0, false), Ok(()));
+        assert_eq!(validate_key_gen_interval(10, 10, false), Ok(()));
+        // A re-submitted result for the current interval is a no-op in Move
+        assert_eq!(validate_key_gen_interval(10, 10, true), Ok(()));
+        // Requested, but interrupted
+        assert_eq!(validate_key_gen_interval(9, 10, false), Ok(()));
+        assert_eq!(validate_key_gen_interval(1, 10, false), Ok(()));
+        assert_eq!(
+            validate_key_gen_interval(9, 10, true),
+            Err(KeyGenIntervalNotPending)
+        );
+        // Never requested
+        assert_eq!(
+            validate_key_gen_interval(0, 10, false),
+            Err(KeyGenIntervalNotPending)
+        );
+        assert_eq!(
+            validate_key_gen_interval(11, 10, false),
+            Err(KeyGenIntervalNotPending)
+        );
+        assert_eq!(
+            validate_key_gen_interval(12345, 10, false),
+            Err(KeyGenIntervalNotPending)
+        );
+    }
+
+    #[test]
+    fn test_validate_public_key() {
+        // A BLS signature is a compressed G2 point in the prime-order subgroup
+        let point = bls12381::PrivateKey::generate_for_testing()
+            .sign_arbitrary_message(b"timelock")
+            .to_bytes()
+            .to_vec();
+
+        assert_eq!(validate_public_key(&point), Ok(()));
+        assert_eq!(
+            validate_public_key(&point[..G2_PROJ_NUM_BYTES - 1]),
+            Err(PublicKeyWrongLength)
+        );
+        assert_eq!(
+            validate_public_key(&compressed_g1_with_x(4)),
+            Err(PublicKeyWrongLength)
+        );
+        assert_eq!(
+            validate_public_key(&[0xFF; G2_PROJ_NUM_BYTES]),
+            Err(PublicKeyNotInG2)
+        );
+    }
+
+    #[test]
+    fn test_decode_transcript() {
+        assert_eq!(
+            decode_transcript(&[1, 2, 3]).err(),
+            Some(TranscriptDeserializationFailed)
+        );
+        assert_eq!(
+            decode_transcript(&compressed_g1_with_x(1)).err(),
+            Some(TranscriptDeserializationFailed)
+        );
     }
 }
@@ -0,0 +1,12 @@
+script {
+    use aptos_std::bls12381_algebra::{FormatG1Compr, FormatG2Compr, G1, G2, Gt, HashG1XmdSha256SswuRo};
+    use aptos_std::crypto_algebra::{deserialize, hash_to};
+    use aptos_std::ibe;
+
+    fun main(share: vector<u8>, pk_share: vector<u8>, dst: vector<u8>, identity: vector<u8>, expected: bool) {
+        let share = deserialize<G1, FormatG1Compr>(&share).extract();
+        let pk_share = deserialize<G2, FormatG2Compr>(&pk_share).extract();
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&dst, &identity);
+        assert!(ibe::verify_share<G1, G2, Gt>(&share, &pk_share, &hashed_id) == expected, 1);
+    }
+}
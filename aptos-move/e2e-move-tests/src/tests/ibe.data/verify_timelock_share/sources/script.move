@@ -0,0 +1,11 @@
+script {
+    use aptos_std::bls12381_algebra::{FormatG1Compr, FormatG2Compr, G1, G2, Gt};
+    use aptos_std::crypto_algebra::deserialize;
+    use aptos_std::ibe;
+
+    fun main(share: vector<u8>, pk_share: vector<u8>, identity: vector<u8>, expected: bool) {
+        let share = deserialize<G1, FormatG1Compr>(&share).extract();
+        let pk_share = deserialize<G2, FormatG2Compr>(&pk_share).extract();
+        assert!(ibe::verify_timelock_share<G1, G2, Gt>(&share, &pk_share, identity) == expected, 1);
+    }
+}
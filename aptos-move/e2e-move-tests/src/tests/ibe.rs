@@ -0,0 +1,199 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, tests::common, MoveHarness};
+use aptos_dkg::{
+    ibe::{
+        compute_timelock_identity, derive_timelock_key_share, hash_identity_to_g1, TIMELOCK_IBE_DST,
+    },
+    pvss::das,
+};
+use aptos_framework::{BuildOptions, BuiltPackage};
+use aptos_language_e2e_tests::account::Account;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{Script, TransactionArgument, TransactionOutput, TransactionPayload},
+};
+use blstrs::{G1Projective, G2Projective, Scalar};
+use group::Group;
+
+fn verify_share_script() -> Vec<u8> {
+    let package = BuiltPackage::build(
+        common::test_dir_path("ibe.data/verify_share"),
+        BuildOptions::move_2(),
+    )
+    .expect("building package must succeed");
+    package.extract_script_code().pop().expect("script exists")
+}
+
+/// Runs the script checking `share` against `pk_share` for `identity`, asserting the check
+/// returns `expected`.
+fn run_verify_share(
+    h: &mut MoveHarness,
+    account: &Account,
+    code: Vec<u8>,
+    share: Vec<u8>,
+    pk_share: Vec<u8>,
+    identity: Vec<u8>,
+    expected: bool,
+) -> TransactionOutput {
+    let script = Script::new(code, vec![], vec![
+        TransactionArgument::U8Vector(share),
+        TransactionArgument::U8Vector(pk_share),
+        TransactionArgument::U8Vector(TIMELOCK_IBE_DST.to_vec()),
+        TransactionArgument::U8Vector(identity),
+        TransactionArgument::Bool(expected),
+    ]);
+    let txn = h.create_transaction_payload(account, TransactionPayload::Script(script));
+    h.run_raw(txn)
+}
+
+#[test]
+fn test_verify_share() {
+    let mut h = MoveHarness::new();
+    let account = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let code = verify_share_script();
+
+    let sk_share = Scalar::from(0x5EED_u64);
+    let pk_share = (G2Projective::generator() * sk_share)
+        .to_compressed()
+        .to_vec();
+    let identity = compute_timelock_identity(7, 4).as_bytes().to_vec();
+    let share = (hash_identity_to_g1(&identity) * sk_share)
+        .to_compressed()
+        .to_vec();
+
+    let valid = run_verify_share(
+        &mut h,
+        &account,
+        code.clone(),
+        share.clone(),
+        pk_share.clone(),
+        identity,
+        true,
+    );
+    assert_success!(valid.status().to_owned());
+
+    // The share of interval 7 does not verify for interval 8
+    let other_identity = compute_timelock_identity(8, 4).as_bytes().to_vec();
+    let invalid = run_verify_share(
+        &mut h,
+        &account,
+        code,
+        share,
+        pk_share,
+        other_identity,
+        false,
+    );
+    assert_success!(invalid.status().to_owned());
+
+    // The pairings are charged whether or not the share verifies
+    assert!(valid.gas_used() > 0);
+    assert_eq!(valid.gas_used(), invalid.gas_used());
+}
+
+fn verify_timelock_share_script() -> Vec<u8> {
+    let package = BuiltPackage::build(
+        common::test_dir_path("ibe.data/verify_timelock_share"),
+        BuildOptions::move_2(),
+    )
+    .expect("building package must succeed");
+    package.extract_script_code().pop().expect("script exists")
+}
+
+/// Runs the script checking the timelock `share` against the dealt `pk_share` for `identity`,
+/// asserting the check returns `expected`.
+fn run_verify_timelock_share(
+    h: &mut MoveHarness,
+    account: &Account,
+    code: Vec<u8>,
+    share: Vec<u8>,
+    pk_share: Vec<u8>,
+    identity: Vec<u8>,
+    expected: bool,
+) -> TransactionOutput {
+    let script = Script::new(code, vec![], vec![
+        TransactionArgument::U8Vector(share),
+        TransactionArgument::U8Vector(pk_share),
+        TransactionArgument::U8Vector(identity),
+        TransactionArgument::Bool(expected),
+    ]);
+    let txn = h.create_transaction_payload(account, TransactionPayload::Script(script));
+    h.run_raw(txn)
+}
+
+#[test]
+fn test_verify_timelock_share() {
+    let mut h = MoveHarness::new();
+    let account = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let code = verify_timelock_share_script();
+
+    // A share `h^{f(x_k)}` dealt by the timelock DKG and its public key share `g_2^{f(x_k)}`
+    let pp = das::PublicParameters::default();
+    let f_x = Scalar::from(0x5EED_u64);
+    let dealt_share = pp.get_encryption_public_params().message_base() * f_x;
+    let pk_share = (pp.get_commitment_base() * f_x).to_compressed().to_vec();
+    let identity = compute_timelock_identity(7, 4);
+    let share = derive_timelock_key_share(&dealt_share, &identity)
+        .to_compressed()
+        .to_vec();
+
+    let valid = run_verify_timelock_share(
+        &mut h,
+        &account,
+        code.clone(),
+        share.clone(),
+        pk_share.clone(),
+        identity.as_bytes().to_vec(),
+        true,
+    );
+    assert_success!(valid.status().to_owned());
+
+    // The share of interval 7 does not verify for interval 8
+    let other_identity = compute_timelock_identity(8, 4).as_bytes().to_vec();
+    let invalid = run_verify_timelock_share(
+        &mut h,
+        &account,
+        code.clone(),
+        share,
+        pk_share.clone(),
+        other_identity,
+        false,
+    );
+    assert_success!(invalid.status().to_owned());
+
+    // Nor does a Boneh-Franklin share of the same secret, which is not bound to the DKG bases
+    let bf_share = (hash_identity_to_g1(identity.as_bytes()) * f_x)
+        .to_compressed()
+        .to_vec();
+    let bf_output = run_verify_timelock_share(
+        &mut h,
+        &account,
+        code.clone(),
+        bf_share,
+        pk_share.clone(),
+        identity.as_bytes().to_vec(),
+        false,
+    );
+    assert_success!(bf_output.status().to_owned());
+
+    // The pairings are charged whether or not the share verifies
+    assert!(valid.gas_used() > 0);
+    assert_eq!(valid.gas_used(), invalid.gas_used());
+
+    // Identities are 32-byte hashes
+    let e_ibe_wrong_identity_length = 0x01_0008;
+    let wrong_length = run_verify_timelock_share(
+        &mut h,
+        &account,
+        code,
+        G1Projective::generator().to_compressed().to_vec(),
+        pk_share,
+        vec![0; 31],
+        false,
+    );
+    assert_abort!(
+        wrong_length.status().to_owned(),
+        e_ibe_wrong_identity_length
+    );
+}
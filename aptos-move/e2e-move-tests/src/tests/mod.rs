@@ -33,6 +33,7 @@ mod gas;
 mod generate_upgrade_script;
 mod generic_cmp;
 mod governance_updates;
+mod ibe;
 mod infinite_loop;
 mod init_module;
 mod keyless_feature_gating;
@@ -1,77 +1,725 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_framework::{natives::code::PackageMetadata, BuildOptions, BuiltPackage};
-use aptos_language_e2e_tests::{
-    account::{Account, AccountData},
-    common_transactions::create_user_account,
-    executor::FakeExecutor,
-};
+use aptos_crypto::{bls12381, Uniform};
+use aptos_dkg::ibe::{compute_timelock_identity, derive_timelock_key_share};
+use aptos_language_e2e_tests::executor::FakeExecutor;
 use aptos_types::{
     account_address::AccountAddress,
-    on_chain_config::{FeatureFlag, OnChainConfig},
-    transaction::{Script, Transaction},
-};
-use move_core_types::{
-    identifier::Identifier,
-    language_storage::{ModuleId, TypeTag},
-    value::{MoveValue, SerializeValues},
+    chain_id::ChainId,
+    contract_event::ContractEvent,
+    dkg::{
+        real_dkg::{maybe_dk_from_bls_sk, RealDKG, RealDKGPublicParams},
+        DKGTrait, DKGTranscript, PublicKeyPublishedEvent, RequestRevealEvent, SecretRevealedEvent,
+        ShareReceivedEvent, StartKeyGenEvent, TimelockDKGResult, TimelockKeyGenConfig,
+        TimelockShare, TimelockState,
+    },
+    move_utils::MemberId,
+    on_chain_config::{ConfigurationResource, OnChainConfig, ValidatorSet},
+    transaction::{ExecutionStatus, Transaction, TransactionOutput, TransactionStatus},
+    validator_txn::ValidatorTransaction,
+    validator_verifier::ValidatorVerifier,
 };
+use aptos_vm_genesis::{test_genesis_change_set_and_validators, TestValidator};
+use move_core_types::vm_status::StatusCode;
+use rand::thread_rng;
+use std::str::FromStr;
+
+/// The timelock interval configured at genesis.
+const INTERVAL_MICROSECONDS: u64 = 3600 * 1_000_000;
+
+/// Runs an empty block at `time_microseconds` and returns the events it emitted.
+fn run_block(executor: &mut FakeExecutor, time_microseconds: u64) -> Vec<ContractEvent> {
+    let proposer = *ValidatorSet::fetch_config(executor.get_state_view())
+        .unwrap()
+        .payload()
+        .next()
+        .unwrap()
+        .account_address();
+    let outputs = executor
+        .execute_transaction_block_with_metadata(time_microseconds, proposer, vec![], vec![])
+        .unwrap();
+    executor.set_block_time(time_microseconds);
+    let mut events = vec![];
+    for output in outputs {
+        executor.apply_write_set(output.write_set());
+        events.extend(output.events().iter().cloned());
+    }
+    events
+}
+
+/// The intervals of the reveal requests and key generations in `events`.
+fn rotation_events(events: &[ContractEvent]) -> (Vec<u64>, Vec<StartKeyGenEvent>) {
+    let reveals = events
+        .iter()
+        .filter_map(|event| RequestRevealEvent::try_from(event).ok())
+        .map(|event| event.interval)
+        .collect();
+    let key_gens = events
+        .iter()
+        .filter_map(|event| StartKeyGenEvent::try_from(event).ok())
+        .collect();
+    (reveals, key_gens)
+}
 
 #[test]
 fn test_timelock_initialization_and_events() {
     let mut executor = FakeExecutor::from_head_genesis();
 
-    // 1. Verify TimelockState exists after genesis
-    // Use low-level state view access since we don't have a Rust definition of TimelockState
-    let state_key = move_core_types::state_store::state_key::StateKey::resource(
-        &AccountAddress::ONE,
-        &struct_tag_for_timelock_state(),
+    // Initialized at genesis, before any rotation
+    let state = timelock_state(&executor);
+    assert_eq!(state.current_interval, 0);
+    assert_eq!(state.last_rotation_time, 0);
+
+    // The first block only records the rotation time
+    let start = 1_000_000;
+    let (reveals, key_gens) = rotation_events(&run_block(&mut executor, start));
+    assert!(reveals.is_empty() && key_gens.is_empty());
+    let state = timelock_state(&executor);
+    assert_eq!(state.current_interval, 0);
+    assert_eq!(state.last_rotation_time, start);
+
+    // No rotation until more than an interval has passed
+    let (reveals, key_gens) =
+        rotation_events(&run_block(&mut executor, start + INTERVAL_MICROSECONDS));
+    assert!(reveals.is_empty() && key_gens.is_empty());
+    assert_eq!(timelock_state(&executor).current_interval, 0);
+
+    // Rotating requests the reveal of the old interval and the key generation of the new one
+    let now = start + INTERVAL_MICROSECONDS + 1;
+    let (reveals, key_gens) = rotation_events(&run_block(&mut executor, now));
+    let state = timelock_state(&executor);
+    assert_eq!(state.current_interval, 1);
+    assert_eq!(state.last_rotation_time, now);
+    assert_eq!(reveals, vec![0]);
+    assert_eq!(key_gens.len(), 1);
+    assert_eq!(key_gens[0].interval, 1);
+    assert_eq!(
+        key_gens[0].config,
+        TimelockKeyGenConfig::fetch_config(executor.get_state_view())
+            .unwrap()
+            .config
+    );
+}
+
+#[test]
+fn test_timelock_consecutive_rotations() {
+    let mut executor = FakeExecutor::from_head_genesis();
+    let start = 1_000_000;
+    run_block(&mut executor, start);
+    let first_rotation = start + INTERVAL_MICROSECONDS + 1;
+    run_block(&mut executor, first_rotation);
+    assert_eq!(timelock_state(&executor).current_interval, 1);
+
+    // The next interval is measured from the last rotation, not from the start
+    let (reveals, key_gens) = rotation_events(&run_block(
+        &mut executor,
+        first_rotation + INTERVAL_MICROSECONDS,
+    ));
+    assert!(reveals.is_empty() && key_gens.is_empty());
+    assert_eq!(timelock_state(&executor).current_interval, 1);
+
+    let second_rotation = first_rotation + INTERVAL_MICROSECONDS + 1;
+    let (reveals, key_gens) = rotation_events(&run_block(&mut executor, second_rotation));
+    let state = timelock_state(&executor);
+    assert_eq!(state.current_interval, 2);
+    assert_eq!(state.last_rotation_time, second_rotation);
+    assert_eq!(reveals, vec![1]);
+    assert_eq!(
+        key_gens
+            .iter()
+            .map(|event| event.interval)
+            .collect::<Vec<_>>(),
+        vec![2]
+    );
+}
+
+/// Runs blocks until the first timelock rotation of a fresh executor.
+fn rotate_timelock(executor: &mut FakeExecutor) {
+    // The first block only records the rotation time
+    executor.new_block_with_timestamp(1_000_000);
+    executor.new_block_with_timestamp(1_000_000 + INTERVAL_MICROSECONDS + 1);
+    assert!(timelock_state(executor).current_interval > 0);
+}
+
+/// Runs a block past the current timelock interval, which requests its reveal.
+fn reveal_current_interval(executor: &mut FakeExecutor) {
+    let state = timelock_state(executor);
+    executor.new_block_with_timestamp(state.last_rotation_time + INTERVAL_MICROSECONDS + 1);
+    assert_eq!(
+        timelock_state(executor).current_interval,
+        state.current_interval + 1
+    );
+}
+
+fn timelock_state(executor: &FakeExecutor) -> TimelockState {
+    TimelockState::fetch_config(executor.get_state_view()).expect("TimelockState missing")
+}
+
+/// Returns an executor whose genesis has a single validator, along with its consensus key.
+fn executor_with_validator() -> (FakeExecutor, bls12381::PrivateKey) {
+    let (genesis, mut validators) = test_genesis_change_set_and_validators(Some(1));
+    let executor = FakeExecutor::from_genesis(genesis.write_set(), ChainId::test());
+    (executor, validators.pop().unwrap().consensus_key)
+}
+
+/// The public parameters of the timelock DKG for `interval` in the current epoch, rebuilt from
+/// on-chain state the way the VM does.
+fn timelock_pub_params(executor: &FakeExecutor, interval: u64) -> RealDKGPublicParams {
+    let state_view = executor.get_state_view();
+    let epoch = ConfigurationResource::fetch_config(state_view)
+        .unwrap()
+        .epoch();
+    let verifier = ValidatorVerifier::from(&ValidatorSet::fetch_config(state_view).unwrap());
+    let config = TimelockKeyGenConfig::fetch_config(state_view)
+        .expect("KeyGenConfig missing")
+        .config
+        .scaled_to(verifier.len() as u64);
+    RealDKG::new_timelock_public_params(&config.session_metadata(epoch, &verifier), interval)
+}
+
+/// The index of `validator` in the DKG session of `pub_params`, as a dealer and as a player.
+fn player_index(pub_params: &RealDKGPublicParams, validator: &TestValidator) -> usize {
+    pub_params
+        .session_metadata
+        .dealer_validator_set
+        .iter()
+        .position(|info| info.addr == validator.data.owner_address)
+        .expect("not a validator of the session")
+}
+
+/// `transcript` as published by the first validator of the session of `pub_params`.
+fn dkg_transcript(
+    pub_params: &RealDKGPublicParams,
+    transcript: &<RealDKG as DKGTrait>::Transcript,
+) -> DKGTranscript {
+    DKGTranscript::new(
+        pub_params.session_metadata.dealer_epoch,
+        pub_params.session_metadata.dealer_validator_set[0].addr,
+        bcs::to_bytes(transcript).unwrap(),
+    )
+}
+
+/// A transcript for `interval` in the current epoch, dealt by the first validator signing with
+/// `dealer_sk`.
+fn dealt_transcript(
+    executor: &FakeExecutor,
+    dealer_sk: &bls12381::PrivateKey,
+    interval: u64,
+) -> DKGTranscript {
+    let pub_params = timelock_pub_params(executor, interval);
+    let transcript = RealDKG::sample_secret_and_generate_transcript(
+        &mut thread_rng(),
+        &pub_params,
+        0,
+        dealer_sk,
+    );
+    dkg_transcript(&pub_params, &transcript)
+}
+
+/// Runs the timelock DKG for `interval` in the current epoch, in which every one of `validators`
+/// deals. Returns its public parameters and aggregated transcript.
+fn run_key_gen(
+    executor: &FakeExecutor,
+    validators: &[TestValidator],
+    interval: u64,
+) -> (RealDKGPublicParams, <RealDKG as DKGTrait>::Transcript) {
+    let pub_params = timelock_pub_params(executor, interval);
+    let mut transcripts = validators.iter().map(|validator| {
+        RealDKG::sample_secret_and_generate_transcript(
+            &mut thread_rng(),
+            &pub_params,
+            player_index(&pub_params, validator) as u64,
+            &validator.consensus_key,
+        )
+    });
+    let mut transcript = transcripts.next().expect("no dealer");
+    for other in transcripts {
+        RealDKG::aggregate_transcripts(&pub_params, &mut transcript, other);
+    }
+    (pub_params, transcript)
+}
+
+/// The share of `validator` for `interval`, signed with its consensus key.
+fn sign_share(validator: &TestValidator, interval: u64, share: Vec<u8>) -> TimelockShare {
+    TimelockShare::sign(
+        validator.data.owner_address,
+        interval,
+        share,
+        &validator.consensus_key,
+    )
+    .unwrap()
+}
+
+/// The decryption key share of `validator` for `interval`, derived from its secret share of
+/// `transcript` the way validators do, and signed.
+fn derive_share(
+    pub_params: &RealDKGPublicParams,
+    transcript: &<RealDKG as DKGTrait>::Transcript,
+    validator: &TestValidator,
+    interval: u64,
+) -> TimelockShare {
+    let dk = maybe_dk_from_bls_sk(&validator.consensus_key).unwrap();
+    let (secret_share, _) = RealDKG::decrypt_secret_share_from_transcript(
+        pub_params,
+        transcript,
+        player_index(pub_params, validator) as u64,
+        &dk,
+    )
+    .unwrap();
+    let identity = compute_timelock_identity(interval, ChainId::test().id());
+    let share = secret_share
+        .main
+        .iter()
+        .flat_map(|sub_share| {
+            derive_timelock_key_share(sub_share.as_group_element(), &identity).to_compressed()
+        })
+        .collect();
+    sign_share(validator, interval, share)
+}
+
+/// Returns an executor whose genesis has `num_validators` validators, which published the key of
+/// an interval through a DKG they all dealt and then requested its reveal. Returns the validators,
+/// the interval and the decryption key share of each validator for it.
+fn executor_with_revealed_interval(
+    num_validators: usize,
+) -> (FakeExecutor, Vec<TestValidator>, u64, Vec<TimelockShare>) {
+    let (genesis, validators) = test_genesis_change_set_and_validators(Some(num_validators));
+    let mut executor = FakeExecutor::from_genesis(genesis.write_set(), ChainId::test());
+    rotate_timelock(&mut executor);
+    let interval = timelock_state(&executor).current_interval;
+    let (pub_params, transcript) = run_key_gen(&executor, &validators, interval);
+    let output = execute_timelock_dkg_result(
+        &executor,
+        interval,
+        dkg_transcript(&pub_params, &transcript),
+    );
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    executor.apply_write_set(output.write_set());
+    reveal_current_interval(&mut executor);
+
+    let shares = validators
+        .iter()
+        .map(|validator| derive_share(&pub_params, &transcript, validator, interval))
+        .collect();
+    (executor, validators, interval, shares)
+}
+
+fn timelock_share_txn(share: TimelockShare) -> Transaction {
+    Transaction::ValidatorTransaction(ValidatorTransaction::TimelockShare(share))
+}
+
+fn execute_timelock_share(executor: &FakeExecutor, share: TimelockShare) -> TransactionOutput {
+    executor
+        .execute_transaction_block(vec![timelock_share_txn(share)])
+        .expect("an invalid share must not fail the block")
+        .pop()
+        .unwrap()
+}
+
+fn execute_timelock_dkg_result(
+    executor: &FakeExecutor,
+    interval: u64,
+    transcript: DKGTranscript,
+) -> TransactionOutput {
+    let txn = Transaction::ValidatorTransaction(ValidatorTransaction::TimelockDKGResult(
+        TimelockDKGResult {
+            interval,
+            transcript,
+        },
+    ));
+    executor
+        .execute_transaction_block(vec![txn])
+        .expect("an invalid DKG result must not fail the block")
+        .pop()
+        .unwrap()
+}
+
+fn timelock_public_key(executor: &mut FakeExecutor, interval: u64) -> Option<Vec<u8>> {
+    timelock_bytes_view(executor, "get_public_key", vec![
+        bcs::to_bytes(&interval).unwrap()
+    ])
+}
+
+fn timelock_secret(executor: &mut FakeExecutor, interval: u64) -> Option<Vec<u8>> {
+    timelock_bytes_view(executor, "get_secret", vec![
+        bcs::to_bytes(&interval).unwrap()
+    ])
+}
+
+fn timelock_share(
+    executor: &mut FakeExecutor,
+    interval: u64,
+    validator: AccountAddress,
+) -> Option<Vec<u8>> {
+    timelock_bytes_view(executor, "get_share", vec![
+        bcs::to_bytes(&interval).unwrap(),
+        bcs::to_bytes(&validator).unwrap(),
+    ])
+}
+
+/// Calls the `0x1::timelock` view `function`, which returns optional bytes, with the BCS `args`.
+fn timelock_bytes_view(
+    executor: &mut FakeExecutor,
+    function: &str,
+    args: Vec<Vec<u8>>,
+) -> Option<Vec<u8>> {
+    let value = executor
+        .execute_view_function(
+            MemberId::from_str(&format!("0x1::timelock::{}", function)).unwrap(),
+            vec![],
+            args,
+        )
+        .values
+        .unwrap()
+        .pop()
+        .unwrap();
+    bcs::from_bytes(&value).unwrap()
+}
+
+fn assert_discarded(output: &TransactionOutput) {
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Discard(StatusCode::ABORTED)
+    );
+    assert!(output.write_set().is_empty());
+}
+
+fn share_received_events(output: &TransactionOutput) -> Vec<ShareReceivedEvent> {
+    output
+        .events()
+        .iter()
+        .filter_map(|event| ShareReceivedEvent::try_from(event).ok())
+        .collect()
+}
+
+fn secrets_revealed(output: &TransactionOutput) -> Vec<u64> {
+    output
+        .events()
+        .iter()
+        .filter_map(|event| SecretRevealedEvent::try_from(event).ok())
+        .map(|event| event.interval)
+        .collect()
+}
+
+/// The number of decryption key sub-shares in `share`, which the threshold weight counts.
+fn num_key_shares(share: &TimelockShare) -> u64 {
+    (share.share.len() / 48) as u64
+}
+
+#[test]
+fn test_invalid_timelock_shares_discarded() {
+    let (executor, validators, interval, shares) = executor_with_revealed_interval(1);
+    let validator = &validators[0];
+
+    // x = 1 is not the x-coordinate of a point on the curve
+    let mut not_on_curve = vec![0u8; 48];
+    not_on_curve[0] = 0x80;
+    not_on_curve[47] = 1;
+    for share in [vec![7u8; 47], vec![7u8; 1024], not_on_curve] {
+        assert_discarded(&execute_timelock_share(
+            &executor,
+            sign_share(validator, interval, share),
+        ));
+    }
+
+    // The current interval has not been revealed yet
+    let share = shares[0].share.clone();
+    assert_discarded(&execute_timelock_share(
+        &executor,
+        sign_share(validator, interval + 1, share),
+    ));
+
+    let output = execute_timelock_share(&executor, shares[0].clone());
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+}
+
+#[test]
+fn test_unverifiable_timelock_shares_discarded() {
+    let (mut executor, validators, interval, shares) = executor_with_revealed_interval(2);
+    let author = &validators[0];
+    let num_sub_shares = shares[0].share.len() / 48;
+    // A BLS12-381 G1 point (compressed), which is not the author's key share
+    let point = bls12381::PublicKey::from(&bls12381::PrivateKey::generate_for_testing())
+        .to_bytes()
+        .to_vec();
+
+    // Signed by a key that is not the author's
+    let wrong_signer = TimelockShare::sign(
+        author.data.owner_address,
+        interval,
+        shares[0].share.clone(),
+        &bls12381::PrivateKey::generate_for_testing(),
     )
-    .expect("failed to create StateKey");
-
-    let bytes_opt = executor
-        .get_state_view()
-        .get_state_value_bytes(&state_key)
-        .expect("storage error");
-
-    assert!(
-        bytes_opt.is_some(),
-        "TimelockState should be initialized at genesis"
-    );
-
-    // 2. Advance time by > 1 hour (3600 seconds)
-    // FakeExecutor uses block time 0 by default.
-    let now = 3600 * 1_000_000 + 1;
-    executor.set_block_time(now);
-
-    // Execute a block prologue to trigger on_new_block
-    executor.new_block();
-
-    // 3. Check for events or state change
-    // Since we verified existence, and we know on_new_block emits keygen event and updates interval,
-    // we assume logic holds if no panic.
-    // Further decoding would require defining the Rust struct for TimelockState.
-    let bytes_after = executor
-        .get_state_view()
-        .get_state_value_bytes(&state_key)
-        .expect("storage error")
-        .expect("resource missing after rotation");
-
-    // Simple check: bytes changed implies state update (interval incremented)
-    // Note: this is a weak check but sufficient for "integration" proof that code runs.
-    assert_ne!(
-        bytes_opt.unwrap(),
-        bytes_after,
-        "TimelockState should have updated (interval++ and last_rotation_time)"
-    );
-}
-
-fn struct_tag_for_timelock_state() -> move_core_types::language_storage::StructTag {
-    move_core_types::language_storage::StructTag {
-        address: AccountAddress::ONE,
-        module: Identifier::new("timelock").unwrap(),
-        name: Identifier::new("TimelockState").unwrap(),
-        type_params: vec![],
+    .unwrap();
+    let mut tampered = shares[0].clone();
+    tampered.share = point.repeat(num_sub_shares);
+    // Signed by the author, but not its key shares for the interval
+    let forged = sign_share(author, interval, point.repeat(num_sub_shares));
+    let other_validators = sign_share(author, interval, shares[1].share.clone());
+    let missing_sub_share = sign_share(author, interval, shares[0].share[48..].to_vec());
+    // No key was published for the previous interval, so nothing to verify against
+    let no_public_key = sign_share(author, interval - 1, shares[0].share.clone());
+    for share in [
+        wrong_signer,
+        tampered,
+        forged,
+        other_validators,
+        missing_sub_share,
+        no_public_key,
+    ] {
+        assert_discarded(&execute_timelock_share(&executor, share));
+    }
+    assert!(timelock_share(&mut executor, interval, author.data.owner_address).is_none());
+
+    let output = execute_timelock_share(&executor, shares[0].clone());
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    executor.apply_write_set(output.write_set());
+    assert_eq!(
+        timelock_share(&mut executor, interval, author.data.owner_address),
+        Some(shares[0].share.clone())
+    );
+}
+
+#[test]
+fn test_duplicate_timelock_shares_are_no_ops() {
+    let (mut executor, _, _, shares) = executor_with_revealed_interval(1);
+    let txn = timelock_share_txn(shares[0].clone());
+
+    // Within a block
+    let outputs = executor
+        .execute_transaction_block(vec![txn.clone(), txn.clone()])
+        .expect("a duplicate share must not fail the block");
+    assert_eq!(
+        outputs[0].status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    assert_discarded(&outputs[1]);
+    executor.apply_write_set(outputs[0].write_set());
+
+    // Across blocks
+    let output = executor
+        .execute_transaction_block(vec![txn])
+        .expect("a duplicate share must not fail the block")
+        .pop()
+        .unwrap();
+    assert_discarded(&output);
+}
+
+#[test]
+fn test_timelock_share_events_count_towards_threshold() {
+    let (mut executor, validators, interval, shares) = executor_with_revealed_interval(2);
+    let pub_params = timelock_pub_params(&executor, interval);
+    let threshold = pub_params.threshold_weight() as u64;
+
+    let first_count = num_key_shares(&shares[0]);
+    assert!(first_count < threshold && first_count + num_key_shares(&shares[1]) >= threshold);
+    let outputs = executor
+        .execute_transaction_block(vec![
+            timelock_share_txn(shares[0].clone()),
+            timelock_share_txn(shares[0].clone()),
+            timelock_share_txn(shares[1].clone()),
+        ])
+        .unwrap();
+    assert_eq!(share_received_events(&outputs[0]), vec![
+        ShareReceivedEvent {
+            interval,
+            validator: validators[0].data.owner_address,
+            shares_received_so_far: first_count,
+            threshold,
+            aggregation_complete: false,
+        }
+    ]);
+    assert!(secrets_revealed(&outputs[0]).is_empty());
+    // A duplicate share is discarded without an event
+    assert_discarded(&outputs[1]);
+    assert!(share_received_events(&outputs[1]).is_empty());
+    assert_eq!(share_received_events(&outputs[2]), vec![
+        ShareReceivedEvent {
+            interval,
+            validator: validators[1].data.owner_address,
+            shares_received_so_far: first_count + num_key_shares(&shares[1]),
+            threshold,
+            aggregation_complete: true,
+        }
+    ]);
+    assert_eq!(secrets_revealed(&outputs[2]), vec![interval]);
+
+    // The secret aggregated on-chain is the one the validators' shares reconstruct
+    for output in &outputs {
+        executor.apply_write_set(output.write_set());
     }
+    let key_shares: Vec<_> = validators
+        .iter()
+        .zip(&shares)
+        .map(|(validator, share)| {
+            let player = player_index(&pub_params, validator) as u64;
+            (player, share.share.clone())
+        })
+        .collect();
+    assert_eq!(
+        timelock_secret(&mut executor, interval),
+        Some(RealDKG::reconstruct_timelock_key(&pub_params, &key_shares).unwrap())
+    );
+}
+
+#[test]
+fn test_timelock_dkg_result_published_for_its_interval() {
+    let (mut executor, consensus_key) = executor_with_validator();
+    rotate_timelock(&mut executor);
+    reveal_current_interval(&mut executor);
+    let interval = timelock_state(&executor).current_interval;
+    // Requested by the previous rotation, but its key never landed
+    let missed = interval - 1;
+
+    // An interval far ahead of the current one, whose key generation was never requested
+    let far_future = interval + 12345;
+    let transcript = dealt_transcript(&executor, &consensus_key, far_future);
+    assert_discarded(&execute_timelock_dkg_result(
+        &executor, far_future, transcript,
+    ));
+
+    // A transcript dealt for the current interval does not verify for another one
+    let transcript = dealt_transcript(&executor, &consensus_key, interval);
+    assert_discarded(&execute_timelock_dkg_result(
+        &executor,
+        missed,
+        transcript.clone(),
+    ));
+
+    let dealer = transcript.metadata.author;
+    let output = execute_timelock_dkg_result(&executor, interval, transcript);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    let events: Vec<_> = output
+        .events()
+        .iter()
+        .filter_map(|event| PublicKeyPublishedEvent::try_from(event).ok())
+        .collect();
+    assert_eq!(events, vec![PublicKeyPublishedEvent {
+        interval,
+        validator: dealer,
+    }]);
+    executor.apply_write_set(output.write_set());
+    assert!(timelock_public_key(&mut executor, interval).is_some());
+    assert!(timelock_public_key(&mut executor, missed).is_none());
+    assert!(timelock_public_key(&mut executor, far_future).is_none());
+
+    // The missed interval still gets its key, but only once
+    let transcript = dealt_transcript(&executor, &consensus_key, missed);
+    let output = execute_timelock_dkg_result(&executor, missed, transcript);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    executor.apply_write_set(output.write_set());
+    let missed_key = timelock_public_key(&mut executor, missed);
+    assert!(missed_key.is_some());
+    let transcript = dealt_transcript(&executor, &consensus_key, missed);
+    assert_discarded(&execute_timelock_dkg_result(&executor, missed, transcript));
+    assert_eq!(timelock_public_key(&mut executor, missed), missed_key);
+}
+
+#[test]
+fn test_unverifiable_timelock_dkg_results_discarded() {
+    let (mut executor, consensus_key) = executor_with_validator();
+    rotate_timelock(&mut executor);
+    let interval = timelock_state(&executor).current_interval;
+    let transcript = dealt_transcript(&executor, &consensus_key, interval);
+
+    let mut not_a_transcript = transcript.clone();
+    not_a_transcript.transcript_bytes = vec![1, 2, 3];
+    // Signed by a key that is not the dealer's
+    let forged = dealt_transcript(
+        &executor,
+        &bls12381::PrivateKey::generate_for_testing(),
+        interval,
+    );
+    let mut wrong_epoch = transcript.clone();
+    wrong_epoch.metadata.epoch += 1;
+
+    for transcript in [not_a_transcript, forged, wrong_epoch] {
+        let output = execute_timelock_dkg_result(&executor, interval, transcript);
+        assert_discarded(&output);
+        executor.apply_write_set(output.write_set());
+        assert!(timelock_public_key(&mut executor, interval).is_none());
+    }
+
+    let output = execute_timelock_dkg_result(&executor, interval, transcript);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    executor.apply_write_set(output.write_set());
+    assert!(timelock_public_key(&mut executor, interval).is_some());
+}
+
+#[test]
+fn test_timelock_key_and_share_published_through_validator_txns() {
+    let (genesis, validators) = test_genesis_change_set_and_validators(Some(1));
+    let mut executor = FakeExecutor::from_genesis(genesis.write_set(), ChainId::test());
+    rotate_timelock(&mut executor);
+    let interval = timelock_state(&executor).current_interval;
+
+    // The key of the current interval, dealt by the only validator
+    let (pub_params, transcript) = run_key_gen(&executor, &validators, interval);
+    let dealer = validators[0].data.owner_address;
+    let dealt_public_key = transcript.dealt_public_key_bytes();
+    let output = execute_timelock_dkg_result(
+        &executor,
+        interval,
+        dkg_transcript(&pub_params, &transcript),
+    );
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    executor.apply_write_set(output.write_set());
+    // BLS12-381 G2 point (compressed)
+    assert_eq!(dealt_public_key.len(), 96);
+    assert_eq!(
+        timelock_public_key(&mut executor, interval),
+        Some(dealt_public_key)
+    );
+    assert!(timelock_share(&mut executor, interval, dealer).is_none());
+    assert!(timelock_secret(&mut executor, interval).is_none());
+
+    // Its share, once the next rotation requests the reveal
+    reveal_current_interval(&mut executor);
+    let share = derive_share(&pub_params, &transcript, &validators[0], interval);
+    let output = execute_timelock_share(&executor, share.clone());
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    // The only validator holds enough weight to aggregate the secret alone
+    assert_eq!(share_received_events(&output), vec![ShareReceivedEvent {
+        interval,
+        validator: dealer,
+        shares_received_so_far: num_key_shares(&share),
+        threshold: pub_params.threshold_weight() as u64,
+        aggregation_complete: true,
+    }]);
+    assert_eq!(secrets_revealed(&output), vec![interval]);
+    executor.apply_write_set(output.write_set());
+    // Published as is, BLS12-381 G1 points (compressed), and aggregated into the secret
+    assert_eq!(
+        timelock_share(&mut executor, interval, dealer),
+        Some(share.share.clone())
+    );
+    assert_eq!(
+        timelock_secret(&mut executor, interval),
+        Some(RealDKG::reconstruct_timelock_key(&pub_params, &[(0, share.share)]).unwrap())
+    );
 }
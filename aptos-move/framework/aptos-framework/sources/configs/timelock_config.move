@@ -31,12 +31,18 @@ module aptos_framework::timelock_config {
     }
 
     /// Initialize with default 1-hour interval.
-    /// Called during genesis to set up the timelock configuration.
     public(friend) fun initialize(framework: &signer) {
+        initialize_with_interval(framework, 3600 * 1000000); // 1 hour default
+    }
+
+    /// Initialize with the given interval.
+    /// Called during genesis to set up the timelock configuration, so that test networks can
+    /// start with short intervals.
+    public(friend) fun initialize_with_interval(framework: &signer, interval_us: u64) {
         system_addresses::assert_aptos_framework(framework);
         if (!exists<TimelockConfig>(@aptos_framework)) {
             move_to(framework, TimelockConfig {
-                interval_microseconds: 3600 * 1000000, // 1 hour default
+                interval_microseconds: interval_us,
             });
         }
     }
@@ -98,6 +104,16 @@ module aptos_framework::timelock_config {
         assert!(get_interval_microseconds() == 3600 * 1000000, 0);
     }
 
+    #[test(framework = @aptos_framework)]
+    fun test_initialize_with_interval(framework: &signer) acquires TimelockConfig {
+        initialize_with_interval(framework, 5 * 1000000);
+        assert!(get_interval_microseconds() == 5 * 1000000, 0);
+
+        // Already initialized
+        initialize(framework);
+        assert!(get_interval_microseconds() == 5 * 1000000, 1);
+    }
+
     #[test(framework = @aptos_framework)]
     fun test_set_for_testing(framework: &signer) acquires TimelockConfig {
         chain_id::initialize_for_test(framework, 4); // testnet
@@ -26,8 +26,6 @@ module aptos_framework::genesis {
     use aptos_framework::state_storage;
     use aptos_framework::storage_gas;
     use aptos_framework::timestamp;
-    use aptos_framework::timelock;
-    use aptos_framework::timelock_config;
     use aptos_framework::transaction_fee;
     use aptos_framework::transaction_validation;
     use aptos_framework::version;
@@ -132,8 +130,6 @@ module aptos_framework::genesis {
         chain_id::initialize(&aptos_framework_account, chain_id);
         reconfiguration::initialize(&aptos_framework_account);
         block::initialize(&aptos_framework_account, epoch_interval_microsecs);
-        timelock::initialize(&aptos_framework_account);
-        timelock_config::initialize(&aptos_framework_account);
         state_storage::initialize(&aptos_framework_account);
         nonce_validation::initialize(&aptos_framework_account);
     }
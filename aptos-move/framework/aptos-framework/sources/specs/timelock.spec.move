@@ -33,13 +33,4 @@ spec aptos_framework::timelock {
         // Complex logic around time and table operations
         // For PoC verification, we focus on safety vs availability
     }
-
-    spec publish_public_key {
-        // TODO: access control spec
-        // pragma verify = false; // Until access control is implemented
-    }
-
-    spec publish_secret_share {
-        // pragma verify = false;
-    }
 }
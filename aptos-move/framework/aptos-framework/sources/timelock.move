@@ -1,7 +1,15 @@
 module aptos_framework::timelock {
 
+    use std::bcs;
+    use std::error;
     use std::option::{Self, Option};
+    use std::signer;
+    use aptos_std::aptos_hash;
+    use aptos_std::bls12381_algebra::{FormatFrLsb, FormatG1Compr, FormatG2Compr, Fr, G1, G2, Gt};
+    use aptos_std::crypto_algebra;
+    use aptos_std::ibe;
     use aptos_std::table::{Self, Table};
+    use aptos_framework::chain_id;
     use aptos_framework::event::{Self, EventHandle};
     use aptos_framework::timestamp;
     use aptos_framework::system_addresses;
@@ -11,11 +19,21 @@ module aptos_framework::timelock {
     friend aptos_framework::block;
     friend aptos_framework::genesis;
 
-    #[test_only]
     use std::vector;
 
     /// The singleton was not initialized.
     const ETIMELOCK_NOT_INITIALIZED: u64 = 1;
+    /// The validators and their public key shares or evaluation points do not match up.
+    const EPUBLIC_KEY_SHARES_LENGTH_MISMATCH: u64 = 2;
+    /// The share does not verify against the public key shares dealt to its validator.
+    const EINVALID_SHARE: u64 = 3;
+
+    /// The length of a compressed BLS12-381 G1 point: one decryption key share.
+    const G1_COMPRESSED_NUM_BYTES: u64 = 48;
+    /// The length of a compressed BLS12-381 G2 point: one public key share.
+    const G2_COMPRESSED_NUM_BYTES: u64 = 96;
+    /// The length of a serialized BLS12-381 scalar: one evaluation point.
+    const FR_NUM_BYTES: u64 = 32;
 
     struct TimelockConfig has copy, drop, store {
         threshold: u64,
@@ -27,13 +45,50 @@ module aptos_framework::timelock {
         last_rotation_time: u64,
         /// Store public keys (for encryption)
         public_keys: Table<u64, vector<u8>>,
-        /// Store revealed secret keys/signatures (for decryption)
+        /// Store revealed secrets, aggregated from the validators' shares (for decryption)
         revealed_secrets: Table<u64, vector<u8>>,
         /// Events
         start_keygen_events: EventHandle<StartKeyGenEvent>,
         request_reveal_events: EventHandle<RequestRevealEvent>,
     }
 
+    /// The config the DKG of every new interval is requested with: `threshold` out of every
+    /// `total_validators` validators reconstruct the secret of an interval. The VM scales it to the
+    /// validator set of the current epoch to rebuild the DKG session and verify the published
+    /// transcript against it.
+    struct KeyGenConfig has key {
+        config: TimelockConfig,
+    }
+
+    /// Identifies the share of a validator for an interval.
+    struct ShareKey has copy, drop, store {
+        interval: u64,
+        validator: address,
+    }
+
+    /// The shares published by each validator, so that a validator's share for an interval only
+    /// counts once however many times it is submitted, and what the DKG of each interval dealt to
+    /// verify and aggregate them.
+    ///
+    /// A validator's share holds one decryption key share per unit of its weight in the DKG.
+    struct ValidatorShares has key {
+        shares: Table<ShareKey, vector<u8>>,
+        /// Number of decryption key shares received, per interval.
+        share_counts: Table<u64, u64>,
+        /// The public key shares dealt to each validator by the DKG of an interval, as
+        /// concatenated 96-byte compressed G2 points. The VM verifies a validator's share for the
+        /// interval against them.
+        public_key_shares: Table<ShareKey, vector<u8>>,
+        /// The evaluation points of the key shares dealt to each validator by the DKG of an
+        /// interval, as concatenated 32-byte little-endian scalars. Its decryption key shares are
+        /// interpolated at them.
+        evaluation_points: Table<ShareKey, vector<u8>>,
+        /// Number of decryption key shares the secret of an interval is aggregated from.
+        thresholds: Table<u64, u64>,
+        /// The validators that published a share, per interval, in the order they did.
+        validators: Table<u64, vector<address>>,
+    }
+
     /// Event emitted to tell validators: "Please generate keys for interval X"
     struct StartKeyGenEvent has drop, store {
         interval: u64,
@@ -45,6 +100,32 @@ module aptos_framework::timelock {
         interval: u64,
     }
 
+    #[event]
+    /// Event emitted when the secret of an interval is aggregated and stored.
+    struct SecretRevealedEvent has drop, store {
+        interval: u64,
+    }
+
+    #[event]
+    /// Event emitted when the public key for an interval is stored.
+    struct PublicKeyPublishedEvent has drop, store {
+        interval: u64,
+        validator: address,
+    }
+
+    #[event]
+    /// Event emitted when a validator's share for an interval is accepted, with the number of
+    /// decryption key shares received for the interval so far and the number needed to aggregate
+    /// the secret.
+    struct ShareReceivedEvent has drop, store {
+        interval: u64,
+        validator: address,
+        shares_received_so_far: u64,
+        threshold: u64,
+        /// Whether the secret of the interval is aggregated.
+        aggregation_complete: bool,
+    }
+
     /// Initialize the timelock system.
     public(friend) fun initialize(framework: &signer) {
         system_addresses::assert_aptos_framework(framework);
@@ -56,13 +137,29 @@ module aptos_framework::timelock {
             start_keygen_events: account::new_event_handle<StartKeyGenEvent>(framework),
             request_reveal_events: account::new_event_handle<RequestRevealEvent>(framework),
         });
+        // Two thirds of the validators, rounded up, like the reconstruction threshold of the
+        // randomness DKG
+        move_to(framework, KeyGenConfig {
+            config: TimelockConfig {
+                threshold: 2,
+                total_validators: 3,
+            },
+        });
+        move_to(framework, ValidatorShares {
+            shares: table::new(),
+            share_counts: table::new(),
+            public_key_shares: table::new(),
+            evaluation_points: table::new(),
+            thresholds: table::new(),
+            validators: table::new(),
+        });
     }
 
     /// Called by block prologue to trigger rotations.
-    public(friend) fun on_new_block(vm: &signer) acquires TimelockState {
+    public(friend) fun on_new_block(vm: &signer) acquires TimelockState, KeyGenConfig {
         system_addresses::assert_vm(vm);
 
-        if (!exists<TimelockState>(@aptos_framework)) {
+        if (!exists<TimelockState>(@aptos_framework) || !exists<KeyGenConfig>(@aptos_framework)) {
             return
         };
 
@@ -87,13 +184,7 @@ module aptos_framework::timelock {
             state.current_interval = state.current_interval + 1;
             state.last_rotation_time = now;
 
-            // TODO: In a real implementation, we would get the actual validator set size/threshold.
-            // For this PoC, we'll hardcode or placeholders.
-            // Let's assume a fixed threshold for now or just emit the event.
-            let config = TimelockConfig {
-                threshold: 1, // Placeholder
-                total_validators: 1, // Placeholder
-            };
+            let config = borrow_global<KeyGenConfig>(@aptos_framework).config;
 
             event::emit_event(&mut state.start_keygen_events, StartKeyGenEvent {
                 interval: state.current_interval,
@@ -102,48 +193,162 @@ module aptos_framework::timelock {
         }
     }
 
-    /// validators call this to publish the public key for a future interval
-    public entry fun publish_public_key(
-        _validator: &signer,
+    /// Publish the public key for a future interval, with the public key shares and evaluation
+    /// points dealt to each of `validators` and the number of decryption key shares its secret
+    /// is aggregated from.
+    ///
+    /// Only called by the VM, on behalf of `validator`, for a timelock DKG result validator
+    /// transaction whose transcript it verified.
+    fun publish_public_key(
+        validator: &signer,
         interval: u64,
-        pk: vector<u8>
-    ) acquires TimelockState {
-        // TODO: Verify sender is a validator
-        // In this minimal PoC, we trust the sender for now or assume the VM only allows valid calls via ValidatorTxn
-        // BUT `public entry` means anyone can call it?
-        // The plan says "Submit 0x1::timelock::publish_secret_share" via ValidatorTransaction.
-        // If it comes via ValidatorTransaction, it should be a governance/system transaction, but `entry` allows user calls.
-        
-        // Use system_addresses or relevant checks if strictly required.
-        // For PoC, let's keep it simple but functional.
-        
+        pk: vector<u8>,
+        threshold: u64,
+        validators: vector<address>,
+        public_key_shares: vector<vector<u8>>,
+        evaluation_points: vector<vector<u8>>,
+    ) acquires TimelockState, ValidatorShares {
+        assert!(
+            vector::length(&validators) == vector::length(&public_key_shares)
+                && vector::length(&validators) == vector::length(&evaluation_points),
+            error::invalid_argument(EPUBLIC_KEY_SHARES_LENGTH_MISMATCH)
+        );
         let state = borrow_global_mut<TimelockState>(@aptos_framework);
-        if (!table::contains(&state.public_keys, interval)) {
-            table::add(&mut state.public_keys, interval, pk);
+        if (table::contains(&state.public_keys, interval)) {
+            return
+        };
+        table::add(&mut state.public_keys, interval, pk);
+        if (exists<ValidatorShares>(@aptos_framework)) {
+            let validator_shares = borrow_global_mut<ValidatorShares>(@aptos_framework);
+            table::add(&mut validator_shares.thresholds, interval, threshold);
+            vector::zip_ref(&validators, &public_key_shares, |validator, public_key_share| {
+                let key = ShareKey { interval, validator: *validator };
+                table::add(&mut validator_shares.public_key_shares, key, *public_key_share);
+            });
+            vector::zip(validators, evaluation_points, |validator, evaluation_point| {
+                let key = ShareKey { interval, validator };
+                table::add(&mut validator_shares.evaluation_points, key, evaluation_point);
+            });
         };
+        event::emit(PublicKeyPublishedEvent { interval, validator: signer::address_of(validator) });
     }
 
-    /// validators call this to publish the secret share/signature for a past interval
-    public entry fun publish_secret_share(
-        _validator: &signer,
+    /// Publish the secret share of `validator` for a past interval. Once the shares received for
+    /// the interval reach its threshold, aggregates them into its secret.
+    ///
+    /// Only called by the VM, for a timelock share validator transaction whose share it validated.
+    /// The VM discards shares that do not verify before calling this; the check here keeps one
+    /// from ever being stored, and so from being aggregated.
+    fun publish_secret_share(
+        validator: &signer,
         interval: u64,
         share: vector<u8>
-    ) acquires TimelockState {
-        // TODO: Implement proper BLS signature aggregation in Phase 2
-        // Current behavior: Store first share only (placeholder)
-        // Real implementation needs to:
-        // 1. Verify validator authorization (via ValidatorTransaction context)
-        // 2. Verify BLS signature is valid for the interval identity
-        // 3. Collect shares from multiple validators
-        // 4. Once threshold reached, aggregate G1 points to compute final decryption key
-        // 5. Store aggregated key in revealed_secrets table
-        //
-        // For now, we just store the first share to allow basic testing
+    ) acquires TimelockState, ValidatorShares {
+        // A validator's share for an interval only counts once
+        let validator_address = signer::address_of(validator);
+        let validator_shares = borrow_global_mut<ValidatorShares>(@aptos_framework);
+        let key = ShareKey { interval, validator: validator_address };
+        if (table::contains(&validator_shares.shares, key)) {
+            return
+        };
+        assert!(
+            table::contains(&validator_shares.public_key_shares, key)
+                && is_share_valid(interval, &share, table::borrow(&validator_shares.public_key_shares, key)),
+            error::invalid_argument(EINVALID_SHARE)
+        );
+        let num_key_shares = vector::length(&share) / G1_COMPRESSED_NUM_BYTES;
+        table::add(&mut validator_shares.shares, key, share);
+        let count = table::borrow_mut_with_default(&mut validator_shares.share_counts, interval, 0);
+        *count = *count + num_key_shares;
+        let shares_received_so_far = *count;
+        let validators = table::borrow_mut_with_default(&mut validator_shares.validators, interval, vector[]);
+        vector::push_back(validators, validator_address);
+
+        let threshold = *table::borrow(&validator_shares.thresholds, interval);
+        let aggregation_complete = shares_received_so_far >= threshold;
         let state = borrow_global_mut<TimelockState>(@aptos_framework);
-         if (!table::contains(&state.revealed_secrets, interval)) {
-            table::add(&mut state.revealed_secrets, interval, share);
+        if (aggregation_complete && !table::contains(&state.revealed_secrets, interval)) {
+            let secret = aggregate_secret(validator_shares, interval, threshold);
+            table::add(&mut state.revealed_secrets, interval, secret);
+            event::emit(SecretRevealedEvent { interval });
         };
-        // TODO: Otherwise, aggregate with existing shares (BLS aggregation)
+        event::emit(ShareReceivedEvent {
+            interval,
+            validator: validator_address,
+            shares_received_so_far,
+            threshold,
+            aggregation_complete,
+        });
+    }
+
+    /// Aggregates the secret of `interval` from the first `threshold` decryption key shares
+    /// published for it, each interpolated at the evaluation point it was dealt at.
+    fun aggregate_secret(validator_shares: &ValidatorShares, interval: u64, threshold: u64): vector<u8> {
+        let evaluation_points = vector[];
+        let key_shares = vector[];
+        let validators = table::borrow(&validator_shares.validators, interval);
+        let i = 0;
+        while (vector::length(&key_shares) < threshold) {
+            let key = ShareKey { interval, validator: *vector::borrow(validators, i) };
+            let share = table::borrow(&validator_shares.shares, key);
+            let points = table::borrow(&validator_shares.evaluation_points, key);
+            let j = 0;
+            while (j < vector::length(share) / G1_COMPRESSED_NUM_BYTES && vector::length(&key_shares) < threshold) {
+                vector::push_back(&mut key_shares, option::destroy_some(crypto_algebra::deserialize<G1, FormatG1Compr>(
+                    &vector::slice(share, j * G1_COMPRESSED_NUM_BYTES, (j + 1) * G1_COMPRESSED_NUM_BYTES)
+                )));
+                vector::push_back(&mut evaluation_points, option::destroy_some(crypto_algebra::deserialize<Fr, FormatFrLsb>(
+                    &vector::slice(points, j * FR_NUM_BYTES, (j + 1) * FR_NUM_BYTES)
+                )));
+                j = j + 1;
+            };
+            i = i + 1;
+        };
+        let secret = ibe::aggregate_shares_at<G1, Fr>(&evaluation_points, &key_shares, threshold);
+        crypto_algebra::serialize<G1, FormatG1Compr>(&secret)
+    }
+
+    /// Whether each decryption key share in `share` verifies against the public key share dealt for
+    /// the same sub-share in `public_key_shares`, for the identity of `interval`.
+    fun is_share_valid(interval: u64, share: &vector<u8>, public_key_shares: &vector<u8>): bool {
+        let num_sub_shares = vector::length(share) / G1_COMPRESSED_NUM_BYTES;
+        if (num_sub_shares == 0
+            || vector::length(share) != num_sub_shares * G1_COMPRESSED_NUM_BYTES
+            || vector::length(public_key_shares) != num_sub_shares * G2_COMPRESSED_NUM_BYTES) {
+            return false
+        };
+        let identity = timelock_identity(interval);
+        let i = 0;
+        while (i < num_sub_shares) {
+            let key_share = crypto_algebra::deserialize<G1, FormatG1Compr>(
+                &vector::slice(share, i * G1_COMPRESSED_NUM_BYTES, (i + 1) * G1_COMPRESSED_NUM_BYTES)
+            );
+            let public_key_share = crypto_algebra::deserialize<G2, FormatG2Compr>(
+                &vector::slice(public_key_shares, i * G2_COMPRESSED_NUM_BYTES, (i + 1) * G2_COMPRESSED_NUM_BYTES)
+            );
+            if (option::is_none(&key_share) || option::is_none(&public_key_share)) {
+                return false
+            };
+            if (!ibe::verify_timelock_share<G1, G2, Gt>(
+                option::borrow(&key_share),
+                option::borrow(&public_key_share),
+                identity
+            )) {
+                return false
+            };
+            i = i + 1;
+        };
+        true
+    }
+
+    /// The identity that timelock ciphertexts for `interval` on this chain are encrypted to:
+    /// keccak256(interval (little-endian) || chain id || "atomica_timelock"), like
+    /// `aptos_dkg::ibe::compute_timelock_identity` in Rust.
+    fun timelock_identity(interval: u64): vector<u8> {
+        let bytes = bcs::to_bytes(&interval);
+        vector::push_back(&mut bytes, chain_id::get());
+        vector::append(&mut bytes, b"atomica_timelock");
+        aptos_hash::keccak256(bytes)
     }
 
     /// Get the current interval number.
@@ -156,8 +361,8 @@ module aptos_framework::timelock {
         borrow_global<TimelockState>(@aptos_framework).current_interval
     }
 
-    /// Get the public key (MPK) for a specific interval.
-    /// Returns None if the public key hasn't been published yet.
+    /// Get the public key (MPK) for a specific interval: the 96-byte compressed G2 point dealt
+    /// by the interval's DKG. Returns None if the public key hasn't been published yet.
     ///
     /// This is used by clients to encrypt messages to a future interval.
     #[view]
@@ -186,10 +391,10 @@ module aptos_framework::timelock {
         table::contains(&state.revealed_secrets, interval)
     }
 
-    /// Get the revealed secret (aggregated decryption key) for a specific interval.
-    /// Returns None if the secret hasn't been revealed yet.
+    /// Get the revealed secret (aggregated decryption key) for a specific interval: the 48-byte
+    /// compressed G1 point aggregated from the validators' shares. Returns None if the secret
+    /// hasn't been revealed yet.
     /// This is used by clients to decrypt messages from a past interval.
-    /// Alias for backward compatibility.
     #[view]
     public fun get_secret(interval: u64): Option<vector<u8>> acquires TimelockState {
         if (!exists<TimelockState>(@aptos_framework)) {
@@ -203,12 +408,63 @@ module aptos_framework::timelock {
         }
     }
 
+    /// Get the share `validator` published for an interval: its decryption key shares for the
+    /// interval, as concatenated 48-byte compressed G1 points. Returns None if it hasn't been
+    /// published.
+    #[view]
+    public fun get_share(
+        interval: u64,
+        validator: address
+    ): Option<vector<u8>> acquires ValidatorShares {
+        if (!exists<ValidatorShares>(@aptos_framework)) {
+            return option::none()
+        };
+        let validator_shares = borrow_global<ValidatorShares>(@aptos_framework);
+        let key = ShareKey { interval, validator };
+        if (table::contains(&validator_shares.shares, key)) {
+            option::some(*table::borrow(&validator_shares.shares, key))
+        } else {
+            option::none()
+        }
+    }
+
+    /// Get the number of decryption key shares published for an interval.
+    #[view]
+    public fun get_share_count(interval: u64): u64 acquires ValidatorShares {
+        if (!exists<ValidatorShares>(@aptos_framework)) {
+            return 0
+        };
+        let validator_shares = borrow_global<ValidatorShares>(@aptos_framework);
+        *table::borrow_with_default(&validator_shares.share_counts, interval, &0)
+    }
+
     #[test_only]
     use aptos_framework::account::create_signer_for_test;
 
+    #[test_only]
+    /// The compressed point at infinity of BLS12-381 G1 or G2. At infinity, a key share verifies for
+    /// any identity under a public key share at infinity.
+    fun compressed_infinity(num_bytes: u64): vector<u8> {
+        let bytes = vector[0xc0];
+        while (vector::length(&bytes) < num_bytes) {
+            vector::push_back(&mut bytes, 0);
+        };
+        bytes
+    }
+
+    #[test_only]
+    /// The evaluation point `x` of a decryption key share, as the VM publishes it.
+    fun evaluation_point(x: u64): vector<u8> {
+        crypto_algebra::serialize<Fr, FormatFrLsb>(&crypto_algebra::from_u64<Fr>(x))
+    }
+
     #[test(framework = @aptos_framework)]
-    public fun test_timelock_flow(framework: &signer) acquires TimelockState {
+    public fun test_timelock_flow(
+        framework: &signer
+    ) acquires TimelockState, KeyGenConfig, ValidatorShares {
         timestamp::set_time_has_started_for_testing(framework);
+        chain_id::initialize_for_test(framework, 4);
+        crypto_algebra::enable_cryptography_algebra_natives(framework);
         account::create_account_for_test(@aptos_framework);
         initialize(framework);
         let vm = create_signer_for_test(@0x0);
@@ -227,17 +483,102 @@ module aptos_framework::timelock {
 
         // Test publishing
         let val = create_signer_for_test(@0x123);
-        let pk = vector::empty<u8>();
-        vector::push_back(&mut pk, 10);
-        publish_public_key(&val, 1, pk);
+        let pk_share = compressed_infinity(G2_COMPRESSED_NUM_BYTES);
+        publish_public_key(
+            &val,
+            1,
+            vector[10],
+            2,
+            vector[@0x123, @0x456],
+            vector[pk_share, pk_share],
+            vector[evaluation_point(1), evaluation_point(2)]
+        );
+        // The key and its shares of an interval are only published once
+        publish_public_key(&val, 1, vector[13], 1, vector[@0x123], vector[vector[14]], vector[vector[15]]);
 
-        let share = vector::empty<u8>();
-        vector::push_back(&mut share, 20);
-        publish_secret_share(&val, 0, share);
+        let share = compressed_infinity(G1_COMPRESSED_NUM_BYTES);
+        publish_secret_share(&val, 1, share);
 
-        let state = borrow_global<TimelockState>(@aptos_framework);
-        assert!(table::contains(&state.public_keys, 1), 101);
-        assert!(table::contains(&state.revealed_secrets, 0), 102);
+        assert!(get_public_key(1) == option::some(vector[10]), 101);
+        let validator_shares = borrow_global<ValidatorShares>(@aptos_framework);
+        let key = ShareKey { interval: 1, validator: @0x123 };
+        assert!(*table::borrow(&validator_shares.public_key_shares, key) == pk_share, 109);
+        assert!(*table::borrow(&validator_shares.evaluation_points, key) == evaluation_point(1), 111);
+        assert!(*table::borrow(&validator_shares.thresholds, 1) == 2, 112);
+        assert!(table::contains(&validator_shares.public_key_shares, ShareKey { interval: 1, validator: @0x456 }), 110);
+        assert!(get_share(1, @0x123) == option::some(share), 102);
+        assert!(get_share(1, @0x456) == option::none(), 106);
+        // One share of two is not enough to aggregate the secret
+        assert!(!is_secret_revealed(1), 113);
+
+        // Re-submitting a share for the same interval is a no-op
+        publish_secret_share(&val, 1, vector[21]);
+        assert!(get_share(1, @0x123) == option::some(share), 103);
+        assert!(get_share_count(1) == 1, 107);
+
+        // The second share reaches the threshold and aggregates the secret
+        publish_secret_share(&create_signer_for_test(@0x456), 1, share);
+        assert!(event::emitted_events<ShareReceivedEvent>() == vector[
+            ShareReceivedEvent {
+                interval: 1,
+                validator: @0x123,
+                shares_received_so_far: 1,
+                threshold: 2,
+                aggregation_complete: false,
+            },
+            ShareReceivedEvent {
+                interval: 1,
+                validator: @0x456,
+                shares_received_so_far: 2,
+                threshold: 2,
+                aggregation_complete: true,
+            },
+        ], 104);
+        assert!(event::emitted_events<PublicKeyPublishedEvent>() == vector[
+            PublicKeyPublishedEvent { interval: 1, validator: @0x123 },
+        ], 105);
+        assert!(get_share_count(1) == 2, 108);
+        assert!(event::emitted_events<SecretRevealedEvent>() == vector[SecretRevealedEvent { interval: 1 }], 114);
+        // Shares at infinity aggregate to the point at infinity
+        assert!(get_secret(1) == option::some(share), 115);
+    }
+
+    #[test(framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x10003, location = Self)]
+    public fun test_unverifiable_share_rejected(
+        framework: &signer
+    ) acquires TimelockState, ValidatorShares {
+        chain_id::initialize_for_test(framework, 4);
+        crypto_algebra::enable_cryptography_algebra_natives(framework);
+        account::create_account_for_test(@aptos_framework);
+        initialize(framework);
+        let val = create_signer_for_test(@0x123);
+        publish_public_key(
+            &val,
+            1,
+            vector[10],
+            1,
+            vector[@0x123],
+            vector[compressed_infinity(G2_COMPRESSED_NUM_BYTES)],
+            vector[evaluation_point(1)]
+        );
+
+        // Not a G1 point
+        publish_secret_share(&val, 1, vector[20]);
+    }
+
+    #[test(framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x10003, location = Self)]
+    public fun test_share_without_public_key_shares_rejected(
+        framework: &signer
+    ) acquires TimelockState, ValidatorShares {
+        chain_id::initialize_for_test(framework, 4);
+        crypto_algebra::enable_cryptography_algebra_natives(framework);
+        account::create_account_for_test(@aptos_framework);
+        initialize(framework);
+
+        // No key was published for the interval
+        publish_secret_share(&create_signer_for_test(@0x123), 1, compressed_infinity(G1_COMPRESSED_NUM_BYTES));
     }
 
     #[test]
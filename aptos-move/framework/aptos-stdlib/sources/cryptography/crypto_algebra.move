@@ -39,6 +39,8 @@ module aptos_std::crypto_algebra {
     use std::option::{Option, some, none};
     use std::features;
 
+    friend aptos_std::ibe;
+
     const E_NOT_IMPLEMENTED: u64 = 1;
     const E_NON_EQUAL_LENGTHS: u64 = 2;
     const E_TOO_MUCH_MEMORY_USED: u64 = 3;
@@ -63,6 +65,11 @@ module aptos_std::crypto_algebra {
         element.handle
     }
 
+    /// Wrap a handle returned by a native of a friend module as an element of structure `S`.
+    public(friend) fun from_handle<S>(handle: u64): Element<S> {
+        Element<S> { handle }
+    }
+
     /// Convert a u64 to an element of a structure `S`.
     public fun from_u64<S>(value: u64): Element<S> {
         abort_unless_cryptography_algebra_natives_enabled();
@@ -1,5 +1,9 @@
-/// This module provides Identity-Based Encryption (IBE) decryption capabilities.
+/// This module provides Identity-Based Encryption (IBE) encryption and decryption capabilities.
 /// It uses the `crypto_algebra` module for underlying algebraic structures (G1, G2, Gt).
+///
+/// The message is XOR-ed with the Keccak256 hash of the serialized pairing output, used as a one-time pad, so messages
+/// are at most 32 bytes long. This is not the scheme of the Rust client (`aptos_dkg::ibe`), which encrypts with
+/// AES-256-GCM under a key derived by HKDF from the pairing output: ciphertexts of one cannot be decrypted by the other.
 module aptos_std::ibe {
     use aptos_std::crypto_algebra::{Self, Element, abort_unless_cryptography_algebra_natives_enabled};
 
@@ -7,6 +11,8 @@ module aptos_std::ibe {
     /// Performs Pairing(u, sig) -> Gt, Serializes Gt, Hashes (Keccak256), and XORs with ciphertext.
     /// 
     /// generic types G1, G2, Gt must match the curves used (e.g. BLS12-381).
+    ///
+    /// Aborts with `std::error::invalid_argument(3)` if the ciphertext is longer than 32 bytes.
     public fun decrypt<G1, G2, Gt>(u: &Element<G1>, sig: &Element<G2>, ciphertext: vector<u8>): vector<u8> {
         // Use native IBE decryption which is gas-optimized
         // Calls crypto_algebra::handle explicitly to avoid dot-call resolution issues
@@ -17,6 +23,102 @@ module aptos_std::ibe {
         )
     }
 
+    /// Encrypts a message to the identity hashed to `hashed_id`, under the master public key `mpk` and randomness `r`.
+    /// Performs Pairing(r * hashed_id, mpk) -> Gt, Serializes Gt, Hashes (Keccak256), and XORs with plaintext.
+    /// Returns `U = r * generator<G2>` along with the ciphertext: the holder of the decryption key `msk * hashed_id`
+    /// recovers the plaintext with `decrypt(&dk, &U, ciphertext)`.
+    ///
+    /// generic types G1, G2, Gt, S must match the curves used (e.g. BLS12-381 with its scalar field Fr).
+    ///
+    /// Aborts with `std::error::invalid_argument(3)` if the plaintext is longer than 32 bytes.
+    public fun encrypt<G1, G2, Gt, S>(
+        mpk: &Element<G2>,
+        hashed_id: &Element<G1>,
+        r: &Element<S>,
+        plaintext: vector<u8>
+    ): (Element<G2>, vector<u8>) {
+        let u = crypto_algebra::scalar_mul(&crypto_algebra::one<G2>(), r);
+        let ciphertext = encrypt_internal<G1, G2, Gt, S>(
+            crypto_algebra::handle(mpk),
+            crypto_algebra::handle(hashed_id),
+            crypto_algebra::handle(r),
+            plaintext
+        );
+        (u, ciphertext)
+    }
+
+    /// Checks that `share` is the decryption-key share for the identity hashed to `hashed_id`, under the
+    /// public key share `pk_share` of the validator that published it.
+    /// Performs the pairing check e(share, generator<G2>) == e(hashed_id, pk_share).
+    ///
+    /// generic types G1, G2, Gt must match the curves used (e.g. BLS12-381).
+    public fun verify_share<G1, G2, Gt>(share: &Element<G1>, pk_share: &Element<G2>, hashed_id: &Element<G1>): bool {
+        verify_share_internal<G1, G2, Gt>(
+            crypto_algebra::handle(share),
+            crypto_algebra::handle(pk_share),
+            crypto_algebra::handle(hashed_id)
+        )
+    }
+
+    /// Checks that `share` is a timelock decryption-key share for the 32-byte `identity` of an interval, under the
+    /// public key share `pk_share` the interval's DKG dealt to the validator that published it.
+    /// Performs the pairing check e(share, g_2) == e(t * h, pk_share), where h and g_2 are the bases the timelock DKG
+    /// deals in and t is `identity` hashed to a scalar, like `aptos_dkg::ibe::verify_timelock_key_share` in Rust.
+    /// Unlike `verify_share`, which checks plain Boneh-Franklin shares, this checks the shares validators derive from
+    /// their timelock DKG shares.
+    ///
+    /// Aborts with `std::error::invalid_argument(8)` if `identity` is not 32 bytes long.
+    ///
+    /// generic types G1, G2, Gt must be the BLS12-381 structures.
+    public fun verify_timelock_share<G1, G2, Gt>(share: &Element<G1>, pk_share: &Element<G2>, identity: vector<u8>): bool {
+        verify_timelock_share_internal<G1, G2, Gt>(
+            crypto_algebra::handle(share),
+            crypto_algebra::handle(pk_share),
+            identity
+        )
+    }
+
+    /// Aggregates decryption-key shares into the decryption key for their identity, where `shares[i]` is the share
+    /// of the validator with (non-zero) evaluation point `indices[i]`.
+    /// The first `threshold` shares are combined by Lagrange interpolation at 0.
+    ///
+    /// Aborts with `std::error::invalid_argument(2)` if `indices` and `shares` differ in length,
+    /// `std::error::invalid_argument(4)` if `threshold` is 0, `std::error::invalid_argument(5)` if there are fewer
+    /// than `threshold` shares, `std::error::invalid_argument(6)` if an index is 0, and
+    /// `std::error::invalid_argument(7)` if an index is repeated.
+    ///
+    /// generic types G1, S must match the curves used (e.g. BLS12-381 with its scalar field Fr).
+    public fun aggregate_shares<G1, S>(indices: vector<u64>, shares: &vector<Element<G1>>, threshold: u64): Element<G1> {
+        let eval_points = indices.map(|index| crypto_algebra::from_u64<S>(index));
+        aggregate_shares_at<G1, S>(&eval_points, shares, threshold)
+    }
+
+    /// Aggregates decryption-key shares into the decryption key for their identity, where `shares[i]` is the share
+    /// with (non-zero) evaluation point `eval_points[i]`, such as a root of unity for the shares of a weighted DKG.
+    /// The first `threshold` shares are combined by Lagrange interpolation at 0.
+    ///
+    /// Aborts like `aggregate_shares`, with `std::error::invalid_argument(6)` if an evaluation point is 0 and
+    /// `std::error::invalid_argument(7)` if one is repeated.
+    ///
+    /// generic types G1, S must match the curves used (e.g. BLS12-381 with its scalar field Fr).
+    public fun aggregate_shares_at<G1, S>(
+        eval_points: &vector<Element<S>>,
+        shares: &vector<Element<G1>>,
+        threshold: u64
+    ): Element<G1> {
+        let eval_point_handles = eval_points.map_ref(|point| crypto_algebra::handle(point));
+        let share_handles = shares.map_ref(|share| crypto_algebra::handle(share));
+        crypto_algebra::from_handle(aggregate_shares_internal<G1, S>(eval_point_handles, share_handles, threshold))
+    }
+
     // Native function definition
     native fun decrypt_internal<G1, G2, Gt>(u_handle: u64, sig_handle: u64, ciphertext: vector<u8>): vector<u8>;
+
+    native fun encrypt_internal<G1, G2, Gt, S>(mpk_handle: u64, id_handle: u64, r_handle: u64, plaintext: vector<u8>): vector<u8>;
+
+    native fun verify_share_internal<G1, G2, Gt>(share_handle: u64, pk_share_handle: u64, id_handle: u64): bool;
+
+    native fun verify_timelock_share_internal<G1, G2, Gt>(share_handle: u64, pk_share_handle: u64, identity: vector<u8>): bool;
+
+    native fun aggregate_shares_internal<G1, S>(eval_point_handles: vector<u64>, share_handles: vector<u64>, threshold: u64): u64;
 }
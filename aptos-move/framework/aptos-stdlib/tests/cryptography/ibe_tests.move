@@ -0,0 +1,166 @@
+#[test_only]
+module aptos_std::ibe_tests {
+    use aptos_std::bls12381_algebra::{Fr, G1, G2, Gt, HashG1XmdSha256SswuRo};
+    use aptos_std::crypto_algebra::{Element, enable_cryptography_algebra_natives, eq, from_u64, hash_to, neg, one, scalar_mul, zero};
+    use aptos_std::ibe;
+
+    const DST: vector<u8> = b"APTOS_IBE_TEST_DST";
+
+    #[test(fx = @std)]
+    fun test_encrypt_then_decrypt(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let msk = from_u64<Fr>(1234567);
+        let mpk = scalar_mul(&one<G2>(), &msk);
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        let plaintext = b"a plaintext of at most 32 bytes";
+
+        let (u, ciphertext) = ibe::encrypt<G1, G2, Gt, Fr>(&mpk, &hashed_id, &from_u64<Fr>(987654321), plaintext);
+        assert!(ciphertext.length() == plaintext.length(), 1);
+        assert!(ciphertext != plaintext, 2);
+
+        let dk = scalar_mul(&hashed_id, &msk);
+        assert!(ibe::decrypt<G1, G2, Gt>(&dk, &u, ciphertext) == plaintext, 3);
+
+        // A key for another identity does not decrypt.
+        let other_dk = scalar_mul(&hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 43"), &msk);
+        assert!(ibe::decrypt<G1, G2, Gt>(&other_dk, &u, ciphertext) != plaintext, 4);
+    }
+
+    #[test(fx = @std)]
+    fun test_verify_share(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let sk_share = from_u64<Fr>(24680);
+        let pk_share = scalar_mul(&one<G2>(), &sk_share);
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        let share = scalar_mul(&hashed_id, &sk_share);
+        assert!(ibe::verify_share<G1, G2, Gt>(&share, &pk_share, &hashed_id), 1);
+
+        // Not for another identity, nor under another validator's public key share
+        let other_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 43");
+        assert!(!ibe::verify_share<G1, G2, Gt>(&share, &pk_share, &other_id), 2);
+        let other_pk_share = scalar_mul(&one<G2>(), &from_u64<Fr>(13579));
+        assert!(!ibe::verify_share<G1, G2, Gt>(&share, &other_pk_share, &hashed_id), 3);
+    }
+
+    /// The share of `f(x) * hashed_id` at `x`, for `f(x) = 5 + 7x + 11x^2`.
+    fun share_at(hashed_id: &Element<G1>, x: u64): Element<G1> {
+        scalar_mul(hashed_id, &from_u64<Fr>(5 + 7 * x + 11 * x * x))
+    }
+
+    #[test(fx = @std)]
+    fun test_aggregate_shares(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        let indices = vector[4, 1, 9, 2];
+        let shares = indices.map_ref(|x| share_at(&hashed_id, *x));
+        let dk = ibe::aggregate_shares<G1, Fr>(indices, &shares, 3);
+        assert!(eq(&dk, &scalar_mul(&hashed_id, &from_u64<Fr>(5))), 1);
+
+        // Any 3 of the shares give the same key, the 4th is ignored
+        let dk = ibe::aggregate_shares<G1, Fr>(vector[2, 9, 4, 1], &vector[shares[3], shares[2], shares[0], zero<G1>()], 3);
+        assert!(eq(&dk, &scalar_mul(&hashed_id, &from_u64<Fr>(5))), 2);
+
+        // Too few shares interpolate to another key
+        let dk = ibe::aggregate_shares<G1, Fr>(indices, &shares, 2);
+        assert!(!eq(&dk, &scalar_mul(&hashed_id, &from_u64<Fr>(5))), 3);
+    }
+
+    #[test(fx = @std)]
+    fun test_aggregate_shares_at(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        // The shares of f at -1, -2 and -3 are f(-1) = 9, f(-2) = 35 and f(-3) = 83 times the hashed identity.
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        let points = vector[neg(&from_u64<Fr>(1)), neg(&from_u64<Fr>(2)), neg(&from_u64<Fr>(3))];
+        let shares = vector[9, 35, 83].map(|y| scalar_mul(&hashed_id, &from_u64<Fr>(y)));
+        let dk = ibe::aggregate_shares_at<G1, Fr>(&points, &shares, 3);
+        assert!(eq(&dk, &scalar_mul(&hashed_id, &from_u64<Fr>(5))), 1);
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x10005, location = aptos_std::ibe)]
+    fun test_aggregate_shares_below_threshold(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        ibe::aggregate_shares<G1, Fr>(vector[1, 2], &vector[share_at(&hashed_id, 1), share_at(&hashed_id, 2)], 3);
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x10007, location = aptos_std::ibe)]
+    fun test_aggregate_shares_duplicate_index(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        let share = share_at(&hashed_id, 1);
+        ibe::aggregate_shares<G1, Fr>(vector[1, 1], &vector[share, share], 2);
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x10002, location = aptos_std::ibe)]
+    fun test_aggregate_shares_length_mismatch(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        ibe::aggregate_shares<G1, Fr>(vector[1, 2, 3], &vector[share_at(&hashed_id, 1), share_at(&hashed_id, 2)], 2);
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x10004, location = aptos_std::ibe)]
+    fun test_aggregate_shares_zero_threshold(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        ibe::aggregate_shares<G1, Fr>(vector[1], &vector[share_at(&hashed_id, 1)], 0);
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x10006, location = aptos_std::ibe)]
+    fun test_aggregate_shares_zero_index(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        ibe::aggregate_shares<G1, Fr>(vector[1, 0], &vector[share_at(&hashed_id, 1), share_at(&hashed_id, 2)], 2);
+    }
+
+    /// A message of 32 bytes, the longest accepted.
+    const MAX_LENGTH_MESSAGE: vector<u8> = b"0123456789abcdef0123456789abcdef";
+
+    #[test(fx = @std)]
+    fun test_encrypt_then_decrypt_max_length(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let plaintext = MAX_LENGTH_MESSAGE;
+        let msk = from_u64<Fr>(1234567);
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        let (u, ciphertext) = ibe::encrypt<G1, G2, Gt, Fr>(
+            &scalar_mul(&one<G2>(), &msk), &hashed_id, &from_u64<Fr>(987654321), plaintext
+        );
+        assert!(ibe::decrypt<G1, G2, Gt>(&scalar_mul(&hashed_id, &msk), &u, ciphertext) == plaintext, 1);
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x10003, location = aptos_std::ibe)]
+    fun test_decrypt_rejects_oversized_ciphertext(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let ciphertext = MAX_LENGTH_MESSAGE;
+        ciphertext.push_back(0);
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        ibe::decrypt<G1, G2, Gt>(&hashed_id, &one<G2>(), ciphertext);
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x10003, location = aptos_std::ibe)]
+    fun test_encrypt_rejects_oversized_plaintext(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        let plaintext = MAX_LENGTH_MESSAGE;
+        plaintext.push_back(0);
+        let hashed_id = hash_to<G1, HashG1XmdSha256SswuRo>(&DST, &b"interval 42");
+        let (_, _) = ibe::encrypt<G1, G2, Gt, Fr>(&one<G2>(), &hashed_id, &from_u64<Fr>(1), plaintext);
+    }
+}
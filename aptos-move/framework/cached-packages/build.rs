@@ -6,6 +6,18 @@ use aptos_framework::ReleaseTarget;
 use std::{env::current_dir, path::PathBuf};
 
 fn main() -> Result<()> {
+    let path = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR defined")).join("head.mrb");
+
+    // Set the below variable to embed a prebuilt release bundle instead of building the framework
+    // in this tree, e.g. to run against the framework of another checkout.
+    println!("cargo:rerun-if-env-changed=ZAPATOS_HEAD_MRB_PATH");
+    if let Ok(bundle_path) = std::env::var("ZAPATOS_HEAD_MRB_PATH") {
+        println!("cargo:rerun-if-changed={}", bundle_path);
+        std::fs::copy(&bundle_path, &path)
+            .with_context(|| format!("Failed to copy release bundle `{}`", bundle_path))?;
+        return Ok(());
+    }
+
     // Set the below variable to skip the building step. This might be useful if the build
     // is broken so it can be debugged with the old outdated artifacts.
     if std::env::var("SKIP_FRAMEWORK_BUILD").is_err() {
@@ -88,9 +100,6 @@ fn main() -> Result<()> {
             prev_dir.join("move-stdlib").join("Move.toml").display()
         );
 
-        let path =
-            PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR defined")).join("head.mrb");
-
         ReleaseTarget::Head
             .create_release(true, Some(path))
             .context("Failed to create release")?;
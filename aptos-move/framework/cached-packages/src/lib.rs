@@ -1,8 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::Context;
 use aptos_framework::ReleaseBundle;
 use once_cell::sync::Lazy;
+use std::path::Path;
 
 pub mod aptos_framework_sdk_builder;
 pub mod aptos_stdlib;
@@ -22,3 +24,40 @@ static HEAD_RELEASE_BUNDLE: Lazy<ReleaseBundle> = Lazy::new(|| {
 pub fn head_release_bundle() -> &'static ReleaseBundle {
     &HEAD_RELEASE_BUNDLE
 }
+
+/// Reads a release bundle from `path`, for tools that swap in another framework without
+/// rebuilding.
+pub fn head_release_bundle_from_path(path: impl AsRef<Path>) -> anyhow::Result<ReleaseBundle> {
+    let path = path.as_ref();
+    ReleaseBundle::read(path.to_path_buf())
+        .with_context(|| format!("Failed to load release bundle `{}`", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_bundle_decodes() {
+        assert!(!head_release_bundle().code().is_empty());
+    }
+
+    #[test]
+    fn test_bundle_from_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("head.mrb");
+        std::fs::write(&path, HEAD_RELEASE_BUNDLE_BYTES).unwrap();
+        let bundle = head_release_bundle_from_path(&path).unwrap();
+        assert_eq!(bundle.code(), head_release_bundle().code());
+
+        std::fs::write(
+            &path,
+            &HEAD_RELEASE_BUNDLE_BYTES[..HEAD_RELEASE_BUNDLE_BYTES.len() / 2],
+        )
+        .unwrap();
+        let err = head_release_bundle_from_path(&path).unwrap_err();
+        assert!(err.to_string().contains(&path.display().to_string()));
+
+        assert!(head_release_bundle_from_path(dir.path().join("missing.mrb")).is_err());
+    }
+}
@@ -4,23 +4,71 @@
 use crate::{
     abort_unless_feature_flag_enabled,
     natives::cryptography::algebra::{
-        abort_invariant_violated, AlgebraContext, Structure, MOVE_ABORT_CODE_NOT_IMPLEMENTED,
+        abort_invariant_violated, AlgebraContext, Structure, E_TOO_MUCH_MEMORY_USED,
+        MEMORY_LIMIT_IN_BYTES, MOVE_ABORT_CODE_INPUT_VECTOR_SIZES_NOT_MATCHING,
+        MOVE_ABORT_CODE_NOT_IMPLEMENTED,
     },
-    safe_borrow_element, structure_from_ty_arg,
+    safe_borrow_element, store_element, structure_from_ty_arg,
+};
+use aptos_crypto::blstrs::{g1_proj_from_bytes, g2_proj_from_bytes};
+use aptos_dkg::{
+    ibe::{verify_timelock_key_share, TimelockIdentity, TIMELOCK_IDENTITY_SCALAR_DST},
+    pvss::das::public_parameters::SEED_PVSS_PUBLIC_PARAMS,
+};
+use aptos_gas_algebra::{Arg, GasExpression};
+use aptos_gas_schedule::{
+    gas_feature_versions::RELEASE_V1_39, gas_params::natives::aptos_framework::*,
 };
-#[allow(unused_imports)]
-use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
 use aptos_native_interface::{
     safely_pop_arg, SafeNativeContext, SafeNativeError, SafeNativeResult,
 };
 use aptos_types::on_chain_config::FeatureFlag;
-use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_ec::{pairing::Pairing, CurveGroup, PrimeGroup};
+use ark_ff::{PrimeField, Zero};
 use ark_serialize::CanonicalSerialize;
+use move_core_types::gas_algebra::{NumArgs, NumBytes};
 use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
 use smallvec::{smallvec, SmallVec};
-use std::{collections::VecDeque, rc::Rc};
+use std::{
+    collections::{BTreeSet, VecDeque},
+    rc::Rc,
+};
 use tiny_keccak::{Hasher, Keccak};
 
+/// The longest plaintext or ciphertext the IBE natives accept, in bytes: the 32-byte keystream is
+/// used as a one-time pad, so a longer message would reuse it.
+pub const MAX_IBE_MESSAGE_LENGTH: usize = 32;
+
+/// Equivalent to `std::error::invalid_argument(3)` in Move.
+const E_IBE_MESSAGE_TOO_LONG: u64 = 0x01_0003;
+
+/// Equivalent to `std::error::invalid_argument(4)` in Move.
+const E_IBE_ZERO_THRESHOLD: u64 = 0x01_0004;
+
+/// Equivalent to `std::error::invalid_argument(5)` in Move.
+const E_IBE_NOT_ENOUGH_SHARES: u64 = 0x01_0005;
+
+/// Equivalent to `std::error::invalid_argument(6)` in Move.
+const E_IBE_ZERO_SHARE_INDEX: u64 = 0x01_0006;
+
+/// Equivalent to `std::error::invalid_argument(7)` in Move.
+const E_IBE_DUPLICATE_SHARE_INDEX: u64 = 0x01_0007;
+
+/// Equivalent to `std::error::invalid_argument(8)` in Move.
+const E_IBE_WRONG_IDENTITY_LENGTH: u64 = 0x01_0008;
+
+fn feature_flag_of_ibe_aggregation(
+    g1_opt: Option<Structure>,
+    scalar_field_opt: Option<Structure>,
+) -> Option<FeatureFlag> {
+    match (g1_opt, scalar_field_opt) {
+        (Some(Structure::BLS12381G1), Some(Structure::BLS12381Fr)) => {
+            Some(FeatureFlag::BLS12_381_STRUCTURES)
+        },
+        _ => None,
+    }
+}
+
 fn feature_flag_of_ibe(
     g1_opt: Option<Structure>,
     g2_opt: Option<Structure>,
@@ -41,6 +89,102 @@ macro_rules! abort_unless_ibe_enabled {
     };
 }
 
+/// Checks the inputs of `aggregate_shares_internal` the way
+/// `aptos_dkg::ibe::aggregate_decryption_shares` does, returning the abort code on failure.
+fn check_aggregation_inputs<F: PrimeField>(
+    eval_points: &[F],
+    num_shares: usize,
+    threshold: u64,
+) -> Result<usize, u64> {
+    if eval_points.len() != num_shares {
+        return Err(MOVE_ABORT_CODE_INPUT_VECTOR_SIZES_NOT_MATCHING);
+    }
+    if threshold == 0 {
+        return Err(E_IBE_ZERO_THRESHOLD);
+    }
+    if (num_shares as u64) < threshold {
+        return Err(E_IBE_NOT_ENOUGH_SHARES);
+    }
+    let mut seen = BTreeSet::new();
+    for x in eval_points {
+        if x.is_zero() {
+            return Err(E_IBE_ZERO_SHARE_INDEX);
+        }
+        if !seen.insert(*x) {
+            return Err(E_IBE_DUPLICATE_SHARE_INDEX);
+        }
+    }
+    Ok(threshold as usize)
+}
+
+/// Computes the Lagrange coefficients at x = 0 for the given distinct, non-zero evaluation points:
+/// lambda_i = prod_{j != i} x_j / (x_j - x_i). Returns `None` if the points are not distinct.
+fn lagrange_coefficients_at_zero<F: PrimeField>(xs: &[F]) -> Option<Vec<F>> {
+    xs.iter()
+        .enumerate()
+        .map(|(i, x_i)| {
+            let (num, denom) = xs
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold((F::one(), F::one()), |(num, denom), (_, x_j)| {
+                    (num * x_j, denom * (*x_j - x_i))
+                });
+            denom.inverse().map(|inv| num * inv)
+        })
+        .collect()
+}
+
+/// Interpolates the shares `bases`, of the given distinct, non-zero evaluation points, at x = 0.
+fn interpolate_at_zero<G: CurveGroup>(xs: &[G::ScalarField], bases: &[G::Affine]) -> Option<G> {
+    let coeffs = lagrange_coefficients_at_zero(xs)?;
+    G::msm(bases, &coeffs).ok()
+}
+
+/// Aborts if `message` is over `MAX_IBE_MESSAGE_LENGTH`.
+fn check_message_length(message: &[u8]) -> SafeNativeResult<()> {
+    if message.len() > MAX_IBE_MESSAGE_LENGTH {
+        return Err(SafeNativeError::Abort {
+            abort_code: E_IBE_MESSAGE_TOO_LONG,
+        });
+    }
+    Ok(())
+}
+
+/// Charges for hashing the serialized pairing output and XOR-ing the message with the mask.
+fn charge_mask_and_xor(
+    context: &mut SafeNativeContext,
+    k_bytes: &[u8],
+    message: &[u8],
+) -> SafeNativeResult<()> {
+    context.charge(
+        ALGEBRA_IBE_KECCAK_BASE
+            + ALGEBRA_IBE_KECCAK_PER_BYTE * NumBytes::new(k_bytes.len() as u64)
+            + ALGEBRA_IBE_XOR_PER_BYTE * NumBytes::new(message.len() as u64),
+    )
+}
+
+/// Derives the 32-byte keystream of the scheme from the serialized pairing output `K`.
+fn mask_from_pairing_output(k_bytes: &[u8]) -> [u8; 32] {
+    let mut sha3 = Keccak::v256();
+    sha3.update(k_bytes);
+    let mut mask = [0u8; 32];
+    sha3.finalize(&mut mask);
+    mask
+}
+
+/// XORs `data` with the repeated `mask`. Applying it twice with the same mask is the identity,
+/// which is what makes encryption and decryption inverses.
+///
+/// NOTE: the mask only repeats for the ciphertexts over `MAX_IBE_MESSAGE_LENGTH` that
+/// `decrypt_internal` still accepts before `RELEASE_V1_39`.
+fn xor_with_mask(data: &[u8], mask: &[u8; 32]) -> Vec<u8> {
+    data.iter()
+        .enumerate()
+        .map(|(i, byte)| byte ^ mask[i % 32])
+        .collect()
+}
+
 macro_rules! decrypt_internal_impl {
     (
         $context:expr,
@@ -56,6 +200,9 @@ macro_rules! decrypt_internal_impl {
         let ciphertext = safely_pop_arg!($args, Vec<u8>);
         let sig_element_handle = safely_pop_arg!($args, u64) as usize;
         let u_element_handle = safely_pop_arg!($args, u64) as usize;
+        if $context.gas_feature_version() >= RELEASE_V1_39 {
+            check_message_length(&ciphertext)?;
+        }
 
         // Load U (G1)
         safe_borrow_element!(
@@ -89,24 +236,268 @@ macro_rules! decrypt_internal_impl {
         k_gt.serialize_uncompressed(&mut k_bytes)
             .map_err(|_e| abort_invariant_violated())?;
 
-        // Keccak256 Hash
-        // Charge some gas for hashing? Reusing serialization cost as proxy for now + per-byte?
-        // Ideally we define specific gas. For PoC, we will assume it is covered.
-        let mut sha3 = Keccak::v256();
-        sha3.update(&k_bytes);
-        let mut mask = [0u8; 32];
-        sha3.finalize(&mut mask);
-
-        // XOR
-        let mut result = Vec::with_capacity(ciphertext.len());
-        for (i, byte) in ciphertext.iter().enumerate() {
-            result.push(byte ^ mask[i % 32]);
-        }
+        // Keccak256 Hash and XOR
+        charge_mask_and_xor($context, &k_bytes, &ciphertext)?;
+        let mask = mask_from_pairing_output(&k_bytes);
+        let result = xor_with_mask(&ciphertext, &mask);
+
+        Ok(smallvec![Value::vector_u8(result)])
+    }};
+}
+
+macro_rules! encrypt_internal_impl {
+    (
+        $context:expr,
+        $args:ident,
+        $pairing:ty,
+        $g1_projective:ty,
+        $g2_projective:ty,
+        $scalar:ty,
+        $pairing_gas_cost:expr,
+        $g1_scalar_mul_gas_cost:expr,
+        $g1_proj_to_affine_gas_cost:expr,
+        $g2_proj_to_affine_gas_cost:expr,
+        $serialize_gas_cost:expr
+    ) => {{
+        let plaintext = safely_pop_arg!($args, Vec<u8>);
+        let r_handle = safely_pop_arg!($args, u64) as usize;
+        let id_element_handle = safely_pop_arg!($args, u64) as usize;
+        let mpk_element_handle = safely_pop_arg!($args, u64) as usize;
+        // Unlike decryption, encryption never accepted longer messages
+        check_message_length(&plaintext)?;
+
+        // Load MPK (G2)
+        safe_borrow_element!(
+            $context,
+            mpk_element_handle,
+            $g2_projective,
+            mpk_element_ptr,
+            mpk_element
+        );
+        $context.charge($g2_proj_to_affine_gas_cost)?;
+        let mpk_element_affine = mpk_element.into_affine();
+
+        // Load H(id) (G1) and r, and compute r * H(id)
+        safe_borrow_element!(
+            $context,
+            id_element_handle,
+            $g1_projective,
+            id_element_ptr,
+            id_element
+        );
+        safe_borrow_element!($context, r_handle, $scalar, r_ptr, r);
+        let r_bigint: ark_ff::BigInteger256 = (*r).into();
+        $context.charge($g1_scalar_mul_gas_cost)?;
+        let r_id_element = id_element.mul_bigint(r_bigint);
+        $context.charge($g1_proj_to_affine_gas_cost)?;
+        let r_id_element_affine = r_id_element.into_affine();
+
+        // Pairing: K = e(r * H(id), MPK), which equals e(s * H(id), r * G2) on the decrypt side.
+        $context.charge($pairing_gas_cost)?;
+        let k_gt = <$pairing>::pairing(r_id_element_affine, mpk_element_affine).0;
+
+        // Serialize K
+        $context.charge($serialize_gas_cost)?;
+        let mut k_bytes = Vec::new();
+        k_gt.serialize_uncompressed(&mut k_bytes)
+            .map_err(|_e| abort_invariant_violated())?;
+
+        // Keccak256 Hash and XOR
+        charge_mask_and_xor($context, &k_bytes, &plaintext)?;
+        let mask = mask_from_pairing_output(&k_bytes);
+        let result = xor_with_mask(&plaintext, &mask);
 
         Ok(smallvec![Value::vector_u8(result)])
     }};
 }
 
+macro_rules! verify_share_internal_impl {
+    (
+        $context:expr,
+        $args:ident,
+        $pairing:ty,
+        $g1_projective:ty,
+        $g2_projective:ty,
+        $multi_pairing_base_gas_cost:expr,
+        $multi_pairing_per_pair_gas_cost:expr,
+        $g1_neg_gas_cost:expr,
+        $g1_proj_to_affine_gas_cost:expr,
+        $g2_proj_to_affine_gas_cost:expr,
+        $g2_generator_gas_cost:expr,
+        $gt_eq_gas_cost:expr
+    ) => {{
+        let id_element_handle = safely_pop_arg!($args, u64) as usize;
+        let pk_share_element_handle = safely_pop_arg!($args, u64) as usize;
+        let share_element_handle = safely_pop_arg!($args, u64) as usize;
+
+        // Load the share and -H(id) (G1)
+        safe_borrow_element!(
+            $context,
+            share_element_handle,
+            $g1_projective,
+            share_element_ptr,
+            share_element
+        );
+        safe_borrow_element!(
+            $context,
+            id_element_handle,
+            $g1_projective,
+            id_element_ptr,
+            id_element
+        );
+        $context.charge($g1_neg_gas_cost)?;
+        let neg_id_element = -*id_element;
+        $context.charge($g1_proj_to_affine_gas_cost)?;
+        let share_element_affine = share_element.into_affine();
+        $context.charge($g1_proj_to_affine_gas_cost)?;
+        let neg_id_element_affine = neg_id_element.into_affine();
+
+        // Load the public key share and the generator (G2)
+        safe_borrow_element!(
+            $context,
+            pk_share_element_handle,
+            $g2_projective,
+            pk_share_element_ptr,
+            pk_share_element
+        );
+        $context.charge($g2_proj_to_affine_gas_cost)?;
+        let pk_share_element_affine = pk_share_element.into_affine();
+        $context.charge($g2_generator_gas_cost)?;
+        let generator_affine = <$g2_projective>::generator().into_affine();
+
+        // e(share, G2) == e(H(id), pk_share)  <=>  e(share, G2) * e(-H(id), pk_share) == 1
+        $context.charge(
+            $multi_pairing_base_gas_cost + $multi_pairing_per_pair_gas_cost * NumArgs::from(2),
+        )?;
+        let product = <$pairing>::multi_pairing([share_element_affine, neg_id_element_affine], [
+            generator_affine,
+            pk_share_element_affine,
+        ]);
+        $context.charge($gt_eq_gas_cost)?;
+
+        Ok(smallvec![Value::bool(product.is_zero())])
+    }};
+}
+
+/// Serializes `element` into the compressed format `aptos_dkg` deserializes its points from.
+fn serialize_compressed<T: CanonicalSerialize>(element: &T) -> SafeNativeResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    element
+        .serialize_compressed(&mut bytes)
+        .map_err(|_e| abort_invariant_violated())?;
+    Ok(bytes)
+}
+
+/// Checks a timelock decryption-key share over BLS12-381 with `aptos_dkg`, which the validators
+/// derive their shares with: `e(share, g_2) == e(t * h, pk_share)`, where `h` and `g_2` are the
+/// bases the timelock DKG deals in and `t` is the identity hashed to a scalar.
+fn verify_timelock_share_bls12381(
+    context: &mut SafeNativeContext,
+    args: &mut VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    let identity = safely_pop_arg!(args, Vec<u8>);
+    let pk_share_element_handle = safely_pop_arg!(args, u64) as usize;
+    let share_element_handle = safely_pop_arg!(args, u64) as usize;
+    let identity = TimelockIdentity::from_bytes(&identity).map_err(|_| SafeNativeError::Abort {
+        abort_code: E_IBE_WRONG_IDENTITY_LENGTH,
+    })?;
+
+    // Hand the share (G1) and the public key share (G2) over as compressed points
+    safe_borrow_element!(
+        context,
+        share_element_handle,
+        ark_bls12_381::G1Projective,
+        share_element_ptr,
+        share_element
+    );
+    context.charge(
+        ALGEBRA_ARK_BLS12_381_G1_PROJ_TO_AFFINE
+            + ALGEBRA_ARK_BLS12_381_G1_AFFINE_SERIALIZE_COMP
+            + ALGEBRA_ARK_BLS12_381_G1_AFFINE_DESER_COMP,
+    )?;
+    let share = g1_proj_from_bytes(&serialize_compressed(&share_element.into_affine())?)
+        .map_err(|_e| abort_invariant_violated())?;
+    safe_borrow_element!(
+        context,
+        pk_share_element_handle,
+        ark_bls12_381::G2Projective,
+        pk_share_element_ptr,
+        pk_share_element
+    );
+    context.charge(
+        ALGEBRA_ARK_BLS12_381_G2_PROJ_TO_AFFINE
+            + ALGEBRA_ARK_BLS12_381_G2_AFFINE_SERIALIZE_COMP
+            + ALGEBRA_ARK_BLS12_381_G2_AFFINE_DESER_COMP,
+    )?;
+    let pk_share = g2_proj_from_bytes(&serialize_compressed(&pk_share_element.into_affine())?)
+        .map_err(|_e| abort_invariant_violated())?;
+
+    // Hashing the DKG bases to the curve and the identity to a scalar (two SHA3-512 hashes), the
+    // scalar multiplication `t * h` and the pairing check
+    let seed_len = NumBytes::new(SEED_PVSS_PUBLIC_PARAMS.len() as u64);
+    let identity_len = NumBytes::new((TIMELOCK_IDENTITY_SCALAR_DST.len() + 64 + 32) as u64);
+    context.charge(
+        ALGEBRA_ARK_H2C_BLS12381G1_XMD_SHA256_SSWU_BASE
+            + ALGEBRA_ARK_H2C_BLS12381G1_XMD_SHA256_SSWU_PER_MSG_BYTE * seed_len
+            + ALGEBRA_ARK_H2C_BLS12381G2_XMD_SHA256_SSWU_BASE
+            + ALGEBRA_ARK_H2C_BLS12381G2_XMD_SHA256_SSWU_PER_MSG_BYTE * seed_len
+            + HASH_SHA3_512_BASE.per::<Arg>() * NumArgs::from(2)
+            + HASH_SHA3_512_PER_BYTE * identity_len
+            + ALGEBRA_ARK_BLS12_381_G1_PROJ_SCALAR_MUL
+            + ALGEBRA_ARK_BLS12_381_G1_PROJ_NEG
+            + ALGEBRA_ARK_BLS12_381_MULTI_PAIRING_BASE
+            + ALGEBRA_ARK_BLS12_381_MULTI_PAIRING_PER_PAIR * NumArgs::from(2)
+            + ALGEBRA_ARK_BLS12_381_FQ12_EQ,
+    )?;
+    let valid = verify_timelock_key_share(&share, &pk_share, &identity).is_ok();
+
+    Ok(smallvec![Value::bool(valid)])
+}
+
+macro_rules! aggregate_shares_internal_impl {
+    (
+        $context:expr,
+        $args:ident,
+        $g1_projective:ty,
+        $scalar_field:ty,
+        $per_share_inv_gas_cost:expr,
+        $per_share_msm_gas_cost:expr,
+        $g1_proj_to_affine_gas_cost:expr,
+        $field_mul_gas_cost:expr
+    ) => {{
+        let threshold = safely_pop_arg!($args, u64);
+        let share_handles = safely_pop_arg!($args, Vec<u64>);
+        let eval_point_handles = safely_pop_arg!($args, Vec<u64>);
+        let mut eval_points = Vec::with_capacity(eval_point_handles.len());
+        for handle in &eval_point_handles {
+            safe_borrow_element!($context, *handle as usize, $scalar_field, ptr, element);
+            eval_points.push(*element);
+        }
+        let threshold = check_aggregation_inputs(&eval_points, share_handles.len(), threshold)
+            .map_err(|abort_code| SafeNativeError::Abort { abort_code })?;
+
+        // Only the first `threshold` shares are interpolated. Computing their Lagrange coefficients
+        // takes one inversion and about 2 * threshold multiplications per share.
+        let num_shares = threshold as u64;
+        $context.charge(
+            ($per_share_inv_gas_cost
+                + $per_share_msm_gas_cost
+                + $g1_proj_to_affine_gas_cost.per::<Arg>())
+                * NumArgs::from(num_shares)
+                + $field_mul_gas_cost.per::<Arg>() * NumArgs::from(2 * num_shares * num_shares),
+        )?;
+        let mut bases = Vec::with_capacity(threshold);
+        for handle in &share_handles[..threshold] {
+            safe_borrow_element!($context, *handle as usize, $g1_projective, ptr, element);
+            bases.push(element.into_affine());
+        }
+        let new_element: $g1_projective = interpolate_at_zero(&eval_points[..threshold], &bases)
+            .ok_or_else(abort_invariant_violated)?;
+        let new_handle = store_element!($context, new_element)?;
+        Ok(smallvec![Value::u64(new_handle as u64)])
+    }};
+}
+
 pub fn decrypt_internal(
     context: &mut SafeNativeContext,
     ty_args: Vec<Type>,
@@ -137,3 +528,289 @@ pub fn decrypt_internal(
         }),
     }
 }
+
+pub fn verify_share_internal(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    assert_eq!(3, ty_args.len());
+    let g1_opt = structure_from_ty_arg!(context, &ty_args[0]);
+    let g2_opt = structure_from_ty_arg!(context, &ty_args[1]);
+    let gt_opt = structure_from_ty_arg!(context, &ty_args[2]);
+    abort_unless_ibe_enabled!(context, g1_opt, g2_opt, gt_opt);
+
+    match (g1_opt, g2_opt, gt_opt) {
+        (Some(Structure::BLS12381G1), Some(Structure::BLS12381G2), Some(Structure::BLS12381Gt)) => {
+            verify_share_internal_impl!(
+                context,
+                args,
+                ark_bls12_381::Bls12_381,
+                ark_bls12_381::G1Projective,
+                ark_bls12_381::G2Projective,
+                ALGEBRA_ARK_BLS12_381_MULTI_PAIRING_BASE,
+                ALGEBRA_ARK_BLS12_381_MULTI_PAIRING_PER_PAIR,
+                ALGEBRA_ARK_BLS12_381_G1_PROJ_NEG,
+                ALGEBRA_ARK_BLS12_381_G1_PROJ_TO_AFFINE,
+                ALGEBRA_ARK_BLS12_381_G2_PROJ_TO_AFFINE,
+                ALGEBRA_ARK_BLS12_381_G2_PROJ_GENERATOR,
+                ALGEBRA_ARK_BLS12_381_FQ12_EQ
+            )
+        },
+        _ => Err(SafeNativeError::Abort {
+            abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
+        }),
+    }
+}
+
+pub fn verify_timelock_share_internal(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    assert_eq!(3, ty_args.len());
+    let g1_opt = structure_from_ty_arg!(context, &ty_args[0]);
+    let g2_opt = structure_from_ty_arg!(context, &ty_args[1]);
+    let gt_opt = structure_from_ty_arg!(context, &ty_args[2]);
+    abort_unless_ibe_enabled!(context, g1_opt, g2_opt, gt_opt);
+
+    match (g1_opt, g2_opt, gt_opt) {
+        (Some(Structure::BLS12381G1), Some(Structure::BLS12381G2), Some(Structure::BLS12381Gt)) => {
+            verify_timelock_share_bls12381(context, &mut args)
+        },
+        _ => Err(SafeNativeError::Abort {
+            abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
+        }),
+    }
+}
+
+pub fn encrypt_internal(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    assert_eq!(4, ty_args.len());
+    let g1_opt = structure_from_ty_arg!(context, &ty_args[0]);
+    let g2_opt = structure_from_ty_arg!(context, &ty_args[1]);
+    let gt_opt = structure_from_ty_arg!(context, &ty_args[2]);
+    let scalar_field_opt = structure_from_ty_arg!(context, &ty_args[3]);
+    abort_unless_ibe_enabled!(context, g1_opt, g2_opt, gt_opt);
+
+    match (g1_opt, g2_opt, gt_opt, scalar_field_opt) {
+        (
+            Some(Structure::BLS12381G1),
+            Some(Structure::BLS12381G2),
+            Some(Structure::BLS12381Gt),
+            Some(Structure::BLS12381Fr),
+        ) => {
+            encrypt_internal_impl!(
+                context,
+                args,
+                ark_bls12_381::Bls12_381,
+                ark_bls12_381::G1Projective,
+                ark_bls12_381::G2Projective,
+                ark_bls12_381::Fr,
+                ALGEBRA_ARK_BLS12_381_PAIRING,
+                ALGEBRA_ARK_BLS12_381_G1_PROJ_SCALAR_MUL,
+                ALGEBRA_ARK_BLS12_381_G1_PROJ_TO_AFFINE,
+                ALGEBRA_ARK_BLS12_381_G2_PROJ_TO_AFFINE,
+                ALGEBRA_ARK_BLS12_381_FQ12_SERIALIZE
+            )
+        },
+        _ => Err(SafeNativeError::Abort {
+            abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
+        }),
+    }
+}
+
+pub fn aggregate_shares_internal(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    assert_eq!(2, ty_args.len());
+    let g1_opt = structure_from_ty_arg!(context, &ty_args[0]);
+    let scalar_field_opt = structure_from_ty_arg!(context, &ty_args[1]);
+    let flag_opt = feature_flag_of_ibe_aggregation(g1_opt, scalar_field_opt);
+    abort_unless_feature_flag_enabled!(context, flag_opt);
+
+    match (g1_opt, scalar_field_opt) {
+        (Some(Structure::BLS12381G1), Some(Structure::BLS12381Fr)) => {
+            aggregate_shares_internal_impl!(
+                context,
+                args,
+                ark_bls12_381::G1Projective,
+                ark_bls12_381::Fr,
+                ALGEBRA_IBE_AGGREGATE_PER_SHARE_INV,
+                ALGEBRA_IBE_AGGREGATE_PER_SHARE_MSM,
+                ALGEBRA_ARK_BLS12_381_G1_PROJ_TO_AFFINE,
+                ALGEBRA_ARK_BLS12_381_FR_MUL
+            )
+        },
+        _ => Err(SafeNativeError::Abort {
+            abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Projective};
+    use ark_serialize::CanonicalDeserialize;
+    use ark_std::{test_rng, UniformRand};
+    use ff::Field;
+
+    fn mask(g1: G1Projective, g2: G2Projective) -> [u8; 32] {
+        let mut k_bytes = vec![];
+        Bls12_381::pairing(g1.into_affine(), g2.into_affine())
+            .0
+            .serialize_uncompressed(&mut k_bytes)
+            .unwrap();
+        mask_from_pairing_output(&k_bytes)
+    }
+
+    #[test]
+    fn test_encrypt_then_decrypt() {
+        let mut rng = test_rng();
+        let msk = Fr::rand(&mut rng);
+        let r = Fr::rand(&mut rng);
+        let hashed_id = G1Projective::rand(&mut rng);
+        let mpk = G2Projective::generator() * msk;
+        let plaintext = b"a plaintext of at most 32 bytes".to_vec();
+
+        // What encrypt_internal computes
+        let ciphertext = xor_with_mask(&plaintext, &mask(hashed_id * r, mpk));
+        assert_ne!(ciphertext, plaintext);
+
+        // What decrypt_internal computes, given the decryption key and U = r * G2
+        let u = G2Projective::generator() * r;
+        let decrypted = xor_with_mask(&ciphertext, &mask(hashed_id * msk, u));
+        assert_eq!(decrypted, plaintext);
+
+        // The wrong key yields garbage
+        let wrong = xor_with_mask(&ciphertext, &mask(hashed_id * r, u));
+        assert_ne!(wrong, plaintext);
+    }
+
+    #[test]
+    fn test_message_length_capped_at_mask_length() {
+        assert!(check_message_length(&[0u8; MAX_IBE_MESSAGE_LENGTH]).is_ok());
+        assert!(matches!(
+            check_message_length(&[0u8; MAX_IBE_MESSAGE_LENGTH + 1]),
+            Err(SafeNativeError::Abort {
+                abort_code: E_IBE_MESSAGE_TOO_LONG
+            })
+        ));
+    }
+
+    fn eval_points(xs: &[u64]) -> Vec<Fr> {
+        xs.iter().map(|x| Fr::from(*x)).collect()
+    }
+
+    #[test]
+    fn test_aggregation_input_checks() {
+        assert_eq!(
+            check_aggregation_inputs(&eval_points(&[1, 2]), 3, 2),
+            Err(MOVE_ABORT_CODE_INPUT_VECTOR_SIZES_NOT_MATCHING)
+        );
+        assert_eq!(
+            check_aggregation_inputs(&eval_points(&[1, 2]), 2, 0),
+            Err(E_IBE_ZERO_THRESHOLD)
+        );
+        assert_eq!(
+            check_aggregation_inputs(&eval_points(&[1, 2]), 2, 3),
+            Err(E_IBE_NOT_ENOUGH_SHARES)
+        );
+        // Points past the threshold are checked too
+        assert_eq!(
+            check_aggregation_inputs(&eval_points(&[1, 2, 0]), 3, 2),
+            Err(E_IBE_ZERO_SHARE_INDEX)
+        );
+        assert_eq!(
+            check_aggregation_inputs(&eval_points(&[1, 2, 1]), 3, 2),
+            Err(E_IBE_DUPLICATE_SHARE_INDEX)
+        );
+        assert_eq!(
+            check_aggregation_inputs(&eval_points(&[3, 1, 2]), 3, 2),
+            Ok(2)
+        );
+    }
+
+    /// Interpolates `shares` of their `xs` at zero the way `aggregate_shares_internal` does,
+    /// returning the compressed key.
+    fn aggregate(
+        xs: &[blstrs::Scalar],
+        shares: &[blstrs::G1Projective],
+        threshold: u64,
+    ) -> Vec<u8> {
+        let xs: Vec<Fr> = xs
+            .iter()
+            .map(|x| Fr::from_le_bytes_mod_order(&x.to_bytes_le()))
+            .collect();
+        let bases: Vec<G1Affine> = shares
+            .iter()
+            .map(|share| G1Affine::deserialize_compressed(&share.to_compressed()[..]).unwrap())
+            .collect();
+        let threshold = check_aggregation_inputs(&xs, bases.len(), threshold).unwrap();
+        let aggregated: G1Projective =
+            interpolate_at_zero(&xs[..threshold], &bases[..threshold]).unwrap();
+        let mut bytes = vec![];
+        aggregated
+            .into_affine()
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    /// Evaluates `f(x) = 5 + 7x + 11x^2` at `x`.
+    fn f(x: &blstrs::Scalar) -> blstrs::Scalar {
+        [5u64, 7, 11]
+            .map(blstrs::Scalar::from)
+            .iter()
+            .rev()
+            .fold(blstrs::Scalar::ZERO, |acc, c| acc * x + c)
+    }
+
+    #[test]
+    fn test_aggregation_matches_aptos_dkg() {
+        // Shares of f(0) * H(id)
+        let hashed_id = aptos_dkg::ibe::hash_identity_to_g1(b"interval 42");
+        let shares: Vec<(u64, blstrs::G1Projective)> = [4u64, 1, 9, 2]
+            .into_iter()
+            .map(|x| (x, hashed_id * f(&blstrs::Scalar::from(x))))
+            .collect();
+        let expected = aptos_dkg::ibe::aggregate_decryption_shares(&shares, 3)
+            .unwrap()
+            .to_bytes();
+        assert_eq!(
+            expected,
+            (hashed_id * blstrs::Scalar::from(5u64))
+                .to_compressed()
+                .to_vec()
+        );
+
+        let (xs, shares): (Vec<_>, Vec<_>) = shares
+            .into_iter()
+            .map(|(x, share)| (blstrs::Scalar::from(x), share))
+            .unzip();
+        assert_eq!(aggregate(&xs, &shares, 3), expected);
+    }
+
+    #[test]
+    fn test_aggregation_at_roots_of_unity() {
+        // Shares of f(0) * h evaluated at roots of unity, as the sub-shares of a weighted DKG are
+        let dom = aptos_dkg::algebra::evaluation_domain::BatchEvaluationDomain::new(8);
+        let h = aptos_dkg::ibe::hash_identity_to_g1(b"h");
+        let (xs, shares): (Vec<_>, Vec<_>) = [6usize, 0, 3, 5]
+            .into_iter()
+            .map(|k| {
+                let x = dom.get_root_of_unity(k);
+                (x, h * f(&x))
+            })
+            .unzip();
+        let expected = (h * blstrs::Scalar::from(5u64)).to_compressed().to_vec();
+        assert_eq!(aggregate(&xs, &shares, 3), expected);
+        assert_ne!(aggregate(&xs, &shares, 2), expected);
+    }
+}
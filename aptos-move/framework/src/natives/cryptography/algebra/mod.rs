@@ -379,6 +379,19 @@ pub fn make_ibe(
 ) -> impl Iterator<Item = (String, NativeFunction)> + '_ {
     let natives = vec![
         ("decrypt_internal", ibe::decrypt_internal as RawSafeNative),
+        ("encrypt_internal", ibe::encrypt_internal as RawSafeNative),
+        (
+            "verify_share_internal",
+            ibe::verify_share_internal as RawSafeNative,
+        ),
+        (
+            "verify_timelock_share_internal",
+            ibe::verify_timelock_share_internal as RawSafeNative,
+        ),
+        (
+            "aggregate_shares_internal",
+            ibe::aggregate_shares_internal as RawSafeNative,
+        ),
     ];
     builder.make_named_natives(natives)
 }
@@ -8,23 +8,14 @@
 //! detailed introspection of the signature verification process.
 
 use aptos_cached_packages::aptos_stdlib;
-use aptos_crypto::{
-    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-    SigningKey, Uniform,
-};
-use aptos_language_e2e_tests::{
-    account::Account,
-    executor::FakeExecutor,
-};
+use aptos_language_e2e_tests::{account::Account, executor::FakeExecutor};
 use aptos_types::{
     account_address::AccountAddress,
-    transaction::{
-        authenticator::{AccountAuthenticator, TransactionAuthenticator},
-        SignedTransaction,
-        TransactionStatus,
-    },
+    transaction::{Auth, ExecutionStatus, TransactionStatus},
 };
+use helpers::*;
 use move_core_types::vm_status::StatusCode;
+use std::sync::Arc;
 
 /// Helper to create a simple APT transfer transaction using ethereum_derivable_account
 /// This mimics what our TypeScript code is doing
@@ -54,7 +45,8 @@ fn test_ethereum_derivable_simple_transfer() {
     println!("Chain ID: {}", raw_tx.chain_id());
 
     // 4. Sign with standard Ed25519 (this should work)
-    let signed_tx = sender.transaction()
+    let signed_tx = sender
+        .transaction()
         .payload(aptos_stdlib::aptos_account_transfer(receiver, 100))
         .sequence_number(0)
         .gas_unit_price(100)
@@ -68,13 +60,10 @@ fn test_ethereum_derivable_simple_transfer() {
     println!("\n=== Standard Signature Test ===");
     println!("Status: {:?}", output.status());
 
-    assert!(matches!(
-        output.status(),
-        TransactionStatus::Keep(_)
-    ));
+    assert!(matches!(output.status(), TransactionStatus::Keep(_)));
 
-    // Now let's try with a custom ethereum_derivable_account authenticator
-    // TODO: Implement ethereum authenticator construction
+    // The same transfer with an ethereum_derivable_account authenticator is submitted by
+    // test_ethereum_authenticator_debug
 }
 
 /// Test to understand how entry_function_name is extracted
@@ -86,16 +75,21 @@ fn test_entry_function_name_extraction() {
 
     // Create different entry function calls and see how they're represented
     let test_cases = vec![
-        ("0x1::aptos_account::transfer",
-         aptos_stdlib::aptos_account_transfer(receiver, 100)),
-        ("0x1::aptos_coin::transfer",
-         aptos_stdlib::aptos_coin_transfer(receiver, 100)),
+        (
+            "0x1::aptos_account::transfer",
+            aptos_stdlib::aptos_account_transfer(receiver, 100),
+        ),
+        (
+            "0x1::aptos_coin::transfer",
+            aptos_stdlib::aptos_coin_transfer(receiver, 100),
+        ),
     ];
 
     for (expected_name, payload) in test_cases {
         println!("\n=== Testing: {} ===", expected_name);
 
-        let signed_tx = sender.transaction()
+        let signed_tx = sender
+            .transaction()
             .payload(payload)
             .sequence_number(0)
             .gas_unit_price(100)
@@ -111,47 +105,264 @@ fn test_entry_function_name_extraction() {
     }
 }
 
-/// Placeholder for ethereum authenticator test
-/// This is where we'll construct the exact same authenticator as TypeScript
+/// Submit a transfer from an ethereum_derivable_account address, authenticated the same way
+/// as the TypeScript SDK does: a SIWE message signed with secp256k1, wrapped in a derivable
+/// `AccountAuthenticator::Abstract`.
 #[test]
-#[ignore] // TODO: Implement ethereum authenticator construction
 fn test_ethereum_authenticator_debug() {
     let mut executor = FakeExecutor::from_head_genesis();
+    let chain_id = executor.get_chain_id();
+    let receiver = AccountAddress::from_hex_literal("0x2").unwrap();
+
+    // 1. Derive the Aptos address of a fixed Ethereum key
+    let secret_key = libsecp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+    let eth_address = ethereum_address(&secret_key);
+    let domain = "localhost:3001";
+    let account_identity = abstract_public_key(&eth_address, domain);
+    let derived_address = derive_aptos_address_from_ethereum(&eth_address, domain);
+
+    println!("=== Derived Account ===");
+    println!("Ethereum address: {}", eth_address);
+    println!("Domain: {}", domain);
+    println!("Abstract public key: 0x{}", hex::encode(&account_identity));
+    println!("Derived Aptos address: {}", derived_address);
+
+    // 2. Fund the derived address
+    executor.store_and_fund_account(
+        Account::new_genesis_account(derived_address),
+        100_000_000,
+        0,
+    );
+
+    // 3-5. Sign the SIWE message of the transaction digest and wrap it in the abstract
+    // signature
+    let sign_function = move |digest: &[u8]| {
+        let message = construct_siwe_message(
+            domain,
+            &eth_address,
+            "0x1::aptos_account::transfer",
+            chain_id.id(),
+            &format!("0x{}", hex::encode(digest)),
+            ISSUED_AT,
+            "https",
+            "local",
+        );
+        println!("\n=== SIWE Message ===\n{}", message);
+        let signature = SIWEAbstractSignature::MessageV2 {
+            scheme: "https".to_string(),
+            issued_at: ISSUED_AT.to_string(),
+            signature: sign_personal_message(&secret_key, &message),
+        };
+        bcs::to_bytes(&signature).unwrap()
+    };
+
+    // 6. Build the transaction with an abstract sender authenticator
+    let raw_txn = Account::new_genesis_account(derived_address)
+        .transaction()
+        .payload(aptos_stdlib::aptos_account_transfer(receiver, 100))
+        .sequence_number(0)
+        .gas_unit_price(100)
+        .max_gas_amount(10000)
+        .raw();
+    let signed_txn = raw_txn
+        .sign_aa_transaction(
+            Auth::DerivableAbstraction {
+                function_info: ethereum_derivable_function_info(),
+                account_identity,
+                sign_function: Arc::new(sign_function),
+            },
+            vec![],
+            vec![],
+            None,
+        )
+        .unwrap()
+        .into_inner();
+    assert!(signed_txn.authenticator().sender().is_abstracted());
+
+    // 7. Submit it
+    let output = executor.execute_transaction(signed_txn);
+
+    // 8. Report why it failed, if it did
+    println!("\n=== Ethereum Derivable Account Test ===");
+    println!("Status: {:?}", output.status());
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success),
+        "{}",
+        describe_status(output.status())
+    );
+}
+
+/// Pins the address derived for a fixed Ethereum address and domain, to compare with
+/// getDerivedAddress() in TypeScript.
+#[test]
+fn test_derived_address_golden() {
+    assert_eq!(
+        hex::encode(abstract_public_key(
+            "0xC7B576Ead6aFb962E2DEcB35814FB29723AEC98a",
+            "localhost:3001"
+        )),
+        "2a3078433742353736456164366146623936324532444563423335383134464232393732334145433938610e6c6f63616c686f73743a33303031"
+    );
+    assert_eq!(
+        derive_aptos_address_from_ethereum(
+            "0xC7B576Ead6aFb962E2DEcB35814FB29723AEC98a",
+            "localhost:3001"
+        ),
+        AccountAddress::from_hex_literal(
+            "0x20023226f636bb4a1518c4154888b4fdd40efa375a09c82b2644cc2d24b5893d"
+        )
+        .unwrap()
+    );
+}
+
+/// The abstract signature starts with its variant, as `deserialize_abstract_signature` expects.
+#[test]
+fn test_abstract_signature_layout() {
+    let v1 = bcs::to_bytes(&SIWEAbstractSignature::MessageV1 {
+        issued_at: ISSUED_AT.to_string(),
+        signature: vec![1; 65],
+    })
+    .unwrap();
+    assert_eq!(v1[0], 0x00);
+    assert_eq!(v1[1] as usize, ISSUED_AT.len());
 
-    // TODO:
-    // 1. Derive Aptos address from Ethereum address using ethereum_derivable_account scheme
-    // 2. Fund that derived address
-    // 3. Construct SIWE message exactly as TypeScript does
-    // 4. Sign with secp256k1 (simulating MetaMask)
-    // 5. Build BCS SIWEAbstractSignature
-    // 6. Create TransactionAuthenticator::Abstract
-    // 7. Submit transaction
-    // 8. Debug why it fails
-
-    println!("TODO: Implement ethereum authenticator test");
-    println!("This will require:");
-    println!("  - ethers-rs for secp256k1 signing");
-    println!("  - Derivation scheme matching ethereum_derivable_account.move");
-    println!("  - BCS serialization of SIWEAbstractPublicKey and SIWEAbstractSignature");
+    let v2 = bcs::to_bytes(&SIWEAbstractSignature::MessageV2 {
+        scheme: "https".to_string(),
+        issued_at: ISSUED_AT.to_string(),
+        signature: vec![1; 65],
+    })
+    .unwrap();
+    assert_eq!(&v2[..7], b"\x01\x05https");
 }
 
 #[cfg(test)]
 mod helpers {
     use super::*;
+    use aptos_types::{function_info::FunctionInfo, transaction::authenticator::AuthenticationKey};
+    use serde::Serialize;
+    use tiny_keccak::{Hasher, Keccak};
+
+    /// Issued At of the SIWE messages signed by the tests.
+    pub const ISSUED_AT: &str = "2025-01-01T00:00:00.000Z";
+
+    /// Mirror of `ethereum_derivable_account::SIWEAbstractPublicKey`.
+    #[derive(Serialize)]
+    pub struct SIWEAbstractPublicKey {
+        /// The Ethereum address, with 0x prefix, in utf8 bytes
+        pub ethereum_address: Vec<u8>,
+        /// The domain, in utf8 bytes
+        pub domain: Vec<u8>,
+    }
+
+    /// Mirror of `ethereum_derivable_account::SIWEAbstractSignature`.
+    #[derive(Serialize)]
+    pub enum SIWEAbstractSignature {
+        /// Deprecated, use MessageV2 instead
+        MessageV1 {
+            issued_at: String,
+            signature: Vec<u8>,
+        },
+        MessageV2 {
+            scheme: String,
+            issued_at: String,
+            signature: Vec<u8>,
+        },
+    }
+
+    /// The authentication function registered at genesis for Ethereum derivable accounts.
+    pub fn ethereum_derivable_function_info() -> FunctionInfo {
+        FunctionInfo::new(
+            AccountAddress::ONE,
+            "ethereum_derivable_account".to_string(),
+            "authenticate".to_string(),
+        )
+    }
+
+    /// BCS serialized `SIWEAbstractPublicKey`, the identity of the derived account.
+    pub fn abstract_public_key(eth_address: &str, domain: &str) -> Vec<u8> {
+        bcs::to_bytes(&SIWEAbstractPublicKey {
+            ethereum_address: eth_address.as_bytes().to_vec(),
+            domain: domain.as_bytes().to_vec(),
+        })
+        .unwrap()
+    }
 
     /// Helper to derive Aptos address from Ethereum address
     /// Must match the scheme in ethereum_derivable_account.move
-    pub fn derive_aptos_address_from_ethereum(
-        eth_address: &str,
-        domain: &str,
-    ) -> AccountAddress {
-        // TODO: Implement derivation scheme
-        // This should match getDerivedAddress() in TypeScript
-        unimplemented!("Need to implement ethereum -> aptos address derivation")
+    pub fn derive_aptos_address_from_ethereum(eth_address: &str, domain: &str) -> AccountAddress {
+        // Same as account_abstraction::derive_account_address:
+        // sha3_256(bcs(function_info) | bcs(abstract_public_key) | DERIVABLE_ABSTRACTION_DERIVED_SCHEME)
+        AuthenticationKey::domain_abstraction_address(
+            bcs::to_bytes(&ethereum_derivable_function_info()).unwrap(),
+            &abstract_public_key(eth_address, domain),
+        )
+        .account_address()
+    }
+
+    /// Keccak256, as `aptos_hash::keccak256` computes it.
+    pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+        let mut hasher = Keccak::v256();
+        hasher.update(bytes);
+        let mut output = [0u8; 32];
+        hasher.finalize(&mut output);
+        output
+    }
+
+    /// The 0x-prefixed Ethereum address of `secret_key`: the last 20 bytes of the keccak256 of
+    /// the uncompressed public key without its 0x04 prefix.
+    pub fn ethereum_address(secret_key: &libsecp256k1::SecretKey) -> String {
+        let public_key = libsecp256k1::PublicKey::from_secret_key(secret_key).serialize();
+        format!("0x{}", hex::encode(&keccak256(&public_key[1..])[12..]))
+    }
+
+    /// Sign `message` the way ethers.js signMessage does: keccak256 of the message with the
+    /// "\x19Ethereum Signed Message:\n<length>" prefix, as r | s | v with v in {27, 28}.
+    pub fn sign_personal_message(secret_key: &libsecp256k1::SecretKey, message: &str) -> Vec<u8> {
+        let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+        let hash = libsecp256k1::Message::parse(&keccak256(prefixed.as_bytes()));
+        let (signature, recovery_id) = libsecp256k1::sign(&hash, secret_key);
+        let mut bytes = signature.serialize().to_vec();
+        bytes.push(recovery_id.serialize() + 27);
+        bytes
+    }
+
+    /// Name and description of an ethereum_derivable_account abort code.
+    pub fn abort_reason(code: u64) -> &'static str {
+        match code {
+            1 => "EINVALID_SIGNATURE: signature failed to verify",
+            2 => "EMISSING_ENTRY_FUNCTION_PAYLOAD: entry function payload is missing",
+            3 => "EINVALID_SIGNATURE_TYPE: invalid signature type",
+            4 => "EADDR_MISMATCH: recovered address does not match the ethereum address",
+            5 => "EUNEXPECTED_V: unexpected v value",
+            _ => "unknown abort code",
+        }
+    }
+
+    /// Describe a transaction status, decoding the abort codes of ethereum_derivable_account.
+    ///
+    /// Aborts during authentication discard the transaction with `ABORTED` and no code, so
+    /// the debug output of the module tells which check failed.
+    pub fn describe_status(status: &TransactionStatus) -> String {
+        match status {
+            TransactionStatus::Keep(ExecutionStatus::MoveAbort { location, code, .. }) => {
+                format!(
+                    "aborted in {} with code {} ({})",
+                    location,
+                    code,
+                    abort_reason(*code)
+                )
+            },
+            TransactionStatus::Discard(StatusCode::ABORTED) => {
+                "discarded: authentication aborted, see the debug output above".to_string()
+            },
+            _ => format!("{:?}", status),
+        }
     }
 
     /// Helper to construct SIWE message
     /// Must match constructSIWEMessage() in TypeScript
+    #[allow(clippy::too_many_arguments)]
     pub fn construct_siwe_message(
         domain: &str,
         eth_address: &str,
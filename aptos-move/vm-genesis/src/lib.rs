@@ -91,6 +91,8 @@ const RANDOMNESS_CONFIG_MODULE_NAME: &str = "randomness_config";
 const RANDOMNESS_MODULE_NAME: &str = "randomness";
 const ACCOUNT_ABSTRACTION_MODULE_NAME: &str = "account_abstraction";
 const RECONFIGURATION_STATE_MODULE_NAME: &str = "reconfiguration_state";
+const TIMELOCK_MODULE_NAME: &str = "timelock";
+const TIMELOCK_CONFIG_MODULE_NAME: &str = "timelock_config";
 
 const NUM_SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
 const MICRO_SECONDS_PER_SECOND: u64 = 1_000_000;
@@ -116,6 +118,27 @@ pub struct GenesisConfiguration {
     pub jwk_consensus_config_override: Option<OnChainJWKConsensusConfig>,
     pub initial_jwks: Vec<IssuerJWK>,
     pub keyless_groth16_vk: Option<Groth16VerificationKey>,
+    /// Defaults to `TimelockGenesisConfig::default()` if not set.
+    pub timelock_config: Option<TimelockGenesisConfig>,
+}
+
+/// How the timelock is set up at genesis.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TimelockGenesisConfig {
+    /// How often the timelock rotates to a new interval.
+    pub interval_microseconds: u64,
+    /// If false, the timelock is not initialized and never rotates.
+    pub enabled: bool,
+}
+
+impl Default for TimelockGenesisConfig {
+    /// The production config: enabled, with a 1-hour interval.
+    fn default() -> Self {
+        Self {
+            interval_microseconds: 3600 * MICRO_SECONDS_PER_SECOND,
+            enabled: true,
+        }
+    }
 }
 
 pub static GENESIS_KEYPAIR: Lazy<(Ed25519PrivateKey, Ed25519PublicKey)> = Lazy::new(|| {
@@ -336,6 +359,12 @@ pub fn encode_genesis_change_set(
         randomness_config,
     );
     initialize_randomness_resources(&mut session, &module_storage, &mut traversal_context);
+    initialize_timelock(
+        &mut session,
+        &module_storage,
+        &mut traversal_context,
+        genesis_config.timelock_config.unwrap_or_default(),
+    );
     initialize_on_chain_governance(
         &mut session,
         &module_storage,
@@ -437,6 +466,12 @@ fn validate_genesis_config(genesis_config: &GenesisConfiguration) {
             && genesis_config.voting_power_increase_limit <= 50,
         "voting_power_increase_limit must be > 0 and <= 50"
     );
+    if let Some(timelock_config) = genesis_config.timelock_config {
+        assert!(
+            !timelock_config.enabled || timelock_config.interval_microseconds > 0,
+            "Timelock interval must be > 0"
+        );
+    }
 }
 
 fn exec_function_internal(
@@ -700,6 +735,38 @@ fn initialize_randomness_config(
     );
 }
 
+fn initialize_timelock(
+    session: &mut SessionExt<impl AptosMoveResolver>,
+    module_storage: &impl AptosModuleStorage,
+    traversal_context: &mut TraversalContext,
+    timelock_config: TimelockGenesisConfig,
+) {
+    if !timelock_config.enabled {
+        return;
+    }
+    exec_function(
+        session,
+        module_storage,
+        traversal_context,
+        TIMELOCK_MODULE_NAME,
+        "initialize",
+        vec![],
+        serialize_values(&vec![MoveValue::Signer(CORE_CODE_ADDRESS)]),
+    );
+    exec_function(
+        session,
+        module_storage,
+        traversal_context,
+        TIMELOCK_CONFIG_MODULE_NAME,
+        "initialize_with_interval",
+        vec![],
+        serialize_values(&vec![
+            MoveValue::Signer(CORE_CODE_ADDRESS),
+            MoveValue::U64(timelock_config.interval_microseconds),
+        ]),
+    );
+}
+
 fn initialize_randomness_resources(
     session: &mut SessionExt<impl AptosMoveResolver>,
     module_storage: &impl AptosModuleStorage,
@@ -1442,6 +1509,7 @@ pub fn generate_test_genesis(
             jwk_consensus_config_override: None,
             initial_jwks: vec![],
             keyless_groth16_vk: None,
+            timelock_config: None,
         },
         &OnChainConsensusConfig::default_for_genesis(),
         &OnChainExecutionConfig::default_for_genesis(),
@@ -1494,6 +1562,7 @@ fn mainnet_genesis_config() -> GenesisConfiguration {
         jwk_consensus_config_override: None,
         initial_jwks: vec![],
         keyless_groth16_vk: None,
+        timelock_config: None,
     }
 }
 
@@ -30,9 +30,10 @@ use aptos_jwk_consensus::{start_jwk_consensus_runtime, types::JWKConsensusMsg};
 use aptos_mempool::QuorumStoreRequest;
 use aptos_network::application::interface::{NetworkClient, NetworkServiceEvents};
 use aptos_storage_interface::DbReaderWriter;
+use aptos_types::chain_id::ChainId;
 use aptos_validator_transaction_pool::VTxnPoolState;
 use futures::channel::mpsc::Sender;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::runtime::Runtime;
 
 /// Creates and starts the consensus runtime (if enabled)
@@ -67,6 +68,7 @@ pub fn create_consensus_runtime(
 /// Creates and starts the DKG runtime (if enabled)
 pub fn create_dkg_runtime(
     node_config: &mut NodeConfig,
+    chain_id: ChainId,
     dkg_subscriptions: Option<(
         ReconfigNotificationListener<DbBackedOnChainConfig>,
         EventNotificationListener,
@@ -86,6 +88,7 @@ pub fn create_dkg_runtime(
             let rb_config = node_config.consensus.rand_rb_config.clone();
             let dkg_runtime = start_dkg_runtime(
                 my_addr,
+                chain_id,
                 &node_config.consensus.safety_rules,
                 network_client,
                 network_service_events,
@@ -94,6 +97,8 @@ pub fn create_dkg_runtime(
                 vtxn_pool.clone(),
                 rb_config,
                 node_config.randomness_override_seq_num,
+                node_config.dkg.max_concurrent_timelock_dkgs,
+                Duration::from_secs(node_config.dkg.timelock_reveal_deferral_timeout_secs),
             );
             Some(dkg_runtime)
         },
@@ -98,6 +98,7 @@ pub fn create_event_subscription_service(
                 "0x1::dkg::DKGStartEvent".to_string(),
                 "0x1::timelock::StartKeyGenEvent".to_string(),
                 "0x1::timelock::RequestRevealEvent".to_string(),
+                "0x1::timelock::ShareReceivedEvent".to_string(),
             ])
             .expect("Consensus must subscribe to DKG events");
         Some((reconfig_events, dkg_start_events))
@@ -7,12 +7,19 @@ use serde::{Deserialize, Serialize};
 #[serde(default, deny_unknown_fields)]
 pub struct DKGConfig {
     pub max_network_channel_size: usize,
+    /// Maximum number of timelock DKGs run at once; further intervals are queued.
+    pub max_concurrent_timelock_dkgs: usize,
+    /// How long a timelock reveal request waits for the interval's DKG to finish locally before
+    /// it is given up on.
+    pub timelock_reveal_deferral_timeout_secs: u64,
 }
 
 impl Default for DKGConfig {
     fn default() -> Self {
         Self {
             max_network_channel_size: 256,
+            max_concurrent_timelock_dkgs: 2,
+            timelock_reveal_deferral_timeout_secs: 300,
         }
     }
 }
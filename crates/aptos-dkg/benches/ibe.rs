@@ -0,0 +1,226 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::blstrs::random_scalar;
+use aptos_dkg::{
+    algebra::{evaluation_domain::BatchEvaluationDomain, lagrange::lagrange_coefficients},
+    ibe::{
+        aggregate_decryption_shares, compute_timelock_identity, decrypt_for_timelock,
+        derive_decryption_key, derive_timelock_key_share, encrypt_for_timelock, ibe_decrypt,
+        ibe_decrypt_batch, ibe_encrypt, timelock_identity_point, verify_decryption_key,
+        verify_timelock_key_share, Ciphertext, MasterPublicKey,
+    },
+    pvss::{das, traits::HasEncryptionPublicParams},
+};
+use blstrs::{G1Projective, G2Projective, Scalar};
+use criterion::{
+    criterion_group, criterion_main, measurement::Measurement, BenchmarkGroup, BenchmarkId,
+    Criterion, Throughput,
+};
+use ff::Field;
+use group::Group;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+/// Message sizes for the encryption benchmarks.
+pub const MESSAGE_SIZES: [usize; 3] = [32, 1024, 65_536];
+
+/// Number of validators in the share-aggregation benchmarks.
+pub const NUM_VALIDATORS: u64 = 100;
+
+/// Reconstruction thresholds (out of `NUM_VALIDATORS`) in the share-aggregation benchmarks.
+pub const THRESHOLDS: [usize; 2] = [34, 67];
+
+/// Interval and chain ID of the timelock benchmarks.
+pub const INTERVAL: u64 = 1000;
+pub const CHAIN_ID: u8 = 1;
+
+pub fn ibe_group(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ibe");
+
+    for size in MESSAGE_SIZES {
+        encrypt(size, &mut group);
+    }
+    decrypt(&mut group);
+    decrypt_batch(1_000, &mut group);
+    derive(&mut group);
+    verify(&mut group);
+    for thresh in THRESHOLDS {
+        aggregate(thresh, NUM_VALIDATORS, &mut group);
+    }
+
+    group.finish();
+}
+
+/// Returns a fixed-seed RNG, so that inputs are identical across runs.
+fn seeded_rng() -> StdRng {
+    StdRng::seed_from_u64(42)
+}
+
+/// A timelock MPK `g_2^s`, as the timelock DKG deals it, and the aggregated decryption key of
+/// [`INTERVAL`] under it, both compressed.
+fn timelock_keys(rng: &mut StdRng) -> (Vec<u8>, Vec<u8>) {
+    let s = random_scalar(rng);
+    let g_2 = *das::PublicParameters::default().get_commitment_base();
+    let identity = compute_timelock_identity(INTERVAL, CHAIN_ID);
+    (
+        (g_2 * s).to_compressed().to_vec(),
+        (timelock_identity_point(&identity) * s)
+            .to_compressed()
+            .to_vec(),
+    )
+}
+
+fn encrypt<M: Measurement>(size: usize, g: &mut BenchmarkGroup<M>) {
+    let mut rng = seeded_rng();
+    let msk = random_scalar(&mut rng);
+    let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+    let identity = compute_timelock_identity(1000, 1);
+    let mut message = vec![0u8; size];
+    rng.fill_bytes(&mut message);
+
+    g.throughput(Throughput::Bytes(size as u64));
+    g.bench_function(BenchmarkId::new("encrypt", size), |b| {
+        b.iter(|| ibe_encrypt(&mpk, &identity, &message).unwrap())
+    });
+
+    let (timelock_mpk, _) = timelock_keys(&mut rng);
+    g.bench_function(BenchmarkId::new("encrypt_for_timelock", size), |b| {
+        b.iter(|| encrypt_for_timelock(&timelock_mpk, INTERVAL, CHAIN_ID, &message).unwrap())
+    });
+}
+
+fn decrypt<M: Measurement>(g: &mut BenchmarkGroup<M>) {
+    let mut rng = seeded_rng();
+    let msk = random_scalar(&mut rng);
+    let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+    let identity = compute_timelock_identity(1000, 1);
+    let dk = derive_decryption_key(&msk, &identity).unwrap();
+    let ciphertext = ibe_encrypt(&mpk, &identity, &[7u8; 32]).unwrap();
+
+    g.throughput(Throughput::Elements(1));
+    g.bench_function("decrypt", |b| {
+        b.iter(|| ibe_decrypt(&dk, &identity, &ciphertext).unwrap())
+    });
+
+    let (timelock_mpk, timelock_dk) = timelock_keys(&mut rng);
+    let ciphertext = encrypt_for_timelock(&timelock_mpk, INTERVAL, CHAIN_ID, &[7u8; 32])
+        .unwrap()
+        .to_bytes();
+    g.bench_function("decrypt_for_timelock", |b| {
+        b.iter(|| decrypt_for_timelock(&timelock_dk, INTERVAL, CHAIN_ID, &ciphertext).unwrap())
+    });
+}
+
+fn decrypt_batch<M: Measurement>(n: usize, g: &mut BenchmarkGroup<M>) {
+    let mut rng = seeded_rng();
+    let msk = random_scalar(&mut rng);
+    let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+    let identity = compute_timelock_identity(1000, 1);
+    let dk = derive_decryption_key(&msk, &identity).unwrap();
+
+    let ciphertexts: Vec<Ciphertext> = (0..n)
+        .map(|i| ibe_encrypt(&mpk, &identity, format!("sealed_bid_{i}").as_bytes()).unwrap())
+        .collect();
+
+    g.throughput(Throughput::Elements(n as u64));
+
+    g.bench_function(BenchmarkId::new("decrypt_loop", n), |b| {
+        b.iter(|| {
+            ciphertexts
+                .iter()
+                .map(|ct| ibe_decrypt(&dk, &identity, ct))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    g.bench_function(BenchmarkId::new("decrypt_batch", n), |b| {
+        b.iter(|| ibe_decrypt_batch(&dk, &identity, &ciphertexts))
+    });
+}
+
+fn derive<M: Measurement>(g: &mut BenchmarkGroup<M>) {
+    let mut rng = seeded_rng();
+    let msk = random_scalar(&mut rng);
+    let identity = compute_timelock_identity(1000, 1);
+
+    g.throughput(Throughput::Elements(1));
+    g.bench_function("derive_decryption_key", |b| {
+        b.iter(|| derive_decryption_key(&msk, &identity).unwrap())
+    });
+}
+
+fn verify<M: Measurement>(g: &mut BenchmarkGroup<M>) {
+    let mut rng = seeded_rng();
+    let msk = random_scalar(&mut rng);
+    let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+    let identity = compute_timelock_identity(1000, 1);
+    let dk = derive_decryption_key(&msk, &identity).unwrap();
+
+    g.throughput(Throughput::Elements(1));
+    g.bench_function("verify_decryption_key", |b| {
+        b.iter(|| verify_decryption_key(&dk, &mpk, &identity).unwrap())
+    });
+
+    let s = random_scalar(&mut rng);
+    let g_2 = *das::PublicParameters::default().get_commitment_base();
+    let timelock_mpk = g_2 * s;
+    let timelock_dk = timelock_identity_point(&identity) * s;
+    g.bench_function("verify_timelock_key_share", |b| {
+        b.iter(|| verify_timelock_key_share(&timelock_dk, &timelock_mpk, &identity).unwrap())
+    });
+}
+
+fn aggregate<M: Measurement>(thresh: usize, n: u64, g: &mut BenchmarkGroup<M>) {
+    let mut rng = seeded_rng();
+    let identity = compute_timelock_identity(1000, 1);
+
+    // Shamir-share a random MSK with a degree-(thresh - 1) polynomial, evaluated at x = 1..=n
+    let coeffs: Vec<Scalar> = (0..thresh).map(|_| random_scalar(&mut rng)).collect();
+    let shares: Vec<(u64, G1Projective)> = (1..=n)
+        .map(|x| {
+            let x_scalar = Scalar::from(x);
+            let sk_i = coeffs
+                .iter()
+                .rev()
+                .fold(Scalar::ZERO, |acc, c| acc * x_scalar + c);
+            let dk_i = derive_decryption_key(&sk_i, &identity).unwrap();
+            (x, *dk_i.as_point())
+        })
+        .collect();
+
+    g.throughput(Throughput::Elements(thresh as u64));
+    g.bench_function(
+        BenchmarkId::new(format!("aggregate_decryption_shares/n={n}"), thresh),
+        |b| b.iter(|| aggregate_decryption_shares(&shares[..thresh], thresh).unwrap()),
+    );
+
+    // The timelock DKG deals `h^{f(x)}` at the roots of unity x = omega^i instead, and the key
+    // shares derived from these interpolate at them (see `RealDKG::reconstruct_timelock_key`)
+    let h = *das::PublicParameters::default()
+        .get_encryption_public_params()
+        .message_base();
+    let dom = BatchEvaluationDomain::new(n as usize);
+    let key_shares: Vec<G1Projective> = (0..n as usize)
+        .map(|i| {
+            let x = dom.get_root_of_unity(i);
+            let f_x = coeffs.iter().rev().fold(Scalar::ZERO, |acc, c| acc * x + c);
+            derive_timelock_key_share(&(h * f_x), &identity)
+        })
+        .collect();
+    let ids: Vec<usize> = (0..thresh).collect();
+    g.bench_function(
+        BenchmarkId::new(format!("aggregate_timelock_key_shares/n={n}"), thresh),
+        |b| {
+            b.iter(|| {
+                let coeffs = lagrange_coefficients(&dom, &ids, &Scalar::ZERO);
+                G1Projective::multi_exp(&key_shares[..thresh], &coeffs)
+            })
+        },
+    );
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = ibe_group);
+criterion_main!(benches);
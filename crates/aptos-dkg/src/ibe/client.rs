@@ -0,0 +1,102 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Client-side (bidder) encryption entry point.
+//!
+//! Only needs the encrypt path, takes its inputs as bytes, returns the serialized ciphertext and
+//! draws all randomness from a caller-provided RNG. It builds without the rest of the crate, e.g.,
+//! for the browser:
+//!
+//! ```text
+//! cargo check --target wasm32-unknown-unknown -p aptos-dkg --no-default-features --features client
+//! ```
+
+use super::{compute_timelock_identity, encrypt_to_timelock_dkg, errors::Result, MasterPublicKey};
+use rand::{CryptoRng, RngCore};
+
+/// Encrypts `message` to the timelock interval `interval` on chain `chain_id`.
+///
+/// # Arguments
+/// * `mpk_bytes` - 96-byte compressed MPK, as published on chain by the interval's DKG
+/// * `interval` - Timelock interval whose decryption key will open the ciphertext
+/// * `chain_id` - Chain ID (to prevent cross-chain replay)
+/// * `message` - Plaintext message to encrypt
+/// * `rng` - Cryptographically secure RNG (e.g., backed by `crypto.getRandomValues` in a browser)
+///
+/// # Returns
+/// The serialized ciphertext (see `Ciphertext::to_bytes`), or an error if `mpk_bytes` is not a
+/// valid G2 point
+pub fn encrypt_for_interval(
+    mpk_bytes: &[u8],
+    interval: u64,
+    chain_id: u8,
+    message: &[u8],
+    mut rng: impl CryptoRng + RngCore,
+) -> Result<Vec<u8>> {
+    let mpk = MasterPublicKey::from_bytes(mpk_bytes)?;
+    let identity = compute_timelock_identity(interval, chain_id);
+    let ciphertext = encrypt_to_timelock_dkg(&mpk, &identity, message, &mut rng)?;
+    Ok(ciphertext.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ibe::{
+        ibe_decrypt, random_scalar, timelock_dkg_bases, timelock_identity_point, Ciphertext,
+        DecryptionKey,
+    };
+    use blstrs::G2Projective;
+    use group::Group;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_encrypt_for_interval() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let msk = random_scalar(&mut rng);
+        let mpk_bytes = (timelock_dkg_bases().1 * msk).to_compressed();
+
+        let bytes = encrypt_for_interval(&mpk_bytes, 1000, 1, b"secret_bid", &mut rng).unwrap();
+        let ciphertext = Ciphertext::from_bytes(&bytes).unwrap();
+
+        let identity = compute_timelock_identity(1000, 1);
+        let dk = DecryptionKey::new(timelock_identity_point(&identity) * msk);
+        assert_eq!(
+            ibe_decrypt(&dk, &identity, &ciphertext).unwrap(),
+            b"secret_bid"
+        );
+
+        // Bound to the interval and the chain id
+        for identity in [
+            compute_timelock_identity(1001, 1),
+            compute_timelock_identity(1000, 2),
+        ] {
+            let dk = DecryptionKey::new(timelock_identity_point(&identity) * msk);
+            assert!(ibe_decrypt(&dk, &identity, &ciphertext).is_err());
+        }
+    }
+
+    #[test]
+    fn test_encrypt_for_interval_is_deterministic_in_rng() {
+        let mpk_bytes = G2Projective::generator().to_compressed();
+
+        let a =
+            encrypt_for_interval(&mpk_bytes, 1000, 1, b"bid", StdRng::seed_from_u64(7)).unwrap();
+        let b =
+            encrypt_for_interval(&mpk_bytes, 1000, 1, b"bid", StdRng::seed_from_u64(7)).unwrap();
+        let c =
+            encrypt_for_interval(&mpk_bytes, 1000, 1, b"bid", StdRng::seed_from_u64(8)).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_encrypt_for_interval_rejects_invalid_mpk() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert!(encrypt_for_interval(&[], 1000, 1, b"bid", &mut rng).is_err());
+        assert!(encrypt_for_interval(&[0xFFu8; 96], 1000, 1, b"bid", &mut rng).is_err());
+        let g1_bytes = blstrs::G1Projective::generator().to_compressed();
+        assert!(encrypt_for_interval(&g1_bytes, 1000, 1, b"bid", &mut rng).is_err());
+    }
+}
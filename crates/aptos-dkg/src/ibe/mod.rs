@@ -8,42 +8,254 @@
 //! - Master Public Key (MPK): G2 point (96 bytes compressed)
 //! - Decryption Key (DK): G1 point (48 bytes compressed)
 //! - Identity: Arbitrary bytes (e.g., interval number)
+//! - Ciphertext: U (G2 point) plus an AES-256-GCM encryption of the message, keyed by
+//!   HKDF over the pairing output
+//!
+//! # Plain Boneh-Franklin and the timelock DKG
+//! [`ibe_encrypt`], [`derive_decryption_key`], [`verify_decryption_key`], [`verify_partial_key`]
+//! and [`aggregate_decryption_shares`] are plain Boneh-Franklin: Q_id = H(identity), the MPK is
+//! `msk * G2_generator` and shares sit at integer indices. Keys dealt by the timelock DKG do not
+//! have that form; use [`encrypt_for_timelock`], [`derive_timelock_key_share`],
+//! [`verify_timelock_key_share`] and [`decrypt_for_timelock`] for them.
 //!
 //! # Security Model
 //! - MPK is generated via threshold DKG by validators
 //! - Decryption keys are revealed only after the timelock period
+//! - Each timelock interval needs an MPK of its own: the decryption key of one interval reveals
+//!   the keys of all identities under its MPK (see [`timelock_identity_point`])
 //! - Uses pairing-based cryptography: e(G1, G2) -> Gt
+//! - Plain Boneh-Franklin ("BasicIdent") is only CPA-secure: flipping bits of V flips bits of
+//!   the plaintext. [`ibe_decrypt`] therefore only accepts AEAD ciphertexts, where V is
+//!   authenticated under a key derived from the pairing output and U is bound as associated
+//!   data, so any modification of U, the nonce or V makes decryption fail.
+//!
+//! # Client build
+//! With `--no-default-features --features client`, the crate only builds this module, without
+//! the PVSS, aptos-crypto and rayon (e.g., for `wasm32-unknown-unknown`). Bidders encrypt with
+//! [`client::encrypt_for_interval`]; the entry points that draw from `thread_rng` or run on the
+//! rayon thread pool need the default `full` feature.
+//!
+//! # On-chain compatibility
+//! Ciphertexts produced here cannot be decrypted by the Move natives
+//! `aptos_std::ibe::encrypt_internal` / `decrypt_internal` (and vice versa). The natives XOR the
+//! message with a plain `Keccak256(serialize_gt(gt))` mask, whereas this module uses AES-256-GCM
+//! keyed by HKDF over the pairing output, and even its [`CIPHERTEXT_VERSION_XOR`] mask is
+//! domain-separated with [`GT_KEY_DERIVATION_LABEL`]. Bridging the two needs a new Move native
+//! that implements the AEAD derivation; until then, decrypt off-chain.
 
+pub mod client;
 pub mod errors;
+mod types;
 
-use crate::weighted_vuf::bls::BLS_WVUF_DST;
-use anyhow::anyhow;
-use aptos_crypto::blstrs::{multi_pairing, random_scalar};
-use blstrs::{G1Projective, G2Projective, Gt, Scalar};
+use aes_gcm::{
+    aead::{Aead, Payload},
+    Aes256Gcm, Key, KeyInit, Nonce,
+};
+use anyhow::{anyhow, ensure};
+use blstrs::{Bls12, Fp12, G1Affine, G1Projective, G2Prepared, G2Projective, Gt, Scalar};
 use errors::Result;
-use group::Group;
+use ff::Field;
+use group::{Curve, Group};
+use hkdf::Hkdf;
+use pairing::{MillerLoopResult, MultiMillerLoop};
+#[cfg(feature = "full")]
 use rand::thread_rng;
-use sha3::{Digest, Keccak256};
-use std::iter;
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "full")]
+use rayon::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Keccak256, Sha3_256, Sha3_512};
+use std::{collections::HashSet, iter};
+pub use types::{
+    DecryptionKey, MasterPublicKey, SecretScalar, TimelockIdentity, TIMELOCK_IDENTITY_NUM_BYTES,
+};
+use zeroize::Zeroizing;
+
+/// The size in bytes of a compressed G2 point (U, or an MPK).
+const G2_PROJ_NUM_BYTES: usize = 96;
+
+/// Domain-separation tag and seed `das::PublicParameters::default()` derives the bases of the
+/// timelock DKG from (see [`timelock_dkg_bases`]).
+const DST_PVSS_PUBLIC_PARAMS: &[u8] = b"APTOS_DISTRIBUTED_RANDOMNESS_DST";
+const SEED_PVSS_PUBLIC_PARAMS: &[u8] = b"APTOS_DISTRIBUTED_RANDOMNESS_SEED";
+
+/// Domain-separation tag used to hash timelock identities to G1.
+///
+/// This is deliberately distinct from the weighted VUF's `BLS_WVUF_DST`, so that a randomness
+/// evaluation on crafted input can never double as a timelock decryption key.
+///
+/// Migration note: decryption keys derived, and ciphertexts produced, under the previous
+/// `BLS_WVUF_DST`-based hashing are not compatible with this DST. Bump the version suffix
+/// (rather than editing the tag in place) if the hashing ever needs to change again.
+pub const TIMELOCK_IBE_DST: &[u8] = b"APTOS_TIMELOCK_IBE_BLS12381G1_XMD:SHA-256_SSWU_RO_V1";
+
+/// Domain-separation tag used to hash timelock identities to the scalar `t` of
+/// [`timelock_identity_point`].
+pub const TIMELOCK_IDENTITY_SCALAR_DST: &[u8] = b"APTOS_TIMELOCK_IBE_IDENTITY_TO_SCALAR_V1";
+
+/// The size in bytes of the canonical serialization of a Gt element (see [`serialize_gt`]).
+pub const GT_NUM_BYTES: usize = 576;
+
+/// Versioned domain-separation label prepended to the serialized Gt element before hashing it
+/// into a symmetric key. Bump the version if the key derivation ever changes.
+pub const GT_KEY_DERIVATION_LABEL: &[u8] = b"APTOS_IBE_GT_TO_KEY_V1";
+
+/// Fixed, versioned HKDF salt used by [`derive_symmetric_key`].
+pub const SYMMETRIC_KEY_DERIVATION_SALT: &[u8] = b"APTOS_IBE_SYMMETRIC_KEY_SALT_V1";
+
+/// Prefix of the HKDF info used by [`derive_symmetric_key`]; the identity is appended to it.
+pub const SYMMETRIC_KEY_DERIVATION_INFO: &[u8] = b"APTOS_IBE_SYMMETRIC_KEY_INFO_V1";
+
+/// The size in bytes of the AES-256-GCM key.
+pub const AEAD_KEY_NUM_BYTES: usize = 32;
+
+/// The size in bytes of the AES-256-GCM nonce carried in a ciphertext.
+pub const AEAD_NONCE_NUM_BYTES: usize = 12;
+
+/// Ciphertext version of the original scheme: V = M XOR H(gid), with no nonce and no
+/// authentication. Only kept so that existing ciphertexts can still be parsed and decrypted.
+pub const CIPHERTEXT_VERSION_XOR: u8 = 0;
+
+/// Ciphertext version of the hybrid scheme: the pairing output is a KEM key from which an
+/// AES-256-GCM key is derived; V is the AEAD encryption of M with U as associated data.
+pub const CIPHERTEXT_VERSION_AEAD: u8 = 1;
 
 /// Ciphertext produced by IBE encryption.
 ///
-/// Structure: (U, V) where:
+/// Structure: (version, U, nonce, V) where:
 /// - U = r * G2_generator (randomness commitment)
-/// - V = M XOR H(e(Q_id, MPK)^r) (encrypted message)
+/// - V = AES-256-GCM(K, nonce, M, aad = U) with K = HKDF(e(Q_id, MPK)^r, identity)
+///
+/// For [`CIPHERTEXT_VERSION_XOR`] ciphertexts the nonce is all zeroes and
+/// V = M XOR H(e(Q_id, MPK)^r).
+///
+/// # Wire format
+/// `version (1 byte) || U (96-byte compressed G2) || nonce (12 bytes, AEAD only) || V`, where V
+/// is BCS-encoded (ULEB128 length prefix followed by the bytes). The serde implementation
+/// serializes this byte string (as hex in human-readable formats).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Ciphertext {
+    /// Scheme version (one of the `CIPHERTEXT_VERSION_*` constants)
+    pub version: u8,
     /// U component: r * G2_generator
     pub u: G2Projective,
-    /// V component: encrypted message bytes
+    /// AEAD nonce (all zeroes for [`CIPHERTEXT_VERSION_XOR`])
+    pub nonce: [u8; AEAD_NONCE_NUM_BYTES],
+    /// V component: encrypted message bytes (including the 16-byte GCM tag for AEAD ciphertexts)
     pub v: Vec<u8>,
 }
 
+impl Ciphertext {
+    /// Builds a ciphertext of the original two-field (U, V) XOR scheme.
+    pub fn new_xor(u: G2Projective, v: Vec<u8>) -> Self {
+        Self {
+            version: CIPHERTEXT_VERSION_XOR,
+            u,
+            nonce: [0u8; AEAD_NONCE_NUM_BYTES],
+            v,
+        }
+    }
+
+    /// Serializes the ciphertext into its compact wire format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.version];
+        bytes.extend_from_slice(&self.u.to_compressed());
+        if self.version != CIPHERTEXT_VERSION_XOR {
+            bytes.extend_from_slice(&self.nonce);
+        }
+        bytes.extend(bcs::to_bytes(&self.v).expect("serializing a byte vector should not fail"));
+        bytes
+    }
+
+    /// Deserializes a ciphertext from its compact wire format, rejecting unknown versions,
+    /// invalid U points, truncated input and trailing bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (&version, rest) = bytes
+            .split_first()
+            .ok_or_else(|| anyhow!("Empty IBE ciphertext"))?;
+        let nonce_len = match version {
+            CIPHERTEXT_VERSION_XOR => 0,
+            CIPHERTEXT_VERSION_AEAD => AEAD_NONCE_NUM_BYTES,
+            version => return Err(anyhow!("Unsupported IBE ciphertext version: {}", version)),
+        };
+        ensure!(
+            rest.len() >= G2_PROJ_NUM_BYTES + nonce_len,
+            "Truncated IBE ciphertext: got {} bytes",
+            bytes.len()
+        );
+
+        let (u_bytes, rest) = rest.split_at(G2_PROJ_NUM_BYTES);
+        let u = deserialize_g2(u_bytes)?;
+
+        let (nonce_bytes, v_bytes) = rest.split_at(nonce_len);
+        let mut nonce = [0u8; AEAD_NONCE_NUM_BYTES];
+        nonce[..nonce_len].copy_from_slice(nonce_bytes);
+
+        let v = bcs::from_bytes::<Vec<u8>>(v_bytes)
+            .map_err(|e| anyhow!("Invalid IBE ciphertext V component: {}", e))?;
+
+        Ok(Self {
+            version,
+            u,
+            nonce,
+            v,
+        })
+    }
+
+    /// Hex-encodes the wire format (without a `0x` prefix).
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// Parses a hex-encoded ciphertext, with or without a `0x` prefix.
+    pub fn from_hex(hex_str: &str) -> Result<Self> {
+        let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+        let bytes =
+            hex::decode(hex_str).map_err(|e| anyhow!("Invalid IBE ciphertext hex: {}", e))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl Serialize for Ciphertext {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_hex())
+        } else {
+            serializer.serialize_newtype_struct(
+                "Ciphertext",
+                serde_bytes::Bytes::new(self.to_bytes().as_slice()),
+            )
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Ciphertext {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let hex_str = <String>::deserialize(deserializer)?;
+            Ciphertext::from_hex(&hex_str).map_err(serde::de::Error::custom)
+        } else {
+            #[derive(Deserialize)]
+            #[serde(rename = "Ciphertext")]
+            struct Value(#[serde(with = "serde_bytes")] Vec<u8>);
+
+            let value = Value::deserialize(deserializer)?;
+            Ciphertext::from_bytes(&value.0).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
 /// Encrypts a message using Identity-Based Encryption.
 ///
 /// # Arguments
 /// * `mpk` - Master Public Key (G2 point from DKG)
-/// * `identity` - Identity bytes (e.g., sha256(interval || chain_id))
+/// * `identity` - Timelock identity (see [`compute_timelock_identity`])
 /// * `message` - Plaintext message to encrypt
 ///
 /// # Returns
@@ -51,96 +263,599 @@ pub struct Ciphertext {
 ///
 /// # Example
 /// ```ignore
-/// let mpk = ...; // From blockchain
+/// let mpk = MasterPublicKey::from_bytes(&mpk_bytes)?; // From blockchain
 /// let identity = compute_timelock_identity(interval, chain_id);
 /// let bid_data = b"secret_bid_100_tokens";
 /// let ciphertext = ibe_encrypt(&mpk, &identity, bid_data)?;
 /// ```
-#[allow(dead_code)]
-pub fn ibe_encrypt(mpk: &G2Projective, identity: &[u8], message: &[u8]) -> Result<Ciphertext> {
-    // Boneh-Franklin IBE encryption:
-    // C = <r*P, M XOR H(e(Q_ID, P_pub)^r)>
+#[cfg(feature = "full")]
+pub fn ibe_encrypt(
+    mpk: &MasterPublicKey,
+    identity: &TimelockIdentity,
+    message: &[u8],
+) -> Result<Ciphertext> {
+    ibe_encrypt_with_rng(mpk, identity, message, &mut thread_rng())
+}
+
+/// Encrypts `message` to the timelock interval `interval` on chain `chain_id`.
+///
+/// Validates `mpk_bytes` (96-byte compressed G2 point in the prime-order subgroup) and derives
+/// the identity with [`compute_timelock_identity`], so callers holding the MPK as fetched from
+/// chain cannot get either step wrong. Use [`Ciphertext::to_bytes`] to serialize the result.
+///
+/// The MPK is the dealt public key of the interval's timelock DKG, so the ciphertext is bound to
+/// [`timelock_identity_point`] rather than to `H(identity)`; it opens with the key the
+/// validators' [`derive_timelock_key_share`] shares aggregate to.
+#[cfg(feature = "full")]
+pub fn encrypt_for_timelock(
+    mpk_bytes: &[u8],
+    interval: u64,
+    chain_id: u8,
+    message: &[u8],
+) -> Result<Ciphertext> {
+    let mpk = MasterPublicKey::from_bytes(mpk_bytes)?;
+    let identity = compute_timelock_identity(interval, chain_id);
+    encrypt_to_timelock_dkg(&mpk, &identity, message, &mut thread_rng())
+}
+
+/// Encrypts `message` to `identity` under the dealt public key `mpk` of a timelock DKG.
+///
+/// Same as [`ibe_encrypt_with_rng`], with U = r * g_2 and Q_id = [`timelock_identity_point`].
+fn encrypt_to_timelock_dkg<R: RngCore + CryptoRng>(
+    mpk: &MasterPublicKey,
+    identity: &TimelockIdentity,
+    message: &[u8],
+    rng: &mut R,
+) -> Result<Ciphertext> {
+    let (_, g_2) = timelock_dkg_bases();
+    let r = SecretScalar::new(random_scalar(rng));
+    let u = g_2 * r.as_scalar();
+    let q_id = timelock_identity_point(identity);
+
+    encrypt_with_randomness(
+        mpk.as_point(),
+        &q_id,
+        identity.as_bytes(),
+        &r,
+        &u,
+        rng,
+        message,
+    )
+}
+
+/// The PVSS bases the timelock DKG deals with: `h`, in which the secret `h^s` and the
+/// validators' shares `h^{f(x_k)}` are dealt, and `g_2`, in which the MPK `g_2^s` is published.
+///
+/// Hashes to the curve exactly like `das::PublicParameters::default()`, which the client build
+/// does not include.
+fn timelock_dkg_bases() -> (G1Projective, G2Projective) {
+    (
+        G1Projective::hash_to_curve(
+            SEED_PVSS_PUBLIC_PARAMS,
+            DST_PVSS_PUBLIC_PARAMS,
+            b"h_with_bls_base",
+        ),
+        G2Projective::hash_to_curve(
+            SEED_PVSS_PUBLIC_PARAMS,
+            DST_PVSS_PUBLIC_PARAMS,
+            b"g_2_with_bls_base",
+        ),
+    )
+}
+
+/// Hashes `identity` to a scalar as SHA3-512(SHA3-512(dst) || identity) mod r, i.e., exactly
+/// like `utils::hash_to_scalar`, which the client build does not include.
+fn hash_timelock_identity_to_scalar(identity: &TimelockIdentity) -> Scalar {
+    let mut hasher = Sha3_512::new();
+    hasher.update(Sha3_512::digest(TIMELOCK_IDENTITY_SCALAR_DST));
+    hasher.update(identity.as_bytes());
+    let bytes = hasher.finalize();
+
+    // Reduce the 512-bit little-endian hash one 64-bit limb at a time, most significant first
+    let two_to_64 = Scalar::from(u64::MAX) + Scalar::ONE;
+    bytes.chunks(8).rev().fold(Scalar::ZERO, |acc, limb| {
+        acc * two_to_64 + Scalar::from(u64::from_le_bytes(limb.try_into().unwrap()))
+    })
+}
+
+/// Returns a uniformly random scalar, rejection-sampling 255-bit integers below the field order.
+///
+/// Unlike `aptos_crypto::blstrs::random_scalar`, only needs blstrs, so it is part of the client
+/// build.
+fn random_scalar<R: RngCore + CryptoRng>(rng: &mut R) -> Scalar {
+    loop {
+        let mut bytes = Zeroizing::new([0u8; 32]);
+        rng.fill_bytes(bytes.as_mut());
+        bytes[31] &= 0x7F;
+        if let Some(scalar) = Option::from(Scalar::from_bytes_le(&bytes)) {
+            return scalar;
+        }
+    }
+}
+
+/// Computes the product of the pairings `e(lhs_i, rhs_i)`, with a single final exponentiation.
+fn multi_pairing<'a, I1, I2>(lhs: I1, rhs: I2) -> Gt
+where
+    I1: Iterator<Item = &'a G1Projective>,
+    I2: Iterator<Item = &'a G2Projective>,
+{
+    let terms: Vec<(G1Affine, G2Prepared)> = lhs
+        .zip(rhs)
+        .map(|(g1, g2)| (g1.to_affine(), G2Prepared::from(g2.to_affine())))
+        .collect();
+    let term_refs: Vec<(&G1Affine, &G2Prepared)> = terms.iter().map(|(g1, g2)| (g1, g2)).collect();
+    Bls12::multi_miller_loop(&term_refs).final_exponentiation()
+}
+
+/// The G1 point `Q_id = t * h` that timelock ciphertexts for `identity` are bound to, where `t` is
+/// `identity` hashed to a scalar and `h` is the base the timelock DKG deals its secret in.
+///
+/// The DKG deals its secret as the group element `h^s`, and each validator only learns its
+/// shares `h^{f(x_k)}`, never the scalars. So, unlike in [`hash_identity_to_g1`], the identity is
+/// applied as a known scalar, which validators can apply to their shares: the decryption key is
+/// `t * h^s`.
+///
+/// # Security
+/// A revealed key gives away `h^s = t^{-1} * dk`, from which anyone derives the key of every
+/// other identity under the same MPK. Timelock ciphertexts are therefore only as safe as the
+/// freshness of the MPK of their interval: every interval must run a DKG of its own and no two
+/// intervals may share an MPK. The VM enforces this by only publishing the key of an interval
+/// from a transcript whose dealers signed that interval, and at most once per interval.
+pub fn timelock_identity_point(identity: &TimelockIdentity) -> G1Projective {
+    let (h, _) = timelock_dkg_bases();
+    h * hash_timelock_identity_to_scalar(identity)
+}
+
+/// Derives a decryption key share for `identity` from one of the G1 secret key shares a
+/// validator decrypted from the timelock DKG transcript.
+///
+/// Like the shares they are derived from, the key shares Lagrange-interpolate to the decryption
+/// key for [`encrypt_for_timelock`].
+pub fn derive_timelock_key_share(
+    dealt_share: &G1Projective,
+    identity: &TimelockIdentity,
+) -> G1Projective {
+    dealt_share * hash_timelock_identity_to_scalar(identity)
+}
+
+/// Checks a timelock decryption key share against the matching public key share `g_2^{f(x_k)}`
+/// of the DKG transcript, i.e., `e(key_share, g_2) == e(Q_id, pk_share)`.
+///
+/// Given the dealt public key as `pk_share`, checks an aggregated decryption key instead.
+pub fn verify_timelock_key_share(
+    key_share: &G1Projective,
+    pk_share: &G2Projective,
+    identity: &TimelockIdentity,
+) -> Result<()> {
+    let (_, g_2) = timelock_dkg_bases();
+    let q_id = timelock_identity_point(identity);
+    let lhs = [*key_share, -q_id];
+    let rhs = [g_2, *pk_share];
+    ensure!(
+        multi_pairing(lhs.iter(), rhs.iter()) == Gt::identity(),
+        "Invalid timelock decryption key share for the given public key share and identity"
+    );
+    Ok(())
+}
+
+/// Decrypts a serialized ciphertext for the timelock interval `interval` on chain `chain_id`.
+///
+/// Counterpart of [`encrypt_for_timelock`]: validates `dk_bytes` (48-byte compressed G1 point in
+/// the prime-order subgroup) and `ciphertext_bytes` (see [`Ciphertext::from_bytes`]) before
+/// decrypting.
+pub fn decrypt_for_timelock(
+    dk_bytes: &[u8],
+    interval: u64,
+    chain_id: u8,
+    ciphertext_bytes: &[u8],
+) -> Result<Vec<u8>> {
+    let dk = DecryptionKey::from_bytes(dk_bytes)?;
+    let ciphertext = Ciphertext::from_bytes(ciphertext_bytes)?;
+    let identity = compute_timelock_identity(interval, chain_id);
+    ibe_decrypt(&dk, &identity, &ciphertext)
+}
+
+/// Same as [`ibe_encrypt`], but draws the encryption randomness and the nonce from `rng`.
+///
+/// For callers that bring their own RNG (e.g., a seeded one in tests).
+pub fn ibe_encrypt_with_rng<R: RngCore + CryptoRng>(
+    mpk: &MasterPublicKey,
+    identity: &TimelockIdentity,
+    message: &[u8],
+    rng: &mut R,
+) -> Result<Ciphertext> {
+    let r = SecretScalar::new(random_scalar(rng));
+    let u = G2Projective::generator() * r.as_scalar();
+    let q_id = hash_identity_to_g1(identity.as_bytes());
+
+    encrypt_with_randomness(
+        mpk.as_point(),
+        &q_id,
+        identity.as_bytes(),
+        &r,
+        &u,
+        rng,
+        message,
+    )
+}
+
+/// Encrypts a message to an arbitrary identity using a raw MPK point.
+#[cfg(feature = "full")]
+#[deprecated(note = "use `ibe_encrypt` with `MasterPublicKey` and `TimelockIdentity` instead")]
+pub fn ibe_encrypt_raw(mpk: &G2Projective, identity: &[u8], message: &[u8]) -> Result<Ciphertext> {
+    encrypt_to_identity_bytes(mpk, identity, message)
+}
+
+#[cfg(feature = "full")]
+fn encrypt_to_identity_bytes(
+    mpk: &G2Projective,
+    identity: &[u8],
+    message: &[u8],
+) -> Result<Ciphertext> {
+    // Hybrid Boneh-Franklin IBE encryption:
+    // C = <r*P, nonce, AEAD(KDF(e(Q_ID, P_pub)^r), nonce, M, aad = r*P)>
     // where P = G2_generator, P_pub = MPK (G2), Q_ID = H(ID) (G1)
 
     // 1. Generate random scalar r using secure RNG
     let mut rng = thread_rng();
-    let r = random_scalar(&mut rng);
+    let r = SecretScalar::new(random_scalar(&mut rng));
 
     // 2. Compute U = r * G2_generator
-    let u = G2Projective::generator() * r;
+    let u = G2Projective::generator() * r.as_scalar();
 
     // 3. Hash identity to G1 curve point: Q_id = H(identity)
-    let q_id = G1Projective::hash_to_curve(identity, BLS_WVUF_DST, b"H(m)");
+    let q_id = hash_identity_to_g1(identity);
 
+    encrypt_with_randomness(mpk, &q_id, identity, &r, &u, &mut rng, message)
+}
+
+/// Encrypts the same message to several identities, sharing the encryption randomness.
+///
+/// Samples a single r and reuses U = r * G2_generator in every ciphertext, while each identity
+/// gets its own symmetric key derived from e(H(id_i), MPK)^r. This saves the per-recipient
+/// scalar multiplication for U and lets a caller store U once for the whole set (e.g., a bid
+/// that is valid across a window of intervals). Each returned ciphertext, in the order of
+/// `identities`, is an ordinary ciphertext for [`ibe_decrypt`].
+///
+/// Reusing r across recipients is safe for Boneh-Franklin: given U = r*P, the key material for
+/// id_i is e(Q_i, P_pub)^r = e(Q_i, U)^msk, and learning it for any set of identities (i.e.,
+/// holding their decryption keys) does not help computing it for another identity, exactly as
+/// for a single recipient (see Bellare, Boldyreva and Staddon, "Randomness Re-use in
+/// Multi-recipient Encryption Schemes", PKC 2003). Since the KDF also binds the identity, and
+/// every ciphertext uses a fresh nonce, no two ciphertexts ever share a (key, nonce) pair.
+#[cfg(feature = "full")]
+pub fn ibe_encrypt_multi(
+    mpk: &MasterPublicKey,
+    identities: &[TimelockIdentity],
+    message: &[u8],
+) -> Result<Vec<Ciphertext>> {
+    let mut rng = thread_rng();
+    let r = SecretScalar::new(random_scalar(&mut rng));
+    let u = G2Projective::generator() * r.as_scalar();
+
+    identities
+        .iter()
+        .map(|identity| {
+            encrypt_with_randomness(
+                mpk.as_point(),
+                &hash_identity_to_g1(identity.as_bytes()),
+                identity.as_bytes(),
+                &r,
+                &u,
+                &mut rng,
+                message,
+            )
+        })
+        .collect()
+}
+
+/// Encrypts `message` to `identity`, whose point is `q_id`, with the given encryption randomness r
+/// and U = r * G2_base.
+fn encrypt_with_randomness<R: RngCore>(
+    mpk: &G2Projective,
+    q_id: &G1Projective,
+    identity: &[u8],
+    r: &SecretScalar,
+    u: &G2Projective,
+    rng: &mut R,
+    message: &[u8],
+) -> Result<Ciphertext> {
     // 4. Compute gid = e(Q_id, MPK)^r
     // We compute e(Q_id, MPK) first, then raise to r
-    let pair = multi_pairing(iter::once(&q_id), iter::once(mpk));
-    let gid = pair * r;
+    let pair = multi_pairing(iter::once(q_id), iter::once(mpk));
+    let gid = pair * r.as_scalar();
 
-    // 5. Derive symmetric key K = H(gid)
-    let key_hash = hash_gt_to_bytes(&gid)?;
+    // 5. Derive symmetric key K = KDF(gid, identity)
+    let key = derive_symmetric_key(&gid, identity)?;
 
-    // 6. Encrypt message: V = M XOR K
-    let v = xor_bytes(message, &key_hash);
+    // 6. Encrypt message under a fresh nonce, binding U as associated data
+    let mut nonce = [0u8; AEAD_NONCE_NUM_BYTES];
+    rng.fill_bytes(&mut nonce);
+    let v = aead_encrypt(&key, &nonce, u, message)?;
 
     // 7. Return ciphertext
-    Ok(Ciphertext { u, v })
+    Ok(Ciphertext {
+        version: CIPHERTEXT_VERSION_AEAD,
+        u: *u,
+        nonce,
+        v,
+    })
 }
 
 /// Decrypts a ciphertext using the decryption key.
 ///
 /// # Arguments
-/// * `dk` - Decryption key (G1 point = H(identity)^msk)
+/// * `dk` - Decryption key (G1 point): `msk * H(identity)` for a ciphertext of [`ibe_encrypt`], or
+///   the key the [`derive_timelock_key_share`] shares aggregate to, `s * Q_id` with Q_id =
+///   [`timelock_identity_point`], for one of [`encrypt_for_timelock`]
+/// * `identity` - Timelock identity the ciphertext was encrypted to (bound into the symmetric key)
 /// * `ciphertext` - Ciphertext to decrypt
 ///
 /// # Returns
-/// Plaintext message bytes
+/// Plaintext message bytes, or an error if the ciphertext is not an AEAD ciphertext, U is the
+/// identity, or authentication fails (i.e., the ciphertext was tampered with or `dk` is wrong)
 ///
 /// # Example
 /// ```ignore
-/// let dk = ...; // From blockchain after reveal
-/// let plaintext = ibe_decrypt(&dk, &ciphertext)?;
+/// let dk = DecryptionKey::from_bytes(&dk_bytes)?; // From blockchain after reveal
+/// let plaintext = ibe_decrypt(&dk, &identity, &ciphertext)?;
 /// ```
-#[allow(dead_code)]
-pub fn ibe_decrypt(dk: &G1Projective, ciphertext: &Ciphertext) -> Result<Vec<u8>> {
+pub fn ibe_decrypt(
+    dk: &DecryptionKey,
+    identity: &TimelockIdentity,
+    ciphertext: &Ciphertext,
+) -> Result<Vec<u8>> {
+    decrypt_with_identity_bytes(dk.as_point(), identity.as_bytes(), ciphertext)
+}
+
+/// Decrypts many ciphertexts encrypted to the same identity, e.g., all sealed bids of an interval
+/// once its decryption key has been revealed.
+///
+/// Returns one result per ciphertext, in input order; a malformed or tampered ciphertext only
+/// fails its own entry. The per-ciphertext pairings run on the rayon thread pool.
+#[cfg(feature = "full")]
+pub fn ibe_decrypt_batch(
+    dk: &DecryptionKey,
+    identity: &TimelockIdentity,
+    ciphertexts: &[Ciphertext],
+) -> Vec<Result<Vec<u8>>> {
+    ciphertexts
+        .par_iter()
+        .map(|ciphertext| ibe_decrypt(dk, identity, ciphertext))
+        .collect()
+}
+
+/// Lazily decrypts a stream of ciphertexts encrypted to the same identity, one at a time.
+///
+/// Unlike [`ibe_decrypt_batch`], neither the ciphertexts nor the plaintexts need to be held in
+/// memory all at once.
+pub fn ibe_decrypt_iter<'a, I>(
+    dk: &'a DecryptionKey,
+    identity: &'a TimelockIdentity,
+    ciphertexts: I,
+) -> impl Iterator<Item = Result<Vec<u8>>> + 'a
+where
+    I: IntoIterator<Item = &'a Ciphertext>,
+    I::IntoIter: 'a,
+{
+    ciphertexts
+        .into_iter()
+        .map(move |ciphertext| ibe_decrypt(dk, identity, ciphertext))
+}
+
+/// Decrypts a ciphertext for an arbitrary identity using a raw decryption-key point.
+#[deprecated(note = "use `ibe_decrypt` with `DecryptionKey` and `TimelockIdentity` instead")]
+pub fn ibe_decrypt_raw(
+    dk: &G1Projective,
+    identity: &[u8],
+    ciphertext: &Ciphertext,
+) -> Result<Vec<u8>> {
+    decrypt_with_identity_bytes(dk, identity, ciphertext)
+}
+
+fn decrypt_with_identity_bytes(
+    dk: &G1Projective,
+    identity: &[u8],
+    ciphertext: &Ciphertext,
+) -> Result<Vec<u8>> {
     // Boneh-Franklin IBE decryption:
     // Recover symmetric key via pairing and decrypt
 
-    // 1. Compute gid = e(DK, U) = e(s*Q_id, r*P) = e(Q_id, P)^(sr)
-    let gid = multi_pairing(iter::once(dk), iter::once(&ciphertext.u));
+    // 1. Only authenticated ciphertexts are accepted
+    ensure!(
+        ciphertext.version == CIPHERTEXT_VERSION_AEAD,
+        "Unsupported IBE ciphertext version: {} (unauthenticated XOR ciphertexts must be \
+         decrypted with ibe_decrypt_xor)",
+        ciphertext.version
+    );
 
-    // 2. Derive symmetric key K = H(gid)
-    let key_hash = hash_gt_to_bytes(&gid)?;
+    // 2. Reject U = identity, for which e(DK, U) = 1 and the key is publicly known
+    ensure!(
+        !bool::from(ciphertext.u.is_identity()),
+        "Invalid IBE ciphertext: U is the identity"
+    );
 
-    // 3. Decrypt message: M = V XOR K
-    let plaintext = xor_bytes(&ciphertext.v, &key_hash);
+    // 3. Compute gid = e(DK, U) = e(s*Q_id, r*P) = e(Q_id, P)^(sr)
+    let gid = multi_pairing(iter::once(dk), iter::once(&ciphertext.u));
+
+    // 4. Derive symmetric key K = KDF(gid, identity) and authenticate-then-decrypt V
+    let key = derive_symmetric_key(&gid, identity)?;
+    aead_decrypt(&key, &ciphertext.nonce, &ciphertext.u, &ciphertext.v)
+}
 
-    // 4. Return plaintext
-    Ok(plaintext)
+/// Decrypts a [`CIPHERTEXT_VERSION_XOR`] ciphertext.
+///
+/// WARNING: XOR ciphertexts are unauthenticated and malleable; a tampered ciphertext decrypts to
+/// garbage (or attacker-controlled bit flips) instead of failing. Only use this for ciphertexts
+/// that predate the AEAD scheme.
+pub fn ibe_decrypt_xor(dk: &G1Projective, ciphertext: &Ciphertext) -> Result<Vec<u8>> {
+    ensure!(
+        ciphertext.version == CIPHERTEXT_VERSION_XOR,
+        "Expected an XOR IBE ciphertext, got version {}",
+        ciphertext.version
+    );
+    let gid = multi_pairing(iter::once(dk), iter::once(&ciphertext.u));
+    let key_hash = Zeroizing::new(hash_gt_to_bytes(&gid));
+    Ok(xor_bytes(&ciphertext.v, key_hash.as_slice()))
 }
 
-/// Derives a decryption key for a specific identity.
+/// Derives the plain Boneh-Franklin decryption key for a specific identity from the MSK.
 ///
-/// This is typically done by validators during the reveal phase.
+/// Validators never hold the MSK of a timelock DKG; they derive key shares with
+/// [`derive_timelock_key_share`] instead.
 ///
 /// # Arguments
 /// * `msk` - Master Secret Key (from DKG)
-/// * `identity` - Identity bytes
+/// * `identity` - Timelock identity
 ///
 /// # Returns
 /// Decryption key (G1 point)
-#[allow(dead_code)]
-pub fn derive_decryption_key(msk: &Scalar, identity: &[u8]) -> Result<G1Projective> {
+pub fn derive_decryption_key(msk: &Scalar, identity: &TimelockIdentity) -> Result<DecryptionKey> {
     // IBE key derivation: DK = msk * H(identity)
+    Ok(DecryptionKey::new(
+        hash_identity_to_g1(identity.as_bytes()) * msk,
+    ))
+}
+
+/// Derives a raw decryption-key point for an arbitrary identity.
+#[deprecated(note = "use `derive_decryption_key` with `TimelockIdentity` instead")]
+pub fn derive_decryption_key_raw(msk: &Scalar, identity: &[u8]) -> Result<G1Projective> {
+    Ok(hash_identity_to_g1(identity) * msk)
+}
+
+/// Verifies that `dk` is the plain Boneh-Franklin decryption key for `identity` under `mpk`.
+///
+/// Checks the pairing equation e(dk, G2_generator) == e(H(identity), mpk), i.e., that
+/// dk = msk * H(identity) for the msk behind `mpk`, as [`derive_decryption_key`] derives it for
+/// ciphertexts of [`ibe_encrypt`]. Lets a decryptor distinguish a malformed key from a malformed
+/// ciphertext.
+///
+/// Keys of the timelock DKG never pass this check, as they are bound to
+/// [`timelock_identity_point`] and its MPK is dealt in another G2 base; check those with
+/// [`verify_timelock_key_share`].
+pub fn verify_decryption_key(
+    dk: &DecryptionKey,
+    mpk: &MasterPublicKey,
+    identity: &TimelockIdentity,
+) -> Result<()> {
+    ensure!(
+        key_pairing_check(dk.as_point(), mpk.as_point(), identity),
+        "Invalid IBE decryption key for the given MPK and identity"
+    );
+    Ok(())
+}
+
+/// Same as [`verify_decryption_key`], but takes the 48-byte compressed decryption key and the
+/// 96-byte compressed MPK (e.g., as fetched from chain).
+pub fn verify_decryption_key_bytes(
+    dk_bytes: &[u8],
+    mpk_bytes: &[u8],
+    identity: &TimelockIdentity,
+) -> Result<()> {
+    let dk = DecryptionKey::from_bytes(dk_bytes)?;
+    let mpk = MasterPublicKey::from_bytes(mpk_bytes)?;
+    verify_decryption_key(&dk, &mpk, identity)
+}
+
+/// Verifies a single validator's plain Boneh-Franklin decryption-key share.
+///
+/// Checks e(share_i, G2_generator) == e(H(identity), pk_i), where `pk_i = sk_i * G2_generator`
+/// is that validator's public key share (i.e., `share_i` must equal sk_i * H(identity)).
+///
+/// The key shares of the timelock DKG never pass this check; check those against the public key
+/// shares of the DKG transcript with [`verify_timelock_key_share`].
+pub fn verify_partial_key(
+    share_i: &G1Projective,
+    pk_i: &G2Projective,
+    identity: &TimelockIdentity,
+) -> Result<()> {
+    ensure!(
+        key_pairing_check(share_i, pk_i, identity),
+        "Invalid IBE decryption-key share for the given public key share"
+    );
+    Ok(())
+}
+
+/// Returns whether e(g1, G2_generator) == e(H(identity), g2).
+fn key_pairing_check(g1: &G1Projective, g2: &G2Projective, identity: &TimelockIdentity) -> bool {
+    // e(g1, G2_gen) == e(Q_id, g2)  <=>  e(g1, G2_gen) * e(-Q_id, g2) == 1
+    let q_id = hash_identity_to_g1(identity.as_bytes());
+    let lhs = [*g1, -q_id];
+    let rhs = [G2Projective::generator(), *g2];
+
+    multi_pairing(lhs.iter(), rhs.iter()) == Gt::identity()
+}
+
+/// Aggregates plain Boneh-Franklin decryption-key shares into the full decryption key.
+///
+/// Each share is `(x_i, sk_i * H(identity))` where `x_i` is the (non-zero) integer evaluation
+/// point of the validator's Shamir share. The first `threshold` shares are combined via Lagrange
+/// interpolation in the exponent at x = 0, yielding msk * H(identity).
+///
+/// The timelock DKG evaluates its shares at roots of unity rather than at integers, so its key
+/// shares do not aggregate with this; see `RealDKG::reconstruct_timelock_key` in `aptos-types`.
+///
+/// # Errors
+/// Returns an error if `threshold` is zero, fewer than `threshold` shares are given, or any
+/// index is zero or duplicated.
+pub fn aggregate_decryption_shares(
+    shares: &[(u64, G1Projective)],
+    threshold: usize,
+) -> Result<DecryptionKey> {
+    ensure!(threshold > 0, "Threshold must be positive");
+    ensure!(
+        shares.len() >= threshold,
+        "Not enough decryption-key shares: got {}, need {}",
+        shares.len(),
+        threshold
+    );
+
+    let mut seen = HashSet::new();
+    for (index, _) in shares {
+        ensure!(*index != 0, "Decryption-key share index must be non-zero");
+        ensure!(
+            seen.insert(*index),
+            "Duplicate decryption-key share index {}",
+            index
+        );
+    }
+
+    let (indices, points): (Vec<u64>, Vec<G1Projective>) =
+        shares.iter().take(threshold).copied().unzip();
+    let coeffs = lagrange_coefficients_at_zero(&indices);
+
+    Ok(DecryptionKey::new(G1Projective::multi_exp(
+        &points, &coeffs,
+    )))
+}
 
-    // 1. Hash identity to G1 curve point: Q_id = H(identity)
-    let q_id = G1Projective::hash_to_curve(identity, BLS_WVUF_DST, b"H(m)");
+/// Computes the Lagrange coefficients at x = 0 for the given distinct, non-zero indices:
+/// lambda_i = prod_{j != i} x_j / (x_j - x_i).
+fn lagrange_coefficients_at_zero(indices: &[u64]) -> Vec<Scalar> {
+    let xs: Vec<Scalar> = indices.iter().map(|&x| Scalar::from(x)).collect();
 
-    // 2. Compute decryption key: DK = msk * Q_id
-    let dk = q_id * msk;
+    xs.iter()
+        .enumerate()
+        .map(|(i, x_i)| {
+            let (num, denom) = xs
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold((Scalar::ONE, Scalar::ONE), |(num, denom), (_, x_j)| {
+                    (num * x_j, denom * (*x_j - x_i))
+                });
+            // Indices are distinct, so the denominator is non-zero
+            num * denom.invert().unwrap()
+        })
+        .collect()
+}
 
-    // 3. Return decryption key
-    Ok(dk)
+/// Hashes an identity to a G1 point under [`TIMELOCK_IBE_DST`].
+///
+/// Every IBE code path (encryption, key derivation, share verification) must hash identities
+/// through this function so that the DST can never diverge between them.
+pub fn hash_identity_to_g1(identity: &[u8]) -> G1Projective {
+    G1Projective::hash_to_curve(identity, TIMELOCK_IBE_DST, &[])
 }
 
 /// Serializes a G2 point to compressed bytes (96 bytes).
@@ -150,7 +865,6 @@ pub fn derive_decryption_key(msk: &Scalar, identity: &[u8]) -> Result<G1Projecti
 ///
 /// # Returns
 /// 96-byte compressed representation
-#[allow(dead_code)]
 pub fn serialize_g2(point: &G2Projective) -> Result<Vec<u8>> {
     // Use blstrs compressed serialization (96 bytes for G2)
     Ok(point.to_compressed().to_vec())
@@ -163,7 +877,6 @@ pub fn serialize_g2(point: &G2Projective) -> Result<Vec<u8>> {
 ///
 /// # Returns
 /// G2 point
-#[allow(dead_code)]
 pub fn deserialize_g2(bytes: &[u8]) -> Result<G2Projective> {
     // Validate input length
     if bytes.len() != 96 {
@@ -189,14 +902,12 @@ pub fn deserialize_g2(bytes: &[u8]) -> Result<G2Projective> {
 }
 
 /// Serializes a G1 point to compressed bytes (48 bytes).
-#[allow(dead_code)]
 pub fn serialize_g1(point: &G1Projective) -> Result<Vec<u8>> {
     // Use blstrs compressed serialization (48 bytes for G1)
     Ok(point.to_compressed().to_vec())
 }
 
 /// Deserializes a G1 point from compressed bytes.
-#[allow(dead_code)]
 pub fn deserialize_g1(bytes: &[u8]) -> Result<G1Projective> {
     // Validate input length
     if bytes.len() != 48 {
@@ -221,31 +932,117 @@ pub fn deserialize_g1(bytes: &[u8]) -> Result<G1Projective> {
     }
 }
 
-/// Hashes a Gt element to bytes for use as a symmetric key.
+/// Serializes a Gt element to its canonical 576-byte representation.
 ///
-/// # Arguments
-/// * `gt` - Gt element from pairing
+/// The layout is the twelve Fp coefficients of the underlying Fp12 element, each encoded as 48
+/// little-endian bytes, in tower order: (c0, c1) of Fp12, then (c0, c1, c2) of each Fp6, then
+/// (c0, c1) of each Fp2. This is byte-for-byte identical to arkworks' `serialize_uncompressed`
+/// for `Fq12`, which is what the Move native `aptos_std::ibe::decrypt_internal` hashes.
+pub fn serialize_gt(gt: &Gt) -> Vec<u8> {
+    let fp12: Fp12 = (*gt).into();
+    let mut bytes = Vec::with_capacity(GT_NUM_BYTES);
+    for fp6 in [fp12.c0(), fp12.c1()] {
+        for fp2 in [fp6.c0(), fp6.c1(), fp6.c2()] {
+            bytes.extend_from_slice(&fp2.c0().to_bytes_le());
+            bytes.extend_from_slice(&fp2.c1().to_bytes_le());
+        }
+    }
+    bytes
+}
+
+/// Hashes a Gt element into the 32-byte XOR mask of [`CIPHERTEXT_VERSION_XOR`] ciphertexts.
 ///
-/// # Returns
-/// Key bytes (32 bytes for XOR)
+/// Computes `Keccak256(GT_KEY_DERIVATION_LABEL || serialize_gt(gt))`.
 ///
 /// # Implementation Note
-/// We use the debug format representation of Gt as input to the hash function.
-/// While not ideal, this is deterministic and provides sufficient randomness
-/// for the XOR-based encryption scheme. A production system might prefer
-/// to use a standardized Gt serialization format if available.
-#[allow(dead_code)]
-fn hash_gt_to_bytes(gt: &Gt) -> Result<Vec<u8>> {
-    // Hash the Gt element to derive a symmetric key
-    // Note: Gt from blstrs doesn't expose compressed serialization,
-    // so we use the debug format which is deterministic
+/// The Move natives `encrypt_internal` / `decrypt_internal` hash the same serialization but
+/// without the label, so these XOR ciphertexts are not interchangeable with on-chain ones (see
+/// the module docs).
+fn hash_gt_to_bytes(gt: &Gt) -> [u8; 32] {
     let mut hasher = Keccak256::new();
-    hasher.update(format!("{:?}", gt));
-    Ok(hasher.finalize().to_vec())
+    hasher.update(GT_KEY_DERIVATION_LABEL);
+    hasher.update(Zeroizing::new(serialize_gt(gt)));
+    hasher.finalize().into()
+}
+
+/// Legacy key derivation which hashed the `Debug` formatting of the Gt element.
+///
+/// Only kept so that ciphertexts produced before the switch to [`serialize_gt`] can still be
+/// decrypted via [`ibe_decrypt_legacy`]. Its output depends on blstrs' `Debug` impl and must
+/// not be used for new ciphertexts.
+fn hash_gt_to_bytes_legacy(gt: &Gt) -> Zeroizing<Vec<u8>> {
+    let mut hasher = Keccak256::new();
+    hasher.update(Zeroizing::new(format!("{:?}", gt)));
+    Zeroizing::new(hasher.finalize().to_vec())
+}
+
+/// Decrypts a ciphertext produced with the legacy, `Debug`-format-based key derivation.
+///
+/// Compatibility shim for already-encrypted fixtures; new code should use [`ibe_decrypt`].
+/// Legacy ciphertexts are XOR ciphertexts, so any other version is rejected.
+pub fn ibe_decrypt_legacy(dk: &G1Projective, ciphertext: &Ciphertext) -> Result<Vec<u8>> {
+    ensure!(
+        ciphertext.version == CIPHERTEXT_VERSION_XOR,
+        "Expected a legacy XOR IBE ciphertext, got version {}",
+        ciphertext.version
+    );
+    let gid = multi_pairing(iter::once(dk), iter::once(&ciphertext.u));
+    let key_hash = hash_gt_to_bytes_legacy(&gid);
+    Ok(xor_bytes(&ciphertext.v, &key_hash))
+}
+
+/// Derives the AES-256-GCM key for an identity from a pairing output.
+///
+/// The returned key (and the serialized pairing output used as IKM) is zeroized on drop.
+///
+/// Computes HKDF-SHA3-256 with IKM = [`serialize_gt`]`(gt)`, salt =
+/// [`SYMMETRIC_KEY_DERIVATION_SALT`] and info = [`SYMMETRIC_KEY_DERIVATION_INFO`]` || identity`,
+/// producing [`AEAD_KEY_NUM_BYTES`] bytes. Binding the identity (which for timelock identities
+/// already commits to the interval and chain id) ensures that ciphertexts for different
+/// identities never share a key, even if their pairing outputs collide. Any other
+/// implementation of this scheme (e.g., a framework native) must use this exact derivation.
+pub fn derive_symmetric_key(gt: &Gt, identity: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    let info = [SYMMETRIC_KEY_DERIVATION_INFO, identity].concat();
+    let ikm = Zeroizing::new(serialize_gt(gt));
+    let mut key = Zeroizing::new(vec![0u8; AEAD_KEY_NUM_BYTES]);
+    Hkdf::<Sha3_256>::new(Some(SYMMETRIC_KEY_DERIVATION_SALT), &ikm)
+        .expand(&info, &mut key)
+        .map_err(|e| anyhow!("IBE key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+/// Encrypts `message` with AES-256-GCM, authenticating the compressed U as associated data.
+fn aead_encrypt(
+    key: &[u8],
+    nonce: &[u8; AEAD_NONCE_NUM_BYTES],
+    u: &G2Projective,
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let aad = u.to_compressed();
+    cipher
+        .encrypt(Nonce::from_slice(nonce), Payload {
+            msg: message,
+            aad: &aad,
+        })
+        .map_err(|e| anyhow!("IBE AEAD encryption failed: {}", e))
+}
+
+/// Decrypts and authenticates an AES-256-GCM ciphertext produced by [`aead_encrypt`].
+fn aead_decrypt(
+    key: &[u8],
+    nonce: &[u8; AEAD_NONCE_NUM_BYTES],
+    u: &G2Projective,
+    v: &[u8],
+) -> Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let aad = u.to_compressed();
+    cipher
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: v, aad: &aad })
+        .map_err(|e| anyhow!("IBE AEAD decryption failed: {}", e))
 }
 
 /// XORs two byte slices, cycling the second if shorter.
-#[allow(dead_code)]
 fn xor_bytes(a: &[u8], b: &[u8]) -> Vec<u8> {
     a.iter()
         .zip(b.iter().cycle())
@@ -269,8 +1066,17 @@ fn xor_bytes(a: &[u8], b: &[u8]) -> Vec<u8> {
 /// let identity = compute_timelock_identity(1000, 1);
 /// // identity will be a deterministic 32-byte hash
 /// ```
-#[allow(dead_code)]
-pub fn compute_timelock_identity(interval: u64, chain_id: u8) -> Vec<u8> {
+pub fn compute_timelock_identity(interval: u64, chain_id: u8) -> TimelockIdentity {
+    TimelockIdentity::new(timelock_identity_hash(interval, chain_id))
+}
+
+/// Computes the timelock identity as raw bytes.
+#[deprecated(note = "use `compute_timelock_identity`, which returns a `TimelockIdentity`")]
+pub fn compute_timelock_identity_raw(interval: u64, chain_id: u8) -> Vec<u8> {
+    timelock_identity_hash(interval, chain_id).to_vec()
+}
+
+fn timelock_identity_hash(interval: u64, chain_id: u8) -> [u8; TIMELOCK_IDENTITY_NUM_BYTES] {
     // Construct canonical identity using Keccak256 (SHA3-256)
     let mut hasher = Keccak256::new();
 
@@ -284,12 +1090,15 @@ pub fn compute_timelock_identity(interval: u64, chain_id: u8) -> Vec<u8> {
     hasher.update(b"atomica_timelock");
 
     // Return 32-byte hash as identity
-    hasher.finalize().to_vec()
+    let mut identity = [0u8; TIMELOCK_IDENTITY_NUM_BYTES];
+    identity.copy_from_slice(&hasher.finalize());
+    identity
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_ibe_encrypt_decrypt_roundtrip() {
@@ -299,20 +1108,21 @@ mod tests {
         // 1. Generate test MSK and derive MPK
         let mut rng = thread_rng();
         let msk = random_scalar(&mut rng);
-        let mpk = G2Projective::generator() * msk;
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
 
         // 2. Test identity and message
-        let identity = b"test_identity_block_1000";
+        let identity = compute_timelock_identity(1000, 1);
         let message = b"secret_bid_value_12345";
 
         // 3. Encrypt the message
-        let ciphertext = ibe_encrypt(&mpk, identity, message).expect("Encryption should succeed");
+        let ciphertext = ibe_encrypt(&mpk, &identity, message).expect("Encryption should succeed");
 
         // 4. Derive decryption key for this identity
-        let dk = derive_decryption_key(&msk, identity).expect("Key derivation should succeed");
+        let dk = derive_decryption_key(&msk, &identity).expect("Key derivation should succeed");
 
         // 5. Decrypt and verify
-        let decrypted = ibe_decrypt(&dk, &ciphertext).expect("Decryption should succeed");
+        let decrypted =
+            ibe_decrypt(&dk, &identity, &ciphertext).expect("Decryption should succeed");
 
         assert_eq!(
             message.as_slice(),
@@ -321,6 +1131,39 @@ mod tests {
         );
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_raw_api_matches_typed_api() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let raw_mpk = G2Projective::generator() * msk;
+        let identity = compute_timelock_identity(1000, 1);
+        let raw_identity = compute_timelock_identity_raw(1000, 1);
+        assert_eq!(raw_identity, identity.as_bytes());
+
+        let raw_dk = derive_decryption_key_raw(&msk, &raw_identity).unwrap();
+        let dk = derive_decryption_key(&msk, &identity).unwrap();
+        assert_eq!(&raw_dk, dk.as_point());
+
+        // Ciphertexts produced by either API decrypt under the other
+        let ciphertext =
+            ibe_encrypt_raw(&raw_mpk, &raw_identity, b"secret_bid_value_12345").unwrap();
+        assert_eq!(
+            ibe_decrypt(&dk, &identity, &ciphertext).unwrap(),
+            b"secret_bid_value_12345"
+        );
+        let ciphertext = ibe_encrypt(
+            &MasterPublicKey::new(raw_mpk),
+            &identity,
+            b"secret_bid_value_12345",
+        )
+        .unwrap();
+        assert_eq!(
+            ibe_decrypt_raw(&raw_dk, &raw_identity, &ciphertext).unwrap(),
+            b"secret_bid_value_12345"
+        );
+    }
+
     #[test]
     fn test_serialize_deserialize_g2() {
         use aptos_crypto::blstrs::random_scalar;
@@ -392,7 +1235,11 @@ mod tests {
         );
 
         // Verify output length (32 bytes from Keccak256)
-        assert_eq!(identity1.len(), 32, "Identity should be 32 bytes");
+        assert_eq!(
+            identity1.as_bytes().len(),
+            32,
+            "Identity should be 32 bytes"
+        );
 
         // Test different intervals produce different outputs
         let identity_interval_1000 = compute_timelock_identity(1000, 1);
@@ -410,4 +1257,684 @@ mod tests {
             "Different chain IDs should produce different identities"
         );
     }
+
+    #[test]
+    fn test_serialize_gt_matches_arkworks() {
+        use ark_ec::pairing::Pairing;
+        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+        let g1 = G1Projective::generator() * Scalar::from(12345u64);
+        let g2 = G2Projective::generator() * Scalar::from(987654321u64);
+        let gt = multi_pairing(iter::once(&g1), iter::once(&g2));
+
+        // Recompute the same pairing with arkworks, as the Move native does
+        let ark_g1 =
+            ark_bls12_381::G1Affine::deserialize_compressed(&g1.to_compressed()[..]).unwrap();
+        let ark_g2 =
+            ark_bls12_381::G2Affine::deserialize_compressed(&g2.to_compressed()[..]).unwrap();
+        let ark_gt = ark_bls12_381::Bls12_381::pairing(ark_g1, ark_g2).0;
+        let mut ark_bytes = Vec::new();
+        ark_gt.serialize_uncompressed(&mut ark_bytes).unwrap();
+
+        let bytes = serialize_gt(&gt);
+        assert_eq!(bytes.len(), GT_NUM_BYTES);
+        assert_eq!(bytes, ark_bytes);
+    }
+
+    #[test]
+    fn test_hash_gt_to_bytes_golden_vector() {
+        // Pins the symmetric key derived for a fixed msk, identity and encryption randomness
+        let msk = Scalar::from(12345u64);
+        let r = Scalar::from(987654321u64);
+        let dk = hash_identity_to_g1(b"block_1000") * msk;
+        let u = G2Projective::generator() * r;
+        let gid = multi_pairing(iter::once(&dk), iter::once(&u));
+
+        let key = hash_gt_to_bytes(&gid);
+        assert_eq!(
+            hex::encode(key),
+            "b00893d7ab0d677061d6e464881dd349b161da0430a1bdbde21aae09b51218cf"
+        );
+    }
+
+    #[test]
+    fn test_derive_symmetric_key() {
+        let msk = Scalar::from(12345u64);
+        let r = Scalar::from(987654321u64);
+        let identity = compute_timelock_identity(1000, 1);
+        let dk = derive_decryption_key(&msk, &identity).unwrap();
+        let gid = multi_pairing(
+            iter::once(dk.as_point()),
+            iter::once(&(G2Projective::generator() * r)),
+        );
+
+        let key = derive_symmetric_key(&gid, identity.as_bytes()).unwrap();
+        assert_eq!(key.len(), AEAD_KEY_NUM_BYTES);
+        assert_eq!(
+            hex::encode(&key),
+            "dca593c90048a3e263cb03428e9bd2c7ef30deb165cefac804ae3e908ce60228"
+        );
+
+        // Same pairing output, different identity => different key
+        let other_identity = compute_timelock_identity(1000, 2);
+        let other_key = derive_symmetric_key(&gid, other_identity.as_bytes()).unwrap();
+        assert_ne!(key, other_key);
+        assert_eq!(
+            hex::encode(&other_key),
+            "22ef132eb49ff4f02cb21a25a0dad3ccc9bfd65ecc2ceeb88f46c7dd5c5965e7"
+        );
+    }
+
+    #[test]
+    fn test_ibe_decrypt_wrong_identity() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identity = compute_timelock_identity(1000, 1);
+        let dk = derive_decryption_key(&msk, &identity).unwrap();
+        let ciphertext = ibe_encrypt(&mpk, &identity, b"secret_bid_value_12345").unwrap();
+
+        let other_identity = compute_timelock_identity(1000, 2);
+        assert!(ibe_decrypt(&dk, &other_identity, &ciphertext).is_err());
+        assert!(ibe_decrypt(&dk, &identity, &ciphertext).is_ok());
+    }
+
+    #[test]
+    fn test_verify_decryption_key() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identity = compute_timelock_identity(1000, 1);
+        let dk = derive_decryption_key(&msk, &identity).unwrap();
+
+        // Correct key
+        assert!(verify_decryption_key(&dk, &mpk, &identity).is_ok());
+        assert!(verify_decryption_key_bytes(&dk.to_bytes(), &mpk.to_bytes(), &identity).is_ok());
+
+        // Key for the wrong identity
+        let other_identity = compute_timelock_identity(1001, 1);
+        let other_dk = derive_decryption_key(&msk, &other_identity).unwrap();
+        assert!(verify_decryption_key(&other_dk, &mpk, &identity).is_err());
+
+        // Key derived from the wrong msk
+        let wrong_dk = derive_decryption_key(&random_scalar(&mut rng), &identity).unwrap();
+        assert!(verify_decryption_key(&wrong_dk, &mpk, &identity).is_err());
+
+        // Malformed bytes
+        assert!(verify_decryption_key_bytes(&[0u8; 47], &mpk.to_bytes(), &identity).is_err());
+    }
+
+    /// Splits `msk` into `n` Shamir shares with threshold `t`, evaluated at x = 1..=n.
+    fn shamir_split(msk: &Scalar, t: usize, n: u64) -> Vec<(u64, Scalar)> {
+        let mut rng = thread_rng();
+        let coeffs: Vec<Scalar> = iter::once(*msk)
+            .chain((1..t).map(|_| random_scalar(&mut rng)))
+            .collect();
+
+        (1..=n)
+            .map(|x| {
+                let x_scalar = Scalar::from(x);
+                let y = coeffs
+                    .iter()
+                    .rev()
+                    .fold(Scalar::ZERO, |acc, c| acc * x_scalar + c);
+                (x, y)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aggregate_decryption_shares() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identity = compute_timelock_identity(1000, 1);
+        let ciphertext = ibe_encrypt(&mpk, &identity, b"secret_bid_value_12345").unwrap();
+
+        let (t, n) = (3, 5);
+        let sk_shares = shamir_split(&msk, t, n);
+        let dk_shares: Vec<(u64, G1Projective)> = sk_shares
+            .iter()
+            .map(|(x, sk_i)| {
+                (
+                    *x,
+                    *derive_decryption_key(sk_i, &identity).unwrap().as_point(),
+                )
+            })
+            .collect();
+
+        // Every share verifies against its own public key share, but not against another's
+        for (i, (_, sk_i)) in sk_shares.iter().enumerate() {
+            let pk_i = G2Projective::generator() * sk_i;
+            assert!(verify_partial_key(&dk_shares[i].1, &pk_i, &identity).is_ok());
+            let other = &dk_shares[(i + 1) % dk_shares.len()].1;
+            assert!(verify_partial_key(other, &pk_i, &identity).is_err());
+        }
+
+        // Any threshold subset aggregates to the full key and decrypts
+        let subset = vec![dk_shares[4], dk_shares[1], dk_shares[2]];
+        let dk = aggregate_decryption_shares(&subset, t).unwrap();
+        assert_eq!(dk, derive_decryption_key(&msk, &identity).unwrap());
+        assert!(verify_decryption_key(&dk, &mpk, &identity).is_ok());
+        assert_eq!(
+            ibe_decrypt(&dk, &identity, &ciphertext).unwrap(),
+            b"secret_bid_value_12345"
+        );
+
+        // Below threshold
+        assert!(aggregate_decryption_shares(&subset[..2], t).is_err());
+        assert!(aggregate_decryption_shares(&subset, 0).is_err());
+
+        // Duplicate index
+        let duplicate = vec![dk_shares[0], dk_shares[1], dk_shares[1]];
+        assert!(aggregate_decryption_shares(&duplicate, t).is_err());
+
+        // Zero index
+        let zero = vec![(0, dk_shares[0].1), dk_shares[1], dk_shares[2]];
+        assert!(aggregate_decryption_shares(&zero, t).is_err());
+    }
+
+    #[test]
+    fn test_ibe_decrypt_legacy() {
+        use crate::weighted_vuf::bls::BLS_WVUF_DST;
+
+        // Encrypted by the original implementation, which hashed identities to G1 under
+        // `BLS_WVUF_DST` and masked with Keccak256 of the pairing output's `Debug` format, for
+        // msk = 12345, r = 987654321 and the identity of interval 1000 on chain 1
+        let msk = Scalar::from(12345u64);
+        let identity = compute_timelock_identity(1000, 1);
+        let message = b"secret_bid_value";
+        let u = "b29cbccb70f3799eeb03645ea19a393af6f8c79b6ce446302ff8e075570bb0e08d3d11a57a56829285abc1b9eb51ea4302c931fb630414ad1478e24421893a7bf7911091e0713f58f507b8277b22ed70f4b7b87b90b2ed2f676d22b46692aaf5";
+        let v = "1fa073ece2d3231f670158a9bad74057";
+        let dk = "a8370bbabae1b7f42250c9431eae42d1e1076dacae73c5ac40161ca5ccc7984112cf290aac79b6987e6669139fcc637f";
+
+        let ciphertext = Ciphertext::new_xor(
+            deserialize_g2(&hex::decode(u).unwrap()).unwrap(),
+            hex::decode(v).unwrap(),
+        );
+        let dk = deserialize_g1(&hex::decode(dk).unwrap()).unwrap();
+        assert_eq!(
+            dk,
+            G1Projective::hash_to_curve(identity.as_bytes(), BLS_WVUF_DST, b"H(m)") * msk
+        );
+
+        let decrypted = ibe_decrypt_legacy(&dk, &ciphertext).unwrap();
+        assert_eq!(message.as_slice(), decrypted.as_slice());
+
+        // The canonical key derivation does not decrypt legacy ciphertexts
+        let decrypted = ibe_decrypt_xor(&dk, &ciphertext).unwrap();
+        assert_ne!(message.as_slice(), decrypted.as_slice());
+    }
+
+    #[test]
+    fn test_ibe_decrypt_legacy_rejects_aead() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identity = compute_timelock_identity(1000, 1);
+        let ciphertext = ibe_encrypt(&mpk, &identity, b"secret_bid_value").unwrap();
+        assert_eq!(ciphertext.version, CIPHERTEXT_VERSION_AEAD);
+
+        let dk = derive_decryption_key(&msk, &identity).unwrap();
+        assert!(ibe_decrypt_legacy(dk.as_point(), &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_ibe_encrypt_decrypt_message_lengths() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identity = compute_timelock_identity(1000, 1);
+        let dk = derive_decryption_key(&msk, &identity).unwrap();
+
+        for len in [0usize, 32, 4096] {
+            let message: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            let ciphertext = ibe_encrypt(&mpk, &identity, &message).unwrap();
+            assert_eq!(ciphertext.version, CIPHERTEXT_VERSION_AEAD);
+            // GCM appends a 16-byte authentication tag
+            assert_eq!(ciphertext.v.len(), len + 16);
+
+            let decrypted = ibe_decrypt(&dk, &identity, &ciphertext).unwrap();
+            assert_eq!(message, decrypted);
+        }
+    }
+
+    #[test]
+    fn test_timelock_dst_separation() {
+        use crate::weighted_vuf::bls::BLS_WVUF_DST;
+
+        assert_ne!(TIMELOCK_IBE_DST, BLS_WVUF_DST.as_slice());
+
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identity = compute_timelock_identity(1000, 1);
+        let ciphertext = ibe_encrypt(&mpk, &identity, b"secret_bid_value_12345").unwrap();
+
+        // A key derived under the weighted VUF's DST (the old hashing) does not decrypt
+        let old_dk = DecryptionKey::new(
+            G1Projective::hash_to_curve(identity.as_bytes(), BLS_WVUF_DST, b"H(m)") * msk,
+        );
+        assert!(ibe_decrypt(&old_dk, &identity, &ciphertext).is_err());
+
+        // Encryption and key derivation agree under the new DST
+        let dk = derive_decryption_key(&msk, &identity).unwrap();
+        assert_eq!(
+            dk.as_point(),
+            &(hash_identity_to_g1(identity.as_bytes()) * msk)
+        );
+        assert_eq!(
+            ibe_decrypt(&dk, &identity, &ciphertext).unwrap(),
+            b"secret_bid_value_12345"
+        );
+    }
+
+    #[test]
+    fn test_ibe_decrypt_detects_tampering() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identity = compute_timelock_identity(1000, 1);
+        let dk = derive_decryption_key(&msk, &identity).unwrap();
+        let ciphertext = ibe_encrypt(&mpk, &identity, b"secret_bid_value_12345").unwrap();
+
+        let mut tampered = ciphertext.clone();
+        tampered.v[0] ^= 1;
+        assert!(ibe_decrypt(&dk, &identity, &tampered).is_err());
+
+        let mut tampered = ciphertext.clone();
+        tampered.nonce[0] ^= 1;
+        assert!(ibe_decrypt(&dk, &identity, &tampered).is_err());
+
+        let mut tampered = ciphertext.clone();
+        tampered.u += G2Projective::generator();
+        assert!(ibe_decrypt(&dk, &identity, &tampered).is_err());
+
+        let mut tampered = ciphertext.clone();
+        tampered.u = G2Projective::identity();
+        assert!(ibe_decrypt(&dk, &identity, &tampered).is_err());
+
+        // A ciphertext re-encrypted under a different U (but the same V) is rejected too
+        let other = ibe_encrypt(&mpk, &identity, b"secret_bid_value_99999").unwrap();
+        let mut tampered = ciphertext.clone();
+        tampered.u = other.u;
+        tampered.nonce = other.nonce;
+        assert!(ibe_decrypt(&dk, &identity, &tampered).is_err());
+        let mut tampered = other.clone();
+        tampered.v = ciphertext.v.clone();
+        assert!(ibe_decrypt(&dk, &identity, &tampered).is_err());
+
+        // The untampered ciphertexts still decrypt
+        assert_eq!(
+            ibe_decrypt(&dk, &identity, &ciphertext).unwrap(),
+            b"secret_bid_value_12345"
+        );
+        assert_eq!(
+            ibe_decrypt(&dk, &identity, &other).unwrap(),
+            b"secret_bid_value_99999"
+        );
+
+        let mut tampered = ciphertext;
+        tampered.version = 2;
+        assert!(ibe_decrypt(&dk, &identity, &tampered).is_err());
+    }
+
+    #[test]
+    fn test_ibe_encrypt_multi() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identities: Vec<TimelockIdentity> = [1000, 1001, 1002]
+            .into_iter()
+            .map(|interval| compute_timelock_identity(interval, 1))
+            .collect();
+        let message = b"secret_bid_valid_for_three_intervals";
+
+        let ciphertexts = ibe_encrypt_multi(&mpk, &identities, message).unwrap();
+        assert_eq!(ciphertexts.len(), identities.len());
+
+        // All ciphertexts share U, but not their nonces or bodies
+        assert!(ciphertexts.iter().all(|ct| ct.u == ciphertexts[0].u));
+        assert_ne!(ciphertexts[0].nonce, ciphertexts[1].nonce);
+        assert_ne!(ciphertexts[0].v, ciphertexts[1].v);
+
+        for (i, identity) in identities.iter().enumerate() {
+            let dk = derive_decryption_key(&msk, identity).unwrap();
+            assert_eq!(
+                ibe_decrypt(&dk, identity, &ciphertexts[i]).unwrap(),
+                message
+            );
+
+            // A key for one interval cannot decrypt another interval's ciphertext
+            for (j, ciphertext) in ciphertexts.iter().enumerate() {
+                if i != j {
+                    assert!(ibe_decrypt(&dk, identity, ciphertext).is_err());
+                    assert!(ibe_decrypt(&dk, &identities[j], ciphertext).is_err());
+                }
+            }
+        }
+
+        assert!(ibe_encrypt_multi(&mpk, &[], message).unwrap().is_empty());
+    }
+
+    /// The compressed MPK a timelock DKG dealing `msk` publishes.
+    fn timelock_dkg_mpk_bytes(msk: &Scalar) -> [u8; G2_PROJ_NUM_BYTES] {
+        (timelock_dkg_bases().1 * msk).to_compressed()
+    }
+
+    /// The compressed decryption key the key shares of a timelock DKG dealing `msk` aggregate to.
+    fn timelock_dkg_dk_bytes(msk: &Scalar, interval: u64, chain_id: u8) -> Vec<u8> {
+        let identity = compute_timelock_identity(interval, chain_id);
+        DecryptionKey::new(timelock_identity_point(&identity) * msk).to_bytes()
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_for_timelock() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk_bytes = timelock_dkg_mpk_bytes(&msk);
+        let dk_bytes = timelock_dkg_dk_bytes(&msk, 1000, 1);
+
+        let ciphertext = encrypt_for_timelock(&mpk_bytes, 1000, 1, b"secret_bid").unwrap();
+        let ciphertext_bytes = ciphertext.to_bytes();
+        assert_eq!(
+            decrypt_for_timelock(&dk_bytes, 1000, 1, &ciphertext_bytes).unwrap(),
+            b"secret_bid"
+        );
+
+        // Wrong interval or chain id
+        assert!(decrypt_for_timelock(&dk_bytes, 1001, 1, &ciphertext_bytes).is_err());
+        assert!(decrypt_for_timelock(&dk_bytes, 1000, 2, &ciphertext_bytes).is_err());
+
+        // Not decryptable with a plain IBE key for the same master secret
+        let plain_dk_bytes = derive_decryption_key(&msk, &compute_timelock_identity(1000, 1))
+            .unwrap()
+            .to_bytes();
+        assert!(decrypt_for_timelock(&plain_dk_bytes, 1000, 1, &ciphertext_bytes).is_err());
+
+        // Malformed inputs
+        assert!(encrypt_for_timelock(&mpk_bytes[..95], 1000, 1, b"secret_bid").is_err());
+        assert!(encrypt_for_timelock(&[0xFFu8; 96], 1000, 1, b"secret_bid").is_err());
+        assert!(decrypt_for_timelock(&dk_bytes[..47], 1000, 1, &ciphertext_bytes).is_err());
+        assert!(decrypt_for_timelock(
+            &dk_bytes,
+            1000,
+            1,
+            &ciphertext_bytes[..ciphertext_bytes.len() - 1]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_client_primitives_match_full_build() {
+        use crate::{
+            pvss::{das, traits::HasEncryptionPublicParams},
+            utils::hash_to_scalar,
+        };
+
+        let pp = das::PublicParameters::default();
+        assert_eq!(
+            timelock_dkg_bases(),
+            (
+                *pp.get_encryption_public_params().message_base(),
+                *pp.get_commitment_base()
+            )
+        );
+
+        for interval in [0, 1000, u64::MAX] {
+            let identity = compute_timelock_identity(interval, 1);
+            assert_eq!(
+                hash_timelock_identity_to_scalar(&identity),
+                hash_to_scalar(identity.as_bytes(), TIMELOCK_IDENTITY_SCALAR_DST)
+            );
+        }
+
+        let mut rng = thread_rng();
+        let lhs = [
+            G1Projective::generator() * random_scalar(&mut rng),
+            G1Projective::generator() * random_scalar(&mut rng),
+        ];
+        let rhs = [G2Projective::generator(), timelock_dkg_bases().1];
+        let gt = multi_pairing(lhs.iter(), rhs.iter());
+        assert_eq!(
+            gt,
+            aptos_crypto::blstrs::multi_pairing(lhs.iter(), rhs.iter())
+        );
+
+        let identity = compute_timelock_identity(1000, 1);
+        let info = [SYMMETRIC_KEY_DERIVATION_INFO, identity.as_bytes()].concat();
+        let expected = aptos_crypto::hkdf::Hkdf::<Sha3_256>::extract_then_expand(
+            Some(SYMMETRIC_KEY_DERIVATION_SALT),
+            &serialize_gt(&gt),
+            Some(&info),
+            AEAD_KEY_NUM_BYTES,
+        )
+        .unwrap();
+        assert_eq!(
+            *derive_symmetric_key(&gt, identity.as_bytes()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_timelock_key_shares_aggregate_to_decryption_key() {
+        let mut rng = thread_rng();
+        let (h, g_2) = timelock_dkg_bases();
+        let identity = compute_timelock_identity(1000, 1);
+
+        // Deal h^s with a degree-1 polynomial f(x) = s + a * x
+        let s = random_scalar(&mut rng);
+        let a = random_scalar(&mut rng);
+        let f = |x: u64| s + a * Scalar::from(x);
+        let key_shares: Vec<(u64, G1Projective)> = [1u64, 2, 3]
+            .into_iter()
+            .map(|x| (x, derive_timelock_key_share(&(h * f(x)), &identity)))
+            .collect();
+        for (x, key_share) in &key_shares {
+            assert!(verify_timelock_key_share(key_share, &(g_2 * f(*x)), &identity).is_ok());
+            assert!(verify_timelock_key_share(key_share, &(g_2 * f(*x + 1)), &identity).is_err());
+        }
+
+        let dk = aggregate_decryption_shares(&key_shares[1..], 2).unwrap();
+        let mpk = g_2 * s;
+        assert!(verify_timelock_key_share(dk.as_point(), &mpk, &identity).is_ok());
+
+        let ciphertext =
+            encrypt_for_timelock(&mpk.to_compressed(), 1000, 1, b"secret_bid").unwrap();
+        assert_eq!(
+            decrypt_for_timelock(&dk.to_bytes(), 1000, 1, &ciphertext.to_bytes()).unwrap(),
+            b"secret_bid"
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn test_timelock_ciphertext_bound_to_chain_id(
+            interval in any::<u64>(),
+            chain_id in any::<u8>(),
+            other_chain_id in any::<u8>(),
+            message in proptest::collection::vec(any::<u8>(), 0..128),
+        ) {
+            prop_assume!(chain_id != other_chain_id);
+
+            let msk = random_scalar(&mut thread_rng());
+            let mpk_bytes = timelock_dkg_mpk_bytes(&msk);
+            let ciphertext_bytes = encrypt_for_timelock(&mpk_bytes, interval, chain_id, &message)
+                .unwrap()
+                .to_bytes();
+
+            let dk_bytes = timelock_dkg_dk_bytes(&msk, interval, chain_id);
+            let other_dk_bytes = timelock_dkg_dk_bytes(&msk, interval, other_chain_id);
+
+            prop_assert_eq!(
+                decrypt_for_timelock(&dk_bytes, interval, chain_id, &ciphertext_bytes).unwrap(),
+                message
+            );
+            prop_assert!(
+                decrypt_for_timelock(&other_dk_bytes, interval, other_chain_id, &ciphertext_bytes)
+                    .is_err()
+            );
+            prop_assert!(
+                decrypt_for_timelock(&other_dk_bytes, interval, chain_id, &ciphertext_bytes)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_ibe_decrypt_batch() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identity = compute_timelock_identity(1000, 1);
+        let dk = derive_decryption_key(&msk, &identity).unwrap();
+
+        let messages: Vec<Vec<u8>> = (0..8u8).map(|i| vec![i; 16 + i as usize]).collect();
+        let mut ciphertexts: Vec<Ciphertext> = messages
+            .iter()
+            .map(|m| ibe_encrypt(&mpk, &identity, m).unwrap())
+            .collect();
+
+        // Corrupt a few entries in different ways
+        ciphertexts[1].v[0] ^= 1;
+        ciphertexts[4].u = G2Projective::identity();
+        ciphertexts[6].version = CIPHERTEXT_VERSION_XOR;
+        let corrupted = [1, 4, 6];
+
+        let results = ibe_decrypt_batch(&dk, &identity, &ciphertexts);
+        assert_eq!(results.len(), ciphertexts.len());
+        for (i, result) in results.iter().enumerate() {
+            if corrupted.contains(&i) {
+                assert!(result.is_err());
+            } else {
+                assert_eq!(result.as_ref().unwrap(), &messages[i]);
+            }
+        }
+
+        // The streaming variant agrees with the batch one
+        let streamed: Vec<_> = ibe_decrypt_iter(&dk, &identity, &ciphertexts).collect();
+        for (a, b) in streamed.iter().zip(results.iter()) {
+            assert_eq!(a.as_ref().ok(), b.as_ref().ok());
+        }
+
+        assert!(ibe_decrypt_batch(&dk, &identity, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_ibe_decrypt_xor_version() {
+        let msk = Scalar::from(12345u64);
+        let r = Scalar::from(987654321u64);
+        let identity = b"block_1000";
+        let message = b"secret_bid_value_longer_than_32_bytes";
+
+        let q_id = hash_identity_to_g1(identity);
+        let mpk = G2Projective::generator() * msk;
+        let gid = multi_pairing(iter::once(&q_id), iter::once(&mpk)) * r;
+        let ciphertext = Ciphertext::new_xor(
+            G2Projective::generator() * r,
+            xor_bytes(message, &hash_gt_to_bytes(&gid)),
+        );
+
+        let dk = q_id * msk;
+        let decrypted = ibe_decrypt_xor(&dk, &ciphertext).unwrap();
+        assert_eq!(message.as_slice(), decrypted.as_slice());
+
+        // Unauthenticated ciphertexts are refused by ibe_decrypt...
+        #[allow(deprecated)]
+        let result = ibe_decrypt_raw(&dk, identity, &ciphertext);
+        assert!(result.is_err());
+
+        // ...because they are malleable
+        let mut tampered = ciphertext;
+        tampered.v[0] ^= 1;
+        let decrypted = ibe_decrypt_xor(&dk, &tampered).unwrap();
+        assert_eq!(decrypted[0], message[0] ^ 1);
+    }
+
+    #[test]
+    fn test_ciphertext_bytes_roundtrip() {
+        let mut rng = thread_rng();
+        let msk = random_scalar(&mut rng);
+        let mpk = MasterPublicKey::new(G2Projective::generator() * msk);
+        let identity = compute_timelock_identity(1000, 1);
+        let ciphertext = ibe_encrypt(&mpk, &identity, b"secret_bid_value_12345").unwrap();
+
+        let bytes = ciphertext.to_bytes();
+        assert_eq!(bytes.len(), 1 + 96 + 12 + 1 + ciphertext.v.len());
+        assert_eq!(Ciphertext::from_bytes(&bytes).unwrap(), ciphertext);
+
+        let hex_str = ciphertext.to_hex();
+        assert_eq!(Ciphertext::from_hex(&hex_str).unwrap(), ciphertext);
+        assert_eq!(
+            Ciphertext::from_hex(&format!("0x{}", hex_str)).unwrap(),
+            ciphertext
+        );
+
+        // BCS wraps the wire format in a single length-prefixed byte string
+        let bcs_bytes = bcs::to_bytes(&ciphertext).unwrap();
+        assert_eq!(bcs_bytes, bcs::to_bytes(&bytes).unwrap());
+        assert_eq!(
+            bcs::from_bytes::<Ciphertext>(&bcs_bytes).unwrap(),
+            ciphertext
+        );
+
+        // XOR ciphertexts carry no nonce
+        let xor = Ciphertext::new_xor(ciphertext.u, vec![1, 2, 3]);
+        let bytes = xor.to_bytes();
+        assert_eq!(bytes.len(), 1 + 96 + 1 + 3);
+        assert_eq!(Ciphertext::from_bytes(&bytes).unwrap(), xor);
+    }
+
+    #[test]
+    fn test_ciphertext_from_bytes_malformed() {
+        let ciphertext = Ciphertext {
+            version: CIPHERTEXT_VERSION_AEAD,
+            u: G2Projective::generator(),
+            nonce: [7u8; AEAD_NONCE_NUM_BYTES],
+            v: vec![1, 2, 3],
+        };
+        let bytes = ciphertext.to_bytes();
+
+        // Empty input
+        assert!(Ciphertext::from_bytes(&[]).is_err());
+
+        // Unknown version
+        let mut bad = bytes.clone();
+        bad[0] = 2;
+        assert!(Ciphertext::from_bytes(&bad).is_err());
+
+        // Truncated U
+        assert!(Ciphertext::from_bytes(&bytes[..50]).is_err());
+
+        // Invalid U point
+        let mut bad = bytes.clone();
+        bad[1..97].copy_from_slice(&[0xFFu8; 96]);
+        assert!(Ciphertext::from_bytes(&bad).is_err());
+
+        // Truncated V
+        assert!(Ciphertext::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // Trailing bytes
+        let mut bad = bytes.clone();
+        bad.push(0);
+        assert!(Ciphertext::from_bytes(&bad).is_err());
+
+        // Invalid hex
+        assert!(Ciphertext::from_hex("zz").is_err());
+    }
+
+    #[test]
+    fn test_ciphertext_golden_vector() {
+        let ciphertext = Ciphertext {
+            version: CIPHERTEXT_VERSION_AEAD,
+            u: G2Projective::generator(),
+            nonce: [7u8; AEAD_NONCE_NUM_BYTES],
+            v: vec![1, 2, 3],
+        };
+        let expected = "0193e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb807070707070707070707070703010203";
+        assert_eq!(ciphertext.to_hex(), expected);
+        assert_eq!(Ciphertext::from_hex(expected).unwrap(), ciphertext);
+    }
 }
@@ -0,0 +1,191 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed wrappers for the IBE inputs and outputs, so that an MPK, a decryption key and an
+//! identity cannot be swapped for one another (or for arbitrary points and bytes).
+
+use super::{deserialize_g1, deserialize_g2, errors::Result};
+use anyhow::anyhow;
+use blstrs::{G1Projective, G2Projective, Scalar};
+use ff::Field;
+use std::sync::atomic::{self, Ordering};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The size in bytes of a timelock identity.
+pub const TIMELOCK_IDENTITY_NUM_BYTES: usize = 32;
+
+/// Master Public Key: a G2 point produced by the timelock DKG (96 bytes compressed).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MasterPublicKey(G2Projective);
+
+impl MasterPublicKey {
+    /// Wraps a G2 point as an MPK.
+    pub fn new(point: G2Projective) -> Self {
+        Self(point)
+    }
+
+    /// Parses a 96-byte compressed G2 point, checking it is on the curve and in the subgroup.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        deserialize_g2(bytes).map(Self)
+    }
+
+    /// Returns the 96-byte compressed representation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_compressed().to_vec()
+    }
+
+    /// Returns the underlying G2 point.
+    pub fn as_point(&self) -> &G2Projective {
+        &self.0
+    }
+}
+
+/// Decryption key for one identity: msk * H(identity), a G1 point (48 bytes compressed).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecryptionKey(G1Projective);
+
+impl DecryptionKey {
+    /// Wraps a G1 point as a decryption key.
+    pub fn new(point: G1Projective) -> Self {
+        Self(point)
+    }
+
+    /// Parses a 48-byte compressed G1 point, checking it is on the curve and in the subgroup.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        deserialize_g1(bytes).map(Self)
+    }
+
+    /// Returns the 48-byte compressed representation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_compressed().to_vec()
+    }
+
+    /// Returns the underlying G1 point.
+    pub fn as_point(&self) -> &G1Projective {
+        &self.0
+    }
+}
+
+/// A 32-byte timelock identity, as computed by `compute_timelock_identity`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimelockIdentity([u8; TIMELOCK_IDENTITY_NUM_BYTES]);
+
+impl TimelockIdentity {
+    /// Wraps 32 identity bytes.
+    pub fn new(bytes: [u8; TIMELOCK_IDENTITY_NUM_BYTES]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parses an identity, checking it is exactly 32 bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes = <[u8; TIMELOCK_IDENTITY_NUM_BYTES]>::try_from(bytes).map_err(|_| {
+            anyhow!(
+                "Invalid timelock identity length: expected {}, got {}",
+                TIMELOCK_IDENTITY_NUM_BYTES,
+                bytes.len()
+            )
+        })?;
+        Ok(Self(bytes))
+    }
+
+    /// Returns the identity bytes.
+    pub fn to_bytes(&self) -> [u8; TIMELOCK_IDENTITY_NUM_BYTES] {
+        self.0
+    }
+
+    /// Returns the identity bytes as a slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A secret scalar (the MSK, a validator's share of it, or encryption randomness) that is
+/// zeroized when dropped.
+///
+/// Deliberately implements neither `Debug` nor `Clone`.
+pub struct SecretScalar(Scalar);
+
+impl SecretScalar {
+    /// Takes ownership of a secret scalar.
+    pub fn new(scalar: Scalar) -> Self {
+        Self(scalar)
+    }
+
+    /// Returns the underlying scalar.
+    pub fn as_scalar(&self) -> &Scalar {
+        &self.0
+    }
+}
+
+impl Zeroize for SecretScalar {
+    fn zeroize(&mut self) {
+        // `Scalar` does not implement `Zeroize`, so overwrite it and keep the compiler from
+        // eliding the write as a dead store.
+        self.0 = Scalar::ZERO;
+        std::hint::black_box(&mut self.0);
+        atomic::compiler_fence(Ordering::SeqCst);
+    }
+}
+
+impl Drop for SecretScalar {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretScalar {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use group::Group;
+    use zeroize::Zeroizing;
+
+    #[test]
+    fn test_master_public_key_bytes() {
+        let mpk = MasterPublicKey::new(G2Projective::generator());
+        let bytes = mpk.to_bytes();
+        assert_eq!(bytes.len(), 96);
+        assert_eq!(MasterPublicKey::from_bytes(&bytes).unwrap(), mpk);
+
+        assert!(MasterPublicKey::from_bytes(&bytes[..48]).is_err());
+        assert!(MasterPublicKey::from_bytes(&[0xFFu8; 96]).is_err());
+        // A valid G1 point is not a valid MPK
+        assert!(MasterPublicKey::from_bytes(&G1Projective::generator().to_compressed()).is_err());
+    }
+
+    #[test]
+    fn test_decryption_key_bytes() {
+        let dk = DecryptionKey::new(G1Projective::generator());
+        let bytes = dk.to_bytes();
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(DecryptionKey::from_bytes(&bytes).unwrap(), dk);
+
+        assert!(DecryptionKey::from_bytes(&[0xFFu8; 48]).is_err());
+        // A valid G2 point is not a valid decryption key
+        assert!(DecryptionKey::from_bytes(&G2Projective::generator().to_compressed()).is_err());
+    }
+
+    #[test]
+    fn test_timelock_identity_bytes() {
+        let identity = TimelockIdentity::new([7u8; 32]);
+        assert_eq!(TimelockIdentity::from_bytes(&[7u8; 32]).unwrap(), identity);
+        assert_eq!(identity.to_bytes(), [7u8; 32]);
+        assert_eq!(identity.as_bytes(), &[7u8; 32]);
+
+        assert!(TimelockIdentity::from_bytes(&[7u8; 31]).is_err());
+        assert!(TimelockIdentity::from_bytes(&[7u8; 33]).is_err());
+    }
+
+    #[test]
+    fn test_secret_scalar_zeroize() {
+        fn assert_zeroize_on_drop<T: Zeroize + ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<SecretScalar>();
+        assert_zeroize_on_drop::<Zeroizing<Vec<u8>>>();
+
+        let mut secret = SecretScalar::new(Scalar::from(12345u64));
+        assert_eq!(secret.as_scalar(), &Scalar::from(12345u64));
+        secret.zeroize();
+        assert_eq!(secret.as_scalar(), &Scalar::ZERO);
+    }
+}
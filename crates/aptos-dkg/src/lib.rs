@@ -17,27 +17,44 @@
 #![allow(clippy::to_string_in_format_args)]
 #![allow(clippy::borrow_interior_mutable_const)]
 
+#[cfg(feature = "full")]
 use crate::pvss::{traits, Player};
+#[cfg(feature = "full")]
 use aptos_crypto::arkworks::{
     random::{sample_field_element, UniformRand},
     shamir::{ShamirShare, ThresholdConfig},
 };
+#[cfg(feature = "full")]
 pub use aptos_crypto::blstrs::{G1_PROJ_NUM_BYTES, G2_PROJ_NUM_BYTES, SCALAR_NUM_BYTES};
+#[cfg(feature = "full")]
 use ark_ec::pairing::Pairing;
+#[cfg(feature = "full")]
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(feature = "full")]
 use more_asserts::{assert_ge, assert_le};
+#[cfg(feature = "full")]
 use rand::Rng;
+#[cfg(feature = "full")]
 pub use utils::random::DST_RAND_CORE_HELL;
 
+#[cfg(feature = "full")]
 pub mod algebra;
+#[cfg(feature = "full")]
 pub mod dlog;
+#[cfg(feature = "full")]
 pub(crate) mod fiat_shamir;
 pub mod ibe;
+#[cfg(feature = "full")]
 pub mod pcs;
+#[cfg(feature = "full")]
 pub mod pvss;
+#[cfg(feature = "full")]
 pub mod range_proofs;
+#[cfg(feature = "full")]
 pub mod sigma_protocol;
+#[cfg(feature = "full")]
 pub mod utils;
+#[cfg(feature = "full")]
 pub mod weighted_vuf;
 
 /// A wrapper around `E::ScalarField` to prevent overlapping trait implementations.
@@ -53,10 +70,12 @@ pub mod weighted_vuf;
 /// Similarly, this issue also arises with blanket implementations like:
 /// `impl<T: Trait> for Vec<T>`, since `Vec<T>` could itself be an
 /// `E::ScalarField`.
+#[cfg(feature = "full")]
 #[repr(transparent)]
 #[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Scalar<E: Pairing>(pub E::ScalarField); // TODO: Maybe this should be Scalar<F: PrimeField> ?? (PrimeField is needed for ThresholdConfig below)
 
+#[cfg(feature = "full")]
 impl<E: Pairing> Scalar<E> {
     /// Converts a `&[Scalar<E>]` into a `&[E::ScalarField]`; could do this without copying
     /// (and similarly for the other functions below) by using `#[repr(transparent)]` and
@@ -89,12 +108,14 @@ impl<E: Pairing> Scalar<E> {
     }
 }
 
+#[cfg(feature = "full")]
 impl<E: Pairing> UniformRand for Scalar<E> {
     fn rand<R: Rng>(rng: &mut R) -> Self {
         Scalar(sample_field_element(rng))
     }
 }
 
+#[cfg(feature = "full")]
 impl<E: Pairing> traits::Reconstructable<ThresholdConfig<E::ScalarField>> for Scalar<E> {
     type Share = Scalar<E>;
 
@@ -1,5 +1,5 @@
-use aptos_dkg::weighted_vuf::bls::BLS_WVUF_DST;
 use aptos_crypto::blstrs::multi_pairing;
+use aptos_dkg::ibe::hash_identity_to_g1;
 use blstrs::{G1Projective, G2Projective, Gt, Scalar};
 use group::Group;
 use sha3::{Digest, Keccak256};
@@ -30,8 +30,8 @@ fn test_tlock_ibe_poc() {
     // 3. Extraction (Validator Side - Threshold DKG)
     // Validators compute signature on identity: sigma = H(id)^s
     // In our POC, we just use the MSK directly to simulate the aggregated result
-    // Hash to curve manually since function is private in BlsWUF but DST is public
-    let h_id = G1Projective::hash_to_curve(identity, BLS_WVUF_DST, b"H(m)");
+    // Hash to curve with the timelock IBE DST, shared with the library's encrypt/derive paths
+    let h_id = hash_identity_to_g1(identity);
     let decryption_key = h_id * msk; // This is the aggregated signature
 
     // 4. Decryption (Client/Public Side)
@@ -63,7 +63,7 @@ fn ibe_encrypt(mpk: G2Projective, identity: &[u8], message: &[u8]) -> Ciphertext
     let u = G2Projective::generator() * r;
 
     // Q_ID = H(ID) in G1
-    let q_id = G1Projective::hash_to_curve(identity, BLS_WVUF_DST, b"H(m)");
+    let q_id = hash_identity_to_g1(identity);
 
     // g_id = e(Q_ID, MPK)^r
     // multi_pairing takes iterables of &Projective
@@ -17,7 +17,9 @@ use aptos_types::{
     waypoint::Waypoint,
 };
 use aptos_vm::aptos_vm::AptosVMBlockExecutor;
-use aptos_vm_genesis::{AccountBalance, EmployeePool, ValidatorWithCommissionRate};
+use aptos_vm_genesis::{
+    AccountBalance, EmployeePool, TimelockGenesisConfig, ValidatorWithCommissionRate,
+};
 
 /// Holder object for all pieces needed to generate a genesis transaction
 #[derive(Clone)]
@@ -65,6 +67,8 @@ pub struct MainnetGenesisInfo {
     randomness_config_override: Option<OnChainRandomnessConfig>,
     /// An optional feature vec to replace `OnChainJWKConsensusConfig::default_for_genesis()`.
     jwk_consensus_config_override: Option<OnChainJWKConsensusConfig>,
+    /// An optional timelock config to replace `TimelockGenesisConfig::default()`.
+    timelock_config: Option<TimelockGenesisConfig>,
 }
 
 impl MainnetGenesisInfo {
@@ -107,6 +111,7 @@ impl MainnetGenesisInfo {
             initial_features_override: genesis_config.initial_features_override.clone(),
             randomness_config_override: genesis_config.randomness_config_override.clone(),
             jwk_consensus_config_override: genesis_config.jwk_consensus_config_override.clone(),
+            timelock_config: genesis_config.timelock_config,
         })
     }
 
@@ -145,6 +150,7 @@ impl MainnetGenesisInfo {
                 jwk_consensus_config_override: self.jwk_consensus_config_override.clone(),
                 initial_jwks: vec![],
                 keyless_groth16_vk: None,
+                timelock_config: self.timelock_config,
             },
         )
     }
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    transcript_aggregation::TranscriptAggregationState, types::DKGTranscriptRequest, DKGMessage,
+    transcript_aggregation::{TimelockTranscriptAggregationState, TranscriptAggregationState},
+    types::{DKGTranscriptRequest, TimelockDKGTranscriptRequest},
+    DKGMessage,
 };
 use aptos_channels::aptos_channel::Sender;
 use aptos_logger::info;
@@ -32,12 +34,26 @@ pub trait TAggTranscriptProducer<S: DKGTrait>: Send + Sync {
 /// The real implementation of `AggTranscriptProducer` that broadcasts a `NodeRequest`, collects and verifies nodes from network.
 pub struct AggTranscriptProducer {
     reliable_broadcast: Arc<ReliableBroadcast<DKGMessage, ExponentialBackoff>>,
+    // Set for a timelock DKG session, whose messages are tagged with its interval.
+    timelock_interval: Option<u64>,
 }
 
 impl AggTranscriptProducer {
     pub fn new(reliable_broadcast: ReliableBroadcast<DKGMessage, ExponentialBackoff>) -> Self {
         Self {
             reliable_broadcast: Arc::new(reliable_broadcast),
+            timelock_interval: None,
+        }
+    }
+
+    /// Creates a producer for the timelock DKG session of `interval`.
+    pub fn new_for_timelock(
+        reliable_broadcast: ReliableBroadcast<DKGMessage, ExponentialBackoff>,
+        interval: u64,
+    ) -> Self {
+        Self {
+            reliable_broadcast: Arc::new(reliable_broadcast),
+            timelock_interval: Some(interval),
         }
     }
 }
@@ -53,6 +69,7 @@ impl<DKG: DKGTrait + 'static> TAggTranscriptProducer<DKG> for AggTranscriptProdu
     ) -> AbortHandle {
         let epoch = epoch_state.epoch;
         let rb = self.reliable_broadcast.clone();
+        let timelock_interval = self.timelock_interval;
         let req = DKGTranscriptRequest::new(epoch_state.epoch);
         let agg_state = Arc::new(TranscriptAggregationState::<DKG>::new(
             start_time,
@@ -61,10 +78,16 @@ impl<DKG: DKGTrait + 'static> TAggTranscriptProducer<DKG> for AggTranscriptProdu
             epoch_state,
         ));
         let task = async move {
-            let agg_trx = rb
-                .broadcast(req, agg_state)
-                .await
-                .expect("broadcast cannot fail");
+            let agg_trx = match timelock_interval {
+                None => rb.broadcast(req, agg_state).await,
+                Some(interval) => {
+                    let req = TimelockDKGTranscriptRequest::new(interval, req);
+                    let agg_state =
+                        Arc::new(TimelockTranscriptAggregationState::new(interval, agg_state));
+                    rb.broadcast(req, agg_state).await
+                },
+            }
+            .expect("broadcast cannot fail");
             info!(
                 epoch = epoch,
                 my_addr = my_addr,
@@ -1,7 +1,12 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_metrics_core::{register_histogram_vec, register_int_gauge, HistogramVec, IntGauge};
+use crate::logging::TimelockStage;
+use aptos_metrics_core::{
+    register_histogram, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, register_int_gauge_vec, Histogram, HistogramVec, IntCounter, IntCounterVec,
+    IntGauge, IntGaugeVec,
+};
 use once_cell::sync::Lazy;
 
 /// Count of the pending messages sent to itself in the channel
@@ -30,3 +35,112 @@ pub static ROUNDING_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Count of timelock events rejected by validation, by event and reason
+pub static TIMELOCK_EVENTS_REJECTED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_dkg_timelock_events_rejected",
+        "Count of timelock events rejected by validation, by event and reason",
+        &["event", "reason"]
+    )
+    .unwrap()
+});
+
+/// Number of timelock secret shares held in memory
+pub static TIMELOCK_SHARES_CACHED: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_dkg_timelock_shares_cached",
+        "Number of timelock secret shares held in memory"
+    )
+    .unwrap()
+});
+
+/// Number of timelock secret shares known to be in secure storage
+pub static TIMELOCK_SHARES_PERSISTED: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_dkg_timelock_shares_persisted",
+        "Number of timelock secret shares known to be in secure storage"
+    )
+    .unwrap()
+});
+
+/// Number of timelock DKGs waiting for a free slot
+pub static TIMELOCK_DKG_QUEUE_DEPTH: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_dkg_timelock_dkg_queue_depth",
+        "Number of timelock DKGs waiting for a free slot"
+    )
+    .unwrap()
+});
+
+/// Count of timelock DKG sessions, by result (started, completed or failed)
+pub static TIMELOCK_KEY_GEN_SESSIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_dkg_timelock_key_gen_sessions",
+        "Count of timelock DKG sessions, by result (started, completed or failed)",
+        &["result"]
+    )
+    .unwrap()
+});
+
+/// Latest timelock interval that reached each stage
+pub static TIMELOCK_LATEST_INTERVAL: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_dkg_timelock_latest_interval",
+        "Latest timelock interval that reached each stage",
+        &["stage"]
+    )
+    .unwrap()
+});
+
+/// Count of timelock reveal requests received
+pub static TIMELOCK_REVEAL_REQUESTS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_dkg_timelock_reveal_requests",
+        "Count of timelock reveal requests received"
+    )
+    .unwrap()
+});
+
+/// Count of timelock decryption key shares revealed
+pub static TIMELOCK_SHARES_REVEALED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_dkg_timelock_shares_revealed",
+        "Count of timelock decryption key shares revealed"
+    )
+    .unwrap()
+});
+
+/// Count of timelock share re-submissions to the validator txn pool
+pub static TIMELOCK_SHARE_SUBMISSION_RETRIES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_dkg_timelock_share_submission_retries",
+        "Count of timelock share re-submissions to the validator txn pool"
+    )
+    .unwrap()
+});
+
+/// Time from the start of a timelock DKG session to our share being available
+pub static TIMELOCK_KEY_GEN_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "aptos_dkg_timelock_key_gen_seconds",
+        "Time from the start of a timelock DKG session to our share being available"
+    )
+    .unwrap()
+});
+
+/// Time from submitting our timelock share to the secret being on-chain
+pub static TIMELOCK_REVEAL_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "aptos_dkg_timelock_reveal_seconds",
+        "Time from submitting our timelock share to it being on-chain"
+    )
+    .unwrap()
+});
+
+/// Records `interval` as the latest timelock interval that reached `stage`.
+pub fn set_timelock_latest_interval(stage: TimelockStage, interval: u64) {
+    TIMELOCK_LATEST_INTERVAL
+        .with_label_values(&[stage.as_str()])
+        .set(interval as i64);
+}
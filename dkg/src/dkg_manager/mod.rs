@@ -5,6 +5,7 @@ use crate::{
     agg_trx_producer::TAggTranscriptProducer,
     counters::{DKG_STAGE_SECONDS, ROUNDING_SECONDS},
     network::IncomingRpcRequest,
+    types::TimelockDKGTranscript,
     DKGMessage,
 };
 use anyhow::{anyhow, bail, ensure, Result};
@@ -15,7 +16,7 @@ use aptos_logger::{debug, error, info, warn};
 use aptos_types::{
     dkg::{
         DKGSessionMetadata, DKGSessionState, DKGStartEvent, DKGTrait, DKGTranscript,
-        DKGTranscriptMetadata, MayHaveRoundingSummary,
+        DKGTranscriptMetadata, MayHaveRoundingSummary, TimelockDKGResult,
     },
     epoch_state::EpochState,
     validator_txn::{Topic, ValidatorTransaction},
@@ -67,7 +68,13 @@ pub struct DKGManager<DKG: DKGTrait> {
     // Control states.
     stopped: bool,
     state: InnerState,
-    is_timelock: bool,
+    // The timelock interval this manager runs the DKG for, if any; its result is then submitted
+    // as a `TimelockDKGResult`.
+    timelock_interval: Option<u64>,
+
+    // Notified with the aggregated transcript once it is ready. Dropped without a send if the
+    // manager stops before that, so the receiver also learns about failures.
+    completion_tx: Option<oneshot::Sender<DKG::Transcript>>,
 }
 
 impl InnerState {
@@ -97,7 +104,7 @@ impl<DKG: DKGTrait> DKGManager<DKG> {
         epoch_state: Arc<EpochState>,
         agg_trx_producer: Arc<dyn TAggTranscriptProducer<DKG>>,
         vtxn_pool: VTxnPoolState,
-        is_timelock: bool,
+        timelock_interval: Option<u64>,
     ) -> Self {
         let (pull_notification_tx, pull_notification_rx) =
             aptos_channel::new(QueueStyle::KLAST, 1, None);
@@ -113,10 +120,17 @@ impl<DKG: DKGTrait> DKGManager<DKG> {
             agg_trx_producer,
             stopped: false,
             state: InnerState::NotStarted,
-            is_timelock,
+            timelock_interval,
+            completion_tx: None,
         }
     }
 
+    /// Sets a channel to be notified with the aggregated transcript once it is ready.
+    pub fn with_completion_tx(mut self, completion_tx: oneshot::Sender<DKG::Transcript>) -> Self {
+        self.completion_tx = Some(completion_tx);
+        self
+    }
+
     pub async fn run(
         mut self,
         in_progress_session: Option<DKGSessionState>,
@@ -311,7 +325,10 @@ impl<DKG: DKGTrait> DKGManager<DKG> {
             secs_since_dkg_start = secs_since_dkg_start,
             "[DKG] Deal transcript started.",
         );
-        let public_params = DKG::new_public_params(dkg_session_metadata);
+        let public_params = match self.timelock_interval {
+            Some(interval) => DKG::new_timelock_public_params(dkg_session_metadata, interval),
+            None => DKG::new_public_params(dkg_session_metadata),
+        };
         if let Some(summary) = public_params.rounding_summary() {
             info!(
                 epoch = self.epoch_state.epoch,
@@ -393,14 +410,17 @@ impl<DKG: DKGTrait> DKGManager<DKG> {
                     .with_label_values(&[self.my_addr.to_hex().as_str(), "agg_transcript_ready"])
                     .observe(secs_since_dkg_start);
 
-                let txn = if self.is_timelock {
-                    ValidatorTransaction::TimelockDKGResult(DKGTranscript {
-                        metadata: DKGTranscriptMetadata {
-                            epoch: self.epoch_state.epoch,
-                            author: self.my_addr,
+                let txn = if let Some(interval) = self.timelock_interval {
+                    ValidatorTransaction::TimelockDKGResult(TimelockDKGResult {
+                        interval,
+                        transcript: DKGTranscript {
+                            metadata: DKGTranscriptMetadata {
+                                epoch: self.epoch_state.epoch,
+                                author: self.my_addr,
+                            },
+                            transcript_bytes: bcs::to_bytes(&agg_trx)
+                                .map_err(|e| anyhow!("transcript serialization error: {e}"))?,
                         },
-                        transcript_bytes: bcs::to_bytes(&agg_trx)
-                            .map_err(|e| anyhow!("transcript serialization error: {e}"))?,
                     })
                 } else {
                     ValidatorTransaction::DKGResult(DKGTranscript {
@@ -422,6 +442,9 @@ impl<DKG: DKGTrait> DKGManager<DKG> {
                     my_addr = self.my_addr,
                     "[DKG] aggregated transcript put into vtxn pool."
                 );
+                if let Some(completion_tx) = self.completion_tx.take() {
+                    let _ = completion_tx.send(agg_trx);
+                }
                 InnerState::Finished {
                     vtxn_guard,
                     start_time,
@@ -476,6 +499,16 @@ impl<DKG: DKGTrait> DKGManager<DKG> {
             | (InnerState::InProgress { my_transcript, .. }, DKGMessage::TranscriptRequest(_)) => {
                 Ok(DKGMessage::TranscriptResponse(my_transcript.clone()))
             },
+            (
+                InnerState::Finished { my_transcript, .. },
+                DKGMessage::TimelockTranscriptRequest(request),
+            )
+            | (
+                InnerState::InProgress { my_transcript, .. },
+                DKGMessage::TimelockTranscriptRequest(request),
+            ) => Ok(DKGMessage::TimelockTranscriptResponse(
+                TimelockDKGTranscript::new(request.interval, my_transcript.clone()),
+            )),
             _ => Err(anyhow!(
                 "[DKG] msg {:?} unexpected in state {:?}",
                 msg.name(),
@@ -3,16 +3,40 @@
 
 use crate::{
     agg_trx_producer::AggTranscriptProducer,
+    counters::{
+        set_timelock_latest_interval, TIMELOCK_EVENTS_REJECTED, TIMELOCK_REVEAL_REQUESTS,
+        TIMELOCK_SHARES_REVEALED,
+    },
     dkg_manager::DKGManager,
+    logging::{TimelockLogSchema, TimelockStage},
     network::{IncomingRpcRequest, NetworkReceivers, NetworkSender},
     network_interface::DKGNetworkClient,
+    timelock_events::{TimelockEventRejection, TimelockEventValidator},
+    timelock_recovery::{pending_timelock_duties, RecoveredTimelockDuty},
+    timelock_sessions::{
+        TimelockDKGCompletion, TimelockSession, TimelockSessions, MAX_TIMELOCK_SESSIONS,
+        TIMELOCK_SESSION_CLOSE_TIMEOUT,
+    },
+    timelock_share_storage::{
+        TimelockShareStorage, TimelockShareWrappingKey, DEFAULT_MAX_CACHED_TIMELOCK_SHARES,
+        DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS,
+    },
+    timelock_submissions::{
+        TimelockShareSubmissions, MAX_TIMELOCK_SHARE_SUBMISSIONS, TIMELOCK_SHARE_RETRY_BASE_DELAY,
+        TIMELOCK_SHARE_RETRY_CHECK_INTERVAL, TIMELOCK_SHARE_RETRY_MAX_DELAY,
+    },
+    types::DKGSessionId,
     DKGMessage,
 };
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use aptos_bounded_executor::BoundedExecutor;
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::config::{ReliableBroadcastConfig, SafetyRulesConfig};
-use aptos_dkg;
+use aptos_crypto::{
+    bls12381,
+    blstrs::{g1_proj_from_bytes, G1_PROJ_NUM_BYTES},
+};
+use aptos_dkg::ibe::{compute_timelock_identity, derive_timelock_key_share};
 use aptos_event_notifications::{
     EventNotification, EventNotificationListener, ReconfigNotification,
     ReconfigNotificationListener,
@@ -22,28 +46,37 @@ use aptos_logger::{debug, error, info, warn};
 use aptos_network::{application::interface::NetworkClient, protocols::network::Event};
 use aptos_reliable_broadcast::ReliableBroadcast;
 use aptos_safety_rules::{safety_rules_manager::storage, PersistentSafetyStorage};
+use aptos_secure_storage::Storage;
 use aptos_types::{
     account_address::AccountAddress,
+    chain_id::ChainId,
     dkg::{
-        DKGSessionMetadata, DKGStartEvent, DKGState, DefaultDKG, RequestRevealEvent,
-        StartKeyGenEvent,
+        real_dkg::maybe_dk_from_bls_sk, DKGSessionMetadata, DKGStartEvent, DKGState, DKGTrait,
+        DefaultDKG, RequestRevealEvent, ShareReceivedEvent, StartKeyGenEvent, TimelockConfig,
+        TimelockKeyGenConfig, TimelockShare, TimelockState,
     },
     epoch_state::EpochState,
     on_chain_config::{
         OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig,
         OnChainRandomnessConfig, RandomnessConfigMoveStruct, RandomnessConfigSeqNum, ValidatorSet,
     },
-    validator_txn::{Topic, ValidatorTransaction},
 };
 use aptos_validator_transaction_pool::VTxnPoolState;
+use fail::fail_point;
 use futures::StreamExt;
-use futures_channel::oneshot;
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use futures_channel::{mpsc::UnboundedReceiver, oneshot};
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio_retry::strategy::ExponentialBackoff;
+use zeroize::Zeroizing;
 
 pub struct EpochManager<P: OnChainConfigProvider> {
     // Some useful metadata
     my_addr: AccountAddress,
+    chain_id: ChainId,
     epoch_state: Option<Arc<EpochState>>,
 
     // Inbound events
@@ -68,27 +101,28 @@ pub struct EpochManager<P: OnChainConfigProvider> {
     key_storage: PersistentSafetyStorage,
 
     // Timelock DKG sessions
-    // Track close channels for active timelock DKG sessions by interval number
-    // Multiple intervals can have concurrent DKG sessions running
-    // We store close_tx to allow graceful shutdown of each DKG session
-    timelock_dkg_close_txs: HashMap<u64, oneshot::Sender<oneshot::Sender<()>>>,
-
-    // RPC message channels for timelock DKG communication per interval
-    // We need separate channels for each timelock interval since they run concurrently
+    // Multiple intervals can have concurrent DKG sessions running, each with its own
+    // RPC and close channels.
     // Note: We don't store start_event_tx because we send the event immediately after spawn
-    timelock_rpc_msg_txs:
-        HashMap<u64, aptos_channel::Sender<AccountAddress, (AccountAddress, IncomingRpcRequest)>>,
-
-    // In-memory storage of timelock secret shares (interval -> scalar_bytes)
-    // TODO Phase 4: Replace with persistent storage to survive restarts
-    // These are the BLS scalar shares from DKG that will be used to compute decryption keys
-    timelock_shares_cache: HashMap<u64, Vec<u8>>,
+    timelock_sessions: TimelockSessions,
+    // Rejects malformed and replayed timelock events
+    timelock_event_validator: TimelockEventValidator,
+    // Outcomes of the timelock DKG sessions, carrying our secret share on success
+    timelock_completion_rx: UnboundedReceiver<TimelockDKGCompletion>,
+
+    // Timelock secret shares (interval -> share bytes), persisted so they survive restarts and
+    // cached in memory for the most recent intervals
+    timelock_share_storage: TimelockShareStorage<Storage>,
+    // Our revealed shares, kept in the validator txn pool and re-submitted until their secret
+    // shows up on-chain
+    timelock_share_submissions: TimelockShareSubmissions<VTxnPoolState>,
 }
 
 impl<P: OnChainConfigProvider> EpochManager<P> {
     pub fn new(
         safety_rules_config: &SafetyRulesConfig,
         my_addr: AccountAddress,
+        chain_id: ChainId,
         reconfig_events: ReconfigNotificationListener<P>,
         dkg_start_events: EventNotificationListener,
         self_sender: aptos_channels::Sender<Event<DKGMessage>>,
@@ -96,9 +130,17 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         vtxn_pool: VTxnPoolState,
         rb_config: ReliableBroadcastConfig,
         randomness_override_seq_num: u64,
+        max_concurrent_timelock_dkgs: usize,
+        timelock_reveal_deferral_timeout: Duration,
     ) -> Self {
+        let (timelock_sessions, timelock_completion_rx) = TimelockSessions::new(
+            MAX_TIMELOCK_SESSIONS,
+            max_concurrent_timelock_dkgs,
+            timelock_reveal_deferral_timeout,
+        );
         Self {
             my_addr,
+            chain_id,
             epoch_state: None,
             reconfig_events,
             dkg_start_events,
@@ -106,14 +148,25 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             dkg_manager_close_tx: None,
             self_sender,
             network_sender,
-            vtxn_pool,
+            vtxn_pool: vtxn_pool.clone(),
             dkg_start_event_tx: None,
             rb_config,
             randomness_override_seq_num,
             key_storage: storage(safety_rules_config),
-            timelock_dkg_close_txs: HashMap::new(),
-            timelock_rpc_msg_txs: HashMap::new(),
-            timelock_shares_cache: HashMap::new(),
+            timelock_sessions,
+            timelock_event_validator: TimelockEventValidator::default(),
+            timelock_completion_rx,
+            timelock_share_storage: TimelockShareStorage::new(
+                (&safety_rules_config.backend).into(),
+                DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS,
+                DEFAULT_MAX_CACHED_TIMELOCK_SHARES,
+            ),
+            timelock_share_submissions: TimelockShareSubmissions::new(
+                vtxn_pool,
+                MAX_TIMELOCK_SHARE_SUBMISSIONS,
+                TIMELOCK_SHARE_RETRY_BASE_DELAY,
+                TIMELOCK_SHARE_RETRY_MAX_DELAY,
+            ),
         }
     }
 
@@ -123,32 +176,44 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         dkg_request: IncomingRpcRequest,
     ) -> Result<()> {
         if Some(dkg_request.msg.epoch()) == self.epoch_state.as_ref().map(|s| s.epoch) {
-            // Forward to DKGManager if it is alive.
-            if let Some(tx) = &self.dkg_rpc_msg_tx {
-                let _ = tx.push(peer_id, (peer_id, dkg_request));
+            match dkg_request.msg.session() {
+                DKGSessionId::Epoch => {
+                    // Forward to DKGManager if it is alive.
+                    if let Some(tx) = &self.dkg_rpc_msg_tx {
+                        let _ = tx.push(peer_id, (peer_id, dkg_request));
+                    }
+                },
+                DKGSessionId::Timelock(interval) => {
+                    self.timelock_sessions
+                        .forward_rpc_request(interval, peer_id, dkg_request);
+                },
             }
         }
         Ok(())
     }
 
+    /// Handles every event of the notification, in order: a single block may, e.g., request the
+    /// reveal of one interval, start the key generation of the next and carry several shares.
     fn on_dkg_start_notification(&mut self, notification: EventNotification) -> Result<()> {
-        if let Some(tx) = self.dkg_start_event_tx.as_ref() {
-            let EventNotification {
-                subscribed_events, ..
-            } = notification;
-            for event in subscribed_events {
-                if let Ok(dkg_start_event) = DKGStartEvent::try_from(&event) {
+        if self.dkg_start_event_tx.is_none() {
+            return Ok(());
+        }
+        let EventNotification {
+            subscribed_events, ..
+        } = notification;
+        for event in subscribed_events {
+            if let Ok(dkg_start_event) = DKGStartEvent::try_from(&event) {
+                if let Some(tx) = self.dkg_start_event_tx.as_ref() {
                     let _ = tx.push((), dkg_start_event);
-                    return Ok(());
-                } else if let Ok(timelock_start) = StartKeyGenEvent::try_from(&event) {
-                    self.start_timelock_dkg(timelock_start);
-                    return Ok(());
-                } else if let Ok(timelock_reveal) = RequestRevealEvent::try_from(&event) {
-                    self.process_timelock_reveal(timelock_reveal);
-                    return Ok(());
-                } else {
-                    debug!("[DKG] on_dkg_start_notification: failed in converting a contract event to a dkg start event!");
                 }
+            } else if let Ok(timelock_start) = StartKeyGenEvent::try_from(&event) {
+                self.start_timelock_dkg(timelock_start);
+            } else if let Ok(timelock_reveal) = RequestRevealEvent::try_from(&event) {
+                self.process_timelock_reveal(timelock_reveal);
+            } else if let Ok(share_received) = ShareReceivedEvent::try_from(&event) {
+                self.process_timelock_share_received(share_received);
+            } else {
+                debug!("[DKG] on_dkg_start_notification: failed in converting a contract event to a dkg start event!");
             }
         }
         Ok(())
@@ -156,6 +221,8 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
 
     pub async fn start(mut self, mut network_receivers: NetworkReceivers) {
         self.await_reconfig_notification().await;
+        let mut timelock_retry_interval =
+            tokio::time::interval(TIMELOCK_SHARE_RETRY_CHECK_INTERVAL);
         loop {
             let handling_result = tokio::select! {
                 notification = self.dkg_start_events.select_next_some() => {
@@ -167,6 +234,14 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                 (peer, rpc_request) = network_receivers.rpc_rx.select_next_some() => {
                     self.process_rpc_request(peer, rpc_request)
                 },
+                completion = self.timelock_completion_rx.select_next_some() => {
+                    self.process_timelock_dkg_completion(completion)
+                },
+                _ = timelock_retry_interval.tick() => {
+                    self.retry_timelock_share_submissions();
+                    self.expire_deferred_timelock_reveals();
+                    Ok(())
+                },
             };
 
             if let Err(e) = handling_result {
@@ -277,7 +352,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                 epoch_state,
                 Arc::new(agg_trx_producer),
                 self.vtxn_pool.clone(),
-                false,
+                None,
             );
             tokio::spawn(dkg_manager.run(
                 in_progress_session,
@@ -286,9 +361,91 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                 dkg_manager_close_rx,
             ));
         };
+
+        // Timelock events are only handled alongside the epoch DKG (see
+        // `on_dkg_start_notification`), so recovery is gated the same way.
+        if randomness_enabled && my_index.is_some() {
+            self.update_timelock_share_wrapping_key(&epoch_state);
+            // Intervals queued in the previous epoch run with the new validator set
+            self.start_queued_timelock_dkgs();
+            match (
+                payload.get::<TimelockState>(),
+                payload.get::<TimelockKeyGenConfig>(),
+            ) {
+                (Ok(timelock_state), Ok(key_gen_config)) => {
+                    self.recover_timelock_duties(&timelock_state, key_gen_config.config)
+                },
+                (Err(e), _) | (_, Err(e)) => {
+                    debug!("[Timelock] No on-chain timelock state: {}", e)
+                },
+            }
+        }
         Ok(())
     }
 
+    /// Derives the key our timelock shares are encrypted with at rest from our consensus key for
+    /// this epoch; if the consensus key was rotated, the stored shares are re-wrapped, provided
+    /// the previous consensus key is still in secure storage.
+    fn update_timelock_share_wrapping_key(&mut self, epoch_state: &EpochState) {
+        let Some(my_pk) = epoch_state.verifier.get_public_key(&self.my_addr) else {
+            error!("[Timelock] Cannot find own public key in validator set");
+            return;
+        };
+        let consensus_sk = match self.key_storage.consensus_sk_by_pk(my_pk) {
+            Ok(sk) => sk,
+            Err(e) => {
+                error!("[Timelock] Failed to load consensus secret key: {}", e);
+                return;
+            },
+        };
+        let key_storage = &self.key_storage;
+        let result = TimelockShareWrappingKey::derive(&consensus_sk).and_then(|key| {
+            // The shares may be wrapped under a consensus key rotated while we were down
+            self.timelock_share_storage
+                .set_wrapping_key(key, |previous_pk| {
+                    let previous_sk = key_storage
+                        .consensus_sk_by_pk(previous_pk.clone())
+                        .map_err(|e| {
+                            anyhow!("Failed to load previous consensus secret key: {}", e)
+                        })?;
+                    TimelockShareWrappingKey::derive(&previous_sk)
+                })
+        });
+        if let Err(e) = result {
+            error!("[Timelock] Failed to update the share wrapping key: {}", e);
+        }
+    }
+
+    /// Acts on the timelock duties whose events we may have missed, e.g., while restarting. Missed
+    /// key generations are joined with `key_gen_config`, the on-chain config they were requested
+    /// with.
+    fn recover_timelock_duties(
+        &mut self,
+        timelock_state: &TimelockState,
+        key_gen_config: TimelockConfig,
+    ) {
+        let duties = pending_timelock_duties(
+            timelock_state,
+            &self.timelock_event_validator,
+            DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS,
+            |interval| matches!(self.timelock_share_storage.retrieve(interval), Ok(Some(_))),
+        );
+        for duty in duties {
+            info!("[Timelock] Recovering {:?}", duty);
+            match duty {
+                RecoveredTimelockDuty::KeyGen { interval } => {
+                    self.start_timelock_dkg(StartKeyGenEvent {
+                        interval,
+                        config: key_gen_config.clone(),
+                    })
+                },
+                RecoveredTimelockDuty::Reveal { interval } => {
+                    self.process_timelock_reveal(RequestRevealEvent { interval })
+                },
+            }
+        }
+    }
+
     async fn on_new_epoch(&mut self, reconfig_notification: ReconfigNotification<P>) -> Result<()> {
         self.shutdown_current_processor().await;
         self.start_new_epoch(reconfig_notification.on_chain_configs)
@@ -302,6 +459,9 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             tx.send(ack_tx).unwrap();
             ack_rx.await.unwrap();
         }
+        self.timelock_sessions
+            .close_all(TIMELOCK_SESSION_CLOSE_TIMEOUT)
+            .await;
     }
 
     fn create_network_sender(&self) -> NetworkSender {
@@ -312,74 +472,31 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         )
     }
 
-    /// Build DKGSessionMetadata for a timelock interval.
-    ///
-    /// For timelock DKG, we construct metadata from the current epoch state
-    /// and the timelock configuration from the event.
-    fn build_timelock_session_metadata(
-        &self,
-        event: &StartKeyGenEvent,
-        epoch_state: &Arc<EpochState>,
-    ) -> DKGSessionMetadata {
-        use aptos_types::{
-            on_chain_config::{OnChainRandomnessConfig, RandomnessConfigMoveStruct},
-            validator_verifier::ValidatorConsensusInfoMoveStruct,
-        };
-
-        // Convert current validator set to move struct format
-        let validator_consensus_infos: Vec<ValidatorConsensusInfoMoveStruct> = epoch_state
-            .verifier
-            .get_ordered_account_addresses_iter()
-            .map(|addr| {
-                let voting_power = epoch_state.verifier.get_voting_power(&addr).unwrap_or(0);
-                let public_key = epoch_state
-                    .verifier
-                    .get_public_key(&addr)
-                    .expect("public key must exist for validator");
-
-                // Convert public key to bytes for MoveStruct
-                let pk_bytes =
-                    bcs::to_bytes(&public_key).expect("public key serialization should not fail");
-
-                ValidatorConsensusInfoMoveStruct {
-                    addr,
-                    pk_bytes,
-                    voting_power,
-                }
-            })
-            .collect();
-
-        // Build randomness config from timelock config
-        // For timelock, we use the threshold from the event
-        // Convert absolute threshold to percentage (0-100)
-        let total = event.config.total_validators;
-        let threshold_percentage = if total > 0 {
-            (event.config.threshold * 100) / total
-        } else {
-            50 // Default to 50% if total is zero (shouldn't happen)
-        };
-
-        // Create RandomnessConfig using the public API
-        let randomness_config_enum = OnChainRandomnessConfig::new_v1(
-            threshold_percentage, // secrecy_threshold_in_percentage
-            threshold_percentage, // reconstruct_threshold_in_percentage
-        );
-
-        let randomness_config = RandomnessConfigMoveStruct::from(randomness_config_enum);
-
-        DKGSessionMetadata {
-            dealer_epoch: epoch_state.epoch,
-            randomness_config,
-            dealer_validator_set: validator_consensus_infos.clone(),
-            target_validator_set: validator_consensus_infos,
+    /// Log schema for a timelock interval, with our current epoch and validator index.
+    fn timelock_log_schema(&self, stage: TimelockStage, interval: u64) -> TimelockLogSchema {
+        let mut schema = TimelockLogSchema::new(stage).interval(interval);
+        if let Some(epoch_state) = &self.epoch_state {
+            schema = schema.epoch(epoch_state.epoch);
+            if let Some(index) = epoch_state
+                .verifier
+                .address_to_validator_index()
+                .get(&self.my_addr)
+            {
+                schema = schema.validator_index(*index);
+            }
         }
+        schema
     }
 
     fn start_timelock_dkg(&mut self, event: StartKeyGenEvent) {
         info!(
+            self.timelock_log_schema(TimelockStage::KeyGenRequested, event.interval),
             "[Timelock] Starting DKG for interval {} (threshold={}, validators={})",
-            event.interval, event.config.threshold, event.config.total_validators
+            event.interval,
+            event.config.threshold,
+            event.config.total_validators
         );
+        fail_point!("dkg::start_timelock_dkg", |_| {});
 
         // Get current epoch state - needed for validator set and network setup
         let epoch_state = match &self.epoch_state {
@@ -390,6 +507,42 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             },
         };
 
+        if let Err(rejection) = self
+            .timelock_event_validator
+            .validate_start(&event, epoch_state.verifier.len() as u64)
+        {
+            log_rejected_timelock_event("start_key_gen", &event, &rejection);
+            return;
+        }
+
+        // Sessions keep the on-chain config, scaled to the validator set of the epoch they run in
+        // (see `spawn_timelock_dkg`), as they may be restarted in a later epoch.
+        if let Some(config) = self.timelock_sessions.admit(event.interval, event.config) {
+            self.spawn_timelock_dkg(event.interval, config);
+        }
+    }
+
+    /// Starts the queued timelock DKGs for which a slot is free.
+    fn start_queued_timelock_dkgs(&mut self) {
+        loop {
+            let validator = &self.timelock_event_validator;
+            let Some((interval, config)) = self
+                .timelock_sessions
+                .next_queued(|interval| validator.reveal_seen(interval))
+            else {
+                break;
+            };
+            info!("[Timelock] Starting queued DKG for interval {}", interval);
+            self.spawn_timelock_dkg(interval, config);
+        }
+    }
+
+    fn spawn_timelock_dkg(&mut self, interval: u64, config: TimelockConfig) {
+        let Some(epoch_state) = self.epoch_state.clone() else {
+            error!("[Timelock] Cannot start DKG - no epoch state available");
+            return;
+        };
+
         // Check if we're in the current validator set
         let my_index = match epoch_state
             .verifier
@@ -401,7 +554,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             None => {
                 warn!(
                     "[Timelock] Not participating in DKG for interval {} - not in validator set",
-                    event.interval
+                    interval
                 );
                 return;
             },
@@ -439,7 +592,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             Duration::from_millis(self.rb_config.rpc_timeout_ms),
             BoundedExecutor::new(8, tokio::runtime::Handle::current()),
         );
-        let agg_trx_producer = Arc::new(AggTranscriptProducer::new(rb));
+        let agg_trx_producer = Arc::new(AggTranscriptProducer::new_for_timelock(rb, interval));
 
         // Create channels for this timelock DKG session
         let (start_event_tx, start_event_rx) = aptos_channel::new(QueueStyle::KLAST, 1, None);
@@ -449,37 +602,36 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         >(QueueStyle::FIFO, 100, None);
         let (close_tx, close_rx) = oneshot::channel();
 
-        // Build DKGSessionMetadata for this timelock interval
-        // Note: For timelock, we use a simplified metadata structure
-        // The threshold/total come from the event.config
-        let session_metadata = self.build_timelock_session_metadata(&event, &epoch_state);
+        // The VM rebuilds the same session from on-chain state to verify the published transcript
+        let session_metadata = config
+            .scaled_to(epoch_state.verifier.len() as u64)
+            .session_metadata(epoch_state.epoch, &epoch_state.verifier);
 
         // Get current timestamp for DKG start
         let start_time_us = aptos_infallible::duration_since_epoch().as_micros() as u64;
 
         // Create the DKGStartEvent to trigger the DKG
         let dkg_start_event = DKGStartEvent {
-            session_metadata,
+            session_metadata: session_metadata.clone(),
             start_time_us,
         };
 
-        // Store channels for routing future messages to this interval's DKG
-        self.timelock_rpc_msg_txs.insert(event.interval, rpc_msg_tx);
-
-        // Create DKG manager with is_timelock=true
+        // Create a DKG manager that submits a TimelockDKGResult for the interval, notifying us of
+        // the aggregated transcript
+        let (transcript_tx, transcript_rx) = oneshot::channel();
         let dkg_manager = DKGManager::<DefaultDKG>::new(
-            dealer_sk,
+            dealer_sk.clone(),
             my_index,
             self.my_addr,
             epoch_state,
             agg_trx_producer,
             self.vtxn_pool.clone(),
-            true, // is_timelock flag - tells DKGManager to submit TimelockDKGResult
-        );
+            Some(interval),
+        )
+        .with_completion_tx(transcript_tx);
 
         // Spawn the DKG manager task
         // Note: in_progress_session is None since this is a fresh timelock DKG start
-        let interval = event.interval;
         tokio::spawn(dkg_manager.run(None, start_event_rx, rpc_msg_rx, close_rx));
 
         // Send the start event to trigger DKG execution
@@ -491,143 +643,688 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             return;
         }
 
-        // Store close channel for later cleanup
-        self.timelock_dkg_close_txs.insert(interval, close_tx);
+        // Track the session; our share is stored once the aggregated transcript is ready
+        // (see `process_timelock_dkg_completion`).
+        // Note: the share is decrypted from our locally aggregated transcript, which may differ
+        // from the one finalized on-chain if another validator's aggregate lands first.
+        self.timelock_sessions.insert(
+            interval,
+            config,
+            TimelockSession {
+                rpc_msg_tx,
+                close_tx,
+            },
+            transcript_rx,
+            move |transcript| {
+                extract_timelock_share(&session_metadata, &transcript, my_index, &dealer_sk)
+            },
+        );
 
         info!(
+            self.timelock_log_schema(TimelockStage::KeyGenStarted, interval),
             "[Timelock] Spawned and triggered DKG manager for interval {} (validator index {})",
-            interval, my_index
+            interval,
+            my_index
+        );
+    }
+
+    /// Stores our share once a timelock DKG session completes, then reveals it if its reveal was
+    /// requested meanwhile; failed sessions are dropped.
+    fn process_timelock_dkg_completion(&mut self, completion: TimelockDKGCompletion) -> Result<()> {
+        let completed = self.timelock_sessions.on_completion(completion);
+        // Either way the session's slot is free now
+        self.start_queued_timelock_dkgs();
+        let Some((interval, share)) = completed else {
+            return Ok(());
+        };
+        let stored = self.store_timelock_share(interval, &share).map_err(|e| {
+            anyhow!(
+                "[Timelock] Failed to store secret share for interval {}: {}",
+                interval,
+                e
+            )
+        });
+        if self.timelock_sessions.take_deferred_reveal(interval) {
+            info!(
+                self.timelock_log_schema(TimelockStage::RevealRequested, interval),
+                "[Timelock] DKG for interval {} completed, processing its deferred reveal",
+                interval
+            );
+            self.reveal_timelock_share(interval);
+        }
+        stored
+    }
+
+    /// Gives up on the reveal requests whose DKG did not complete in time.
+    fn expire_deferred_timelock_reveals(&mut self) {
+        if !self
+            .timelock_sessions
+            .expire_deferred_reveals(Instant::now())
+            .is_empty()
+        {
+            // Their sessions were stopped, freeing slots
+            self.start_queued_timelock_dkgs();
+        }
+    }
+
+    fn process_timelock_reveal(&mut self, event: RequestRevealEvent) {
+        TIMELOCK_REVEAL_REQUESTS.inc();
+        info!(
+            self.timelock_log_schema(TimelockStage::RevealRequested, event.interval),
+            "[Timelock] Revealing share for interval {}", event.interval
         );
 
-        // TODO Phase 3/4: After DKG completes successfully, we need to:
-        // 1. Detect when the DKG transcript is finalized on-chain
-        // 2. Extract our secret share from the local DKG state
-        // 3. Store it using self.store_timelock_share(interval, share_bytes)
-        // Options:
-        //   a) Add a callback to DKGManager for completion notification
-        //   b) Poll blockchain state for TimelockDKGResult events
-        //   c) Have DKGManager write shares directly to storage
-        // For now, this secret share extraction is deferred
+        if let Err(rejection) = self.timelock_event_validator.validate_reveal(&event) {
+            log_rejected_timelock_event("request_reveal", &event, &rejection);
+            return;
+        }
+        if !self.timelock_event_validator.key_gen_seen(event.interval) {
+            info!(
+                "[Timelock] No key-gen seen for interval {} since startup, looking up persistent storage",
+                event.interval
+            );
+        }
+        // Our share does not exist until our DKG for the interval completes
+        if self
+            .timelock_sessions
+            .defer_reveal(event.interval, Instant::now())
+        {
+            return;
+        }
+        self.reveal_timelock_share(event.interval);
     }
 
-    fn process_timelock_reveal(&self, event: RequestRevealEvent) {
-        info!("[Timelock] Revealing share for interval {}", event.interval);
+    /// Submits our decryption key share for `interval`, derived from our stored secret share.
+    fn reveal_timelock_share(&mut self, interval: u64) {
+        // The interval's key generation is long finalized, so its DKG session can stop.
+        self.timelock_sessions.remove(interval);
+        self.start_queued_timelock_dkgs();
 
         // 1. Retrieve secret share from storage
-        let share_bytes = match self.retrieve_timelock_share(event.interval) {
+        let share_bytes = match self.retrieve_timelock_share(interval) {
             Ok(bytes) => bytes,
             Err(e) => {
                 warn!(
-                    "[Timelock] Cannot reveal share for interval {}: {}",
-                    event.interval, e
-                );
-                return;
-            },
-        };
-
-        // 2. Deserialize the secret share scalar
-        let scalar = match aptos_crypto::blstrs::scalar_from_bytes_le(&share_bytes) {
-            Ok(s) => s,
-            Err(e) => {
-                error!(
-                    "[Timelock] Failed to deserialize secret share for interval {}: {}",
-                    event.interval, e
+                    self.timelock_log_schema(TimelockStage::RevealRequested, interval),
+                    "[Timelock] Cannot reveal share for interval {}: {}", interval, e
                 );
                 return;
             },
         };
 
-        // 3. Compute timelock identity for this interval
-        // TODO: Get chain_id from epoch_state or config
-        // For now, hardcode to 1 (testnet). This should come from ChainId config.
-        let chain_id = 1u8;
-        let identity = aptos_dkg::ibe::compute_timelock_identity(event.interval, chain_id);
-
-        // 4. Derive decryption key: dk = scalar * H(identity)
-        let decryption_key = match aptos_dkg::ibe::derive_decryption_key(&scalar, &identity) {
-            Ok(dk) => dk,
+        // 2. Derive our decryption key share for this interval and chain, signed with our
+        // consensus key so the VM can attribute it
+        let author_sk = match self.load_consensus_sk() {
+            Ok(sk) => sk,
             Err(e) => {
                 error!(
-                    "[Timelock] Failed to derive decryption key for interval {}: {}",
-                    event.interval, e
+                    self.timelock_log_schema(TimelockStage::RevealRequested, interval),
+                    "[Timelock] Cannot sign decryption key share for interval {}: {}", interval, e
                 );
                 return;
             },
         };
-
-        // 5. Serialize decryption key to bytes (G1 compressed = 48 bytes)
-        let dk_bytes = match aptos_dkg::ibe::serialize_g1(&decryption_key) {
-            Ok(bytes) => bytes,
+        let share = match derive_timelock_share(
+            &share_bytes,
+            self.my_addr,
+            &author_sk,
+            interval,
+            self.chain_id,
+        ) {
+            Ok(share) => share,
             Err(e) => {
                 error!(
-                    "[Timelock] Failed to serialize decryption key for interval {}: {}",
-                    event.interval, e
+                    self.timelock_log_schema(TimelockStage::RevealRequested, interval),
+                    "[Timelock] Failed to derive decryption key share for interval {}: {}",
+                    interval,
+                    e
                 );
                 return;
             },
         };
 
-        // 6. Create and submit TimelockShare transaction
-        let share = aptos_types::dkg::TimelockShare {
-            interval: event.interval,
-            share: dk_bytes,
-        };
-
-        let txn = ValidatorTransaction::TimelockShare(share);
-        let _guard = self.vtxn_pool.put(Topic::TIMELOCK, Arc::new(txn), None);
+        // 3. Submit TimelockShare transaction; it stays in the pool until it is on-chain (see
+        // `process_timelock_share_received`)
+        self.timelock_share_submissions
+            .submit(share, Instant::now());
+        TIMELOCK_SHARES_REVEALED.inc();
+        set_timelock_latest_interval(TimelockStage::ShareRevealed, interval);
 
         info!(
+            self.timelock_log_schema(TimelockStage::ShareRevealed, interval),
             "[Timelock] Successfully computed and submitted decryption key share for interval {}",
-            event.interval
+            interval
         );
     }
 
+    /// A share for an interval landed on-chain. If it is ours, stop submitting it and drop the
+    /// shares whose grace period has passed; the shares of other validators are ignored.
+    fn process_timelock_share_received(&mut self, event: ShareReceivedEvent) {
+        if event.validator != self.my_addr {
+            return;
+        }
+        set_timelock_latest_interval(TimelockStage::ShareOnChain, event.interval);
+        if self
+            .timelock_share_submissions
+            .on_share_committed(event.interval)
+        {
+            info!(
+                self.timelock_log_schema(TimelockStage::ShareOnChain, event.interval),
+                "[Timelock] Share for interval {} is on-chain, share submission done",
+                event.interval
+            );
+        }
+        self.prune_timelock_shares(event.interval);
+    }
+
+    fn retry_timelock_share_submissions(&mut self) {
+        for interval in self.timelock_share_submissions.retry_due(Instant::now()) {
+            self.prune_timelock_shares(interval);
+        }
+    }
+
+    /// Drops the shares whose grace period after the reveal of `revealed_interval` has passed.
+    fn prune_timelock_shares(&mut self, revealed_interval: u64) {
+        match self
+            .timelock_share_storage
+            .prune_after_reveal(revealed_interval)
+        {
+            Ok(expired) if !expired.is_empty() => {
+                debug!(
+                    "[Timelock] Deleted expired shares for intervals {:?}",
+                    expired
+                );
+            },
+            Ok(_) => {},
+            Err(e) => warn!(
+                "[Timelock] Failed to prune expired shares after interval {}: {}",
+                revealed_interval, e
+            ),
+        }
+    }
+
+    /// Loads our consensus secret key for the current epoch.
+    fn load_consensus_sk(&self) -> Result<bls12381::PrivateKey> {
+        let epoch_state = self
+            .epoch_state
+            .as_ref()
+            .ok_or_else(|| anyhow!("no epoch state available"))?;
+        let my_pk = epoch_state
+            .verifier
+            .get_public_key(&self.my_addr)
+            .ok_or_else(|| anyhow!("not in the current validator set"))?;
+        self.key_storage
+            .consensus_sk_by_pk(my_pk)
+            .map_err(|e| anyhow!("failed to load consensus secret key: {e}"))
+    }
+
     /// Store timelock secret share for later reveal.
     ///
-    /// Currently uses in-memory cache. TODO Phase 4: Add persistent storage
-    /// to survive node restarts.
+    /// Writes the share, encrypted, to secure storage (so it survives restarts) and to the
+    /// in-memory cache, which keeps only the most recent intervals.
     fn store_timelock_share(&mut self, interval: u64, share: &[u8]) -> Result<()> {
         info!(
+            self.timelock_log_schema(TimelockStage::ShareStored, interval),
             "[Timelock] Storing secret share for interval {} ({} bytes)",
             interval,
             share.len()
         );
 
-        // Store in-memory for now
-        self.timelock_shares_cache.insert(interval, share.to_vec());
-
-        // TODO Phase 4: Persist to disk
-        // - Extend PersistentSafetyStorage or create TimelockShareStorage
-        // - Encrypt with validator's consensus key
-        // - Handle cleanup of old shares (after reveal + some grace period)
-
-        warn!(
-            "[Timelock] Share for interval {} stored in-memory only - will be lost on restart",
-            interval
-        );
-        Ok(())
+        self.timelock_share_storage.store(interval, share)
     }
 
     /// Retrieve stored timelock secret share.
     ///
     /// Returns error if share not found (validator may have joined after that interval).
-    fn retrieve_timelock_share(&self, interval: u64) -> Result<Vec<u8>> {
+    /// The returned copy is zeroized when dropped.
+    fn retrieve_timelock_share(&mut self, interval: u64) -> Result<Zeroizing<Vec<u8>>> {
         info!(
-            "[Timelock] Retrieving secret share for interval {}",
-            interval
+            self.timelock_log_schema(TimelockStage::ShareRetrieved, interval),
+            "[Timelock] Retrieving secret share for interval {}", interval
         );
 
-        // Lookup in-memory cache
-        self.timelock_shares_cache
-            .get(&interval)
-            .cloned()
+        // Lookup in-memory cache, falling back to persistent storage (e.g., after a restart)
+        self.timelock_share_storage
+            .retrieve(interval)?
             .ok_or_else(|| {
                 anyhow!(
                     "No secret share found for interval {}. Validator may not have participated in DKG for this interval.",
                     interval
                 )
             })
+    }
+}
 
-        // TODO Phase 4: Load from persistent storage if not in cache
+/// Records and logs a timelock event rejected by validation.
+fn log_rejected_timelock_event(
+    event_name: &str,
+    event: &impl Debug,
+    rejection: &TimelockEventRejection,
+) {
+    TIMELOCK_EVENTS_REJECTED
+        .with_label_values(&[event_name, rejection.label()])
+        .inc();
+    warn!(
+        "[Timelock] Rejected {} event {:?}: {}",
+        event_name, event, rejection
+    );
+}
+
+/// Derives our decryption key share for `interval` on `chain_id` from our stored secret share
+/// (see `extract_timelock_share`), as published by `author`: each of our sub-shares times the
+/// interval's identity scalar (see `ibe::derive_timelock_key_share`), compressed and concatenated
+/// in the same order, signed with `author_sk`.
+pub(crate) fn derive_timelock_share(
+    share_bytes: &[u8],
+    author: AccountAddress,
+    author_sk: &bls12381::PrivateKey,
+    interval: u64,
+    chain_id: ChainId,
+) -> Result<TimelockShare> {
+    ensure!(
+        !share_bytes.is_empty() && share_bytes.len() % G1_PROJ_NUM_BYTES == 0,
+        "secret share of {} bytes is not a sequence of compressed G1 points",
+        share_bytes.len()
+    );
+    let identity = compute_timelock_identity(interval, chain_id.id());
+    let mut share = Vec::with_capacity(share_bytes.len());
+    for sub_share_bytes in share_bytes.chunks_exact(G1_PROJ_NUM_BYTES) {
+        let sub_share = g1_proj_from_bytes(sub_share_bytes)
+            .map_err(|e| anyhow!("secret share deserialization error: {e}"))?;
+        share.extend_from_slice(&derive_timelock_key_share(&sub_share, &identity).to_compressed());
+    }
+    TimelockShare::sign(author, interval, share, author_sk)
+}
+
+/// Decrypts our secret share from an aggregated timelock transcript: the sub-shares `h^{f(x_k)}`
+/// of the main path, as concatenated compressed G1 points.
+pub(crate) fn extract_timelock_share(
+    session_metadata: &DKGSessionMetadata,
+    transcript: &<DefaultDKG as DKGTrait>::Transcript,
+    my_index: usize,
+    dealer_sk: &<DefaultDKG as DKGTrait>::DealerPrivateKey,
+) -> Result<Zeroizing<Vec<u8>>> {
+    let pub_params = DefaultDKG::new_public_params(session_metadata);
+    let dk = maybe_dk_from_bls_sk(dealer_sk)?;
+    let (share, _) = DefaultDKG::decrypt_secret_share_from_transcript(
+        &pub_params,
+        transcript,
+        my_index as u64,
+        &dk,
+    )?;
+    let share_bytes = share
+        .main
+        .iter()
+        .flat_map(|sub_share| sub_share.to_bytes())
+        .collect();
+    Ok(Zeroizing::new(share_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TimelockDKGFixture;
+    use aptos_config::config::{SafetyRulesTestConfig, SecureBackend};
+    use aptos_crypto::{bls12381, Uniform};
+    use aptos_dkg::ibe::{decrypt_for_timelock, encrypt_for_timelock};
+    use aptos_network::application::storage::PeersAndMetadata;
+    use aptos_secure_storage::InMemoryStorage;
+    use aptos_types::{
+        contract_event::ContractEvent, dkg::real_dkg::RealDKG,
+        on_chain_config::InMemoryOnChainConfig, validator_txn::ValidatorTransaction,
+        validator_verifier::ValidatorVerifier, waypoint::Waypoint,
+    };
+    use aptos_validator_transaction_pool::TransactionFilter;
+    use move_core_types::{language_storage::TypeTag, move_resource::MoveStructType};
+    use serde::Serialize;
+    use std::collections::HashMap;
+
+    /// An epoch manager for validator `my_addr`, with `consensus_sk` as its consensus key and no
+    /// network peers.
+    fn test_epoch_manager(
+        my_addr: AccountAddress,
+        consensus_sk: &bls12381::PrivateKey,
+        chain_id: ChainId,
+        vtxn_pool: VTxnPoolState,
+    ) -> EpochManager<InMemoryOnChainConfig> {
+        let mut test_config = SafetyRulesTestConfig::new(my_addr);
+        test_config.consensus_key(consensus_sk.clone());
+        test_config.waypoint = Some(Waypoint::default());
+        let safety_rules_config = SafetyRulesConfig {
+            backend: SecureBackend::InMemoryStorage,
+            test: Some(test_config),
+            ..Default::default()
+        };
+        let (_reconfig_tx, notification_receiver) = aptos_channel::new(QueueStyle::KLAST, 1, None);
+        let (_dkg_start_tx, dkg_start_receiver) = aptos_channel::new(QueueStyle::KLAST, 1, None);
+        let (self_sender, _self_receiver) = aptos_channels::new_test(1);
+        let network_client =
+            NetworkClient::new(vec![], vec![], HashMap::new(), PeersAndMetadata::new(&[]));
+        EpochManager::new(
+            &safety_rules_config,
+            my_addr,
+            chain_id,
+            ReconfigNotificationListener {
+                notification_receiver,
+            },
+            EventNotificationListener {
+                notification_receiver: dkg_start_receiver,
+            },
+            self_sender,
+            DKGNetworkClient::new(network_client),
+            vtxn_pool,
+            ReliableBroadcastConfig::default(),
+            0,
+            1,
+            Duration::from_secs(300),
+        )
+    }
+
+    /// The timelock shares in `vtxn_pool`, as the proposer would pull them.
+    fn pooled_timelock_shares(vtxn_pool: &VTxnPoolState) -> Vec<TimelockShare> {
+        vtxn_pool
+            .pull(
+                Instant::now() + Duration::from_secs(1),
+                u64::MAX,
+                u64::MAX,
+                TransactionFilter::empty(),
+            )
+            .into_iter()
+            .filter_map(|txn| match txn {
+                ValidatorTransaction::TimelockShare(share) => Some(share),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `event` as the VM emits it.
+    fn contract_event<T: MoveStructType + Serialize>(event: &T) -> ContractEvent {
+        ContractEvent::new_v2(
+            TypeTag::Struct(Box::new(T::struct_tag())),
+            bcs::to_bytes(event).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_every_event_of_a_notification_handled() {
+        let config = TimelockConfig {
+            threshold: 2,
+            total_validators: 4,
+        };
+        let dkg = TimelockDKGFixture::run(&config);
+        let chain_id = ChainId::new(2);
+        let vtxn_pool = VTxnPoolState::default();
+        let mut epoch_manager = test_epoch_manager(
+            dkg.addrs[0],
+            &dkg.private_keys[0],
+            chain_id,
+            vtxn_pool.clone(),
+        );
+        // Timelock events are only handled alongside the epoch DKG
+        epoch_manager.epoch_state = Some(Arc::new(EpochState::new(
+            1,
+            (*dkg.pub_params.verifier).clone(),
+        )));
+        let (dkg_start_event_tx, _dkg_start_event_rx) =
+            aptos_channel::new(QueueStyle::KLAST, 1, None);
+        epoch_manager.dkg_start_event_tx = Some(dkg_start_event_tx);
+        epoch_manager
+            .timelock_share_storage
+            .set_wrapping_key(
+                TimelockShareWrappingKey::derive(&dkg.private_keys[0]).unwrap(),
+                |_| unreachable!("no share is stored before the key is set"),
+            )
+            .unwrap();
+        epoch_manager
+            .timelock_share_storage
+            .store(1000, &dkg.share(0))
+            .unwrap();
+        // Our share for interval 999 is waiting to land on-chain
+        let share_999 = derive_timelock_share(
+            &dkg.share(0),
+            dkg.addrs[0],
+            &dkg.private_keys[0],
+            999,
+            chain_id,
+        )
+        .unwrap();
+        epoch_manager
+            .timelock_share_submissions
+            .submit(share_999, Instant::now());
+
+        // A block that requests the reveal of interval 1000, starts the key generation of interval
+        // 1001 and commits the shares of other validators for 1000 and ours for 999
+        let share_received = |interval, validator, shares_received_so_far| ShareReceivedEvent {
+            interval,
+            validator,
+            shares_received_so_far,
+            threshold: config.threshold,
+            aggregation_complete: shares_received_so_far >= config.threshold,
+        };
+        epoch_manager
+            .on_dkg_start_notification(EventNotification {
+                version: 1,
+                subscribed_events: vec![
+                    contract_event(&RequestRevealEvent { interval: 1000 }),
+                    contract_event(&StartKeyGenEvent {
+                        interval: 1001,
+                        config: config.clone(),
+                    }),
+                    contract_event(&share_received(1000, dkg.addrs[1], 1)),
+                    contract_event(&share_received(1000, dkg.addrs[2], 2)),
+                    contract_event(&share_received(999, dkg.addrs[0], 3)),
+                ],
+            })
+            .unwrap();
+
+        // Our share for interval 1000 is submitted, the one for 999 no longer is
+        let shares = pooled_timelock_shares(&vtxn_pool);
+        assert_eq!(shares.len(), 1);
+        assert_eq!((shares[0].author, shares[0].interval), (dkg.addrs[0], 1000));
+        assert!(epoch_manager.timelock_share_submissions.is_pending(1000));
+        assert!(!epoch_manager.timelock_share_submissions.is_pending(999));
+        // The DKG for interval 1001 runs
+        assert!(epoch_manager.timelock_sessions.is_key_gen_pending(1001));
+    }
+
+    #[tokio::test]
+    async fn test_key_gen_interrupted_by_epoch_change_restarts() {
+        let config = TimelockConfig {
+            threshold: 2,
+            total_validators: 4,
+        };
+        let dkg = TimelockDKGFixture::run(&config);
+        let mut epoch_manager = test_epoch_manager(
+            dkg.addrs[0],
+            &dkg.private_keys[0],
+            ChainId::new(2),
+            VTxnPoolState::default(),
+        );
+        epoch_manager.epoch_state = Some(Arc::new(EpochState::new(
+            1,
+            (*dkg.pub_params.verifier).clone(),
+        )));
+        epoch_manager.start_timelock_dkg(StartKeyGenEvent {
+            interval: 1000,
+            config,
+        });
+        assert!(epoch_manager.timelock_sessions.is_key_gen_pending(1000));
+
+        // The epoch changes, with one validator leaving, before the DKG completes
+        epoch_manager.shutdown_current_processor().await;
+        assert!(epoch_manager.timelock_sessions.is_empty());
+        assert_eq!(epoch_manager.timelock_sessions.queued_intervals(), vec![
+            1000
+        ]);
+        let next_verifier = ValidatorVerifier::new(
+            dkg.pub_params
+                .verifier
+                .validator_infos
+                .iter()
+                .filter(|info| info.address != dkg.addrs[3])
+                .cloned()
+                .collect(),
+        );
+        epoch_manager.epoch_state = Some(Arc::new(EpochState::new(2, next_verifier)));
+        epoch_manager.start_queued_timelock_dkgs();
+
+        // The DKG of the interval runs again, among the validators of the new epoch
+        assert!(epoch_manager
+            .timelock_sessions
+            .queued_intervals()
+            .is_empty());
+        assert_eq!(epoch_manager.timelock_sessions.len(), 1);
+        assert!(epoch_manager.timelock_sessions.is_key_gen_pending(1000));
+
+        // The closed session reports its failure, which leaves the restarted one running
+        let completion = epoch_manager.timelock_completion_rx.next().await.unwrap();
+        assert!(completion.result.is_err());
+        epoch_manager
+            .process_timelock_dkg_completion(completion)
+            .unwrap();
+        assert!(epoch_manager.timelock_sessions.is_key_gen_pending(1000));
+    }
+
+    #[tokio::test]
+    async fn test_reveal_deferred_until_completion() {
+        let config = TimelockConfig {
+            threshold: 2,
+            total_validators: 4,
+        };
+        let dkg = TimelockDKGFixture::run(&config);
+        let chain_id = ChainId::new(2);
+        let vtxn_pool = VTxnPoolState::default();
+        let mut epoch_manager = test_epoch_manager(
+            dkg.addrs[0],
+            &dkg.private_keys[0],
+            chain_id,
+            vtxn_pool.clone(),
+        );
+        epoch_manager
+            .timelock_share_storage
+            .set_wrapping_key(
+                TimelockShareWrappingKey::derive(&dkg.private_keys[0]).unwrap(),
+                |_| unreachable!("no share is stored before the key is set"),
+            )
+            .unwrap();
+
+        // A key generation session for interval 1000, as `spawn_timelock_dkg` tracks it, whose
+        // DKG manager is represented by `transcript_tx`
+        let (rpc_msg_tx, _rpc_msg_rx) = aptos_channel::new(QueueStyle::FIFO, 10, None);
+        let (close_tx, _close_rx) = oneshot::channel();
+        let (transcript_tx, transcript_rx) = oneshot::channel();
+        let session_metadata = dkg.session_metadata.clone();
+        let dealer_sk = dkg.private_keys[0].clone();
+        epoch_manager.timelock_sessions.insert(
+            1000,
+            config,
+            TimelockSession {
+                rpc_msg_tx,
+                close_tx,
+            },
+            transcript_rx,
+            move |transcript| extract_timelock_share(&session_metadata, &transcript, 0, &dealer_sk),
+        );
+
+        // The reveal arrives before the DKG completes, so it is deferred
+        epoch_manager.process_timelock_reveal(RequestRevealEvent { interval: 1000 });
+        assert!(pooled_timelock_shares(&vtxn_pool).is_empty());
+        assert!(epoch_manager.timelock_sessions.is_key_gen_pending(1000));
+
+        // Once the DKG completes, our share is stored and the deferred reveal submits it
+        transcript_tx.send(dkg.transcript.clone()).unwrap();
+        let completion = epoch_manager.timelock_completion_rx.next().await.unwrap();
+        epoch_manager
+            .process_timelock_dkg_completion(completion)
+            .unwrap();
+        assert!(epoch_manager
+            .timelock_share_storage
+            .retrieve(1000)
+            .unwrap()
+            .is_some());
+        let shares = pooled_timelock_shares(&vtxn_pool);
+        assert_eq!(shares.len(), 1);
+        assert_eq!((shares[0].author, shares[0].interval), (dkg.addrs[0], 1000));
+        assert!(dkg.is_key_share_valid(
+            0,
+            &shares[0].share,
+            &compute_timelock_identity(1000, chain_id.id())
+        ));
+        assert!(epoch_manager.timelock_sessions.is_empty());
+        assert!(!epoch_manager.timelock_sessions.take_deferred_reveal(1000));
+    }
+
+    #[test]
+    fn test_share_extracted_stored_and_derived() {
+        let config = TimelockConfig {
+            threshold: 2,
+            total_validators: 4,
+        };
+        let dkg = TimelockDKGFixture::run(&config);
+        let chain_id = ChainId::new(2);
+        let identity = compute_timelock_identity(1000, chain_id.id());
+        let ciphertext = encrypt_for_timelock(&dkg.mpk_bytes(), 1000, chain_id.id(), b"bid")
+            .unwrap()
+            .to_bytes();
+
+        let mut key_shares = vec![];
+        for (i, author) in dkg.addrs.iter().enumerate() {
+            // Through storage, as between DKG completion and reveal
+            let mut storage = TimelockShareStorage::new(
+                Storage::from(InMemoryStorage::new()),
+                DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS,
+                DEFAULT_MAX_CACHED_TIMELOCK_SHARES,
+            );
+            storage
+                .set_wrapping_key(
+                    TimelockShareWrappingKey::derive(&dkg.private_keys[i]).unwrap(),
+                    |_| unreachable!("no share is stored before the key is set"),
+                )
+                .unwrap();
+            storage.store(1000, &dkg.share(i)).unwrap();
+            let share_bytes = storage.retrieve(1000).unwrap().unwrap();
+
+            let share =
+                derive_timelock_share(&share_bytes, *author, &dkg.private_keys[i], 1000, chain_id)
+                    .unwrap();
+            assert_eq!((share.author, share.interval), (*author, 1000));
+            share.verify_signature(&dkg.pub_params.verifier).unwrap();
+            assert!(dkg.is_key_share_valid(i, &share.share, &identity));
+            // Bound to the chain id
+            let other_chain_share = derive_timelock_share(
+                &share_bytes,
+                *author,
+                &dkg.private_keys[i],
+                1000,
+                ChainId::new(1),
+            )
+            .unwrap();
+            assert!(!dkg.is_key_share_valid(i, &other_chain_share.share, &identity));
+
+            key_shares.push((i as u64, share.share));
+        }
+
+        // The key shares open what was encrypted to the published MPK, but a single one does not
+        let dk = RealDKG::reconstruct_timelock_key(&dkg.pub_params, &key_shares).unwrap();
+        assert_eq!(
+            decrypt_for_timelock(&dk, 1000, chain_id.id(), &ciphertext).unwrap(),
+            b"bid"
+        );
+        assert!(RealDKG::reconstruct_timelock_key(&dkg.pub_params, &key_shares[3..]).is_err());
+    }
+
+    #[test]
+    fn test_derive_timelock_share_rejects_malformed_share() {
+        for share_bytes in [vec![], vec![7u8; 32], vec![0xFFu8; G1_PROJ_NUM_BYTES]] {
+            assert!(derive_timelock_share(
+                &share_bytes,
+                AccountAddress::ONE,
+                &bls12381::PrivateKey::generate_for_testing(),
+                1000,
+                ChainId::new(2)
+            )
+            .is_err());
+        }
     }
 }
@@ -5,8 +5,16 @@ mod agg_trx_producer;
 mod counters;
 mod dkg_manager;
 pub mod epoch_manager;
+mod logging;
 pub mod network;
 pub mod network_interface;
+#[cfg(test)]
+mod test_utils;
+pub mod timelock_events;
+pub mod timelock_recovery;
+pub mod timelock_sessions;
+pub mod timelock_share_storage;
+pub mod timelock_submissions;
 pub mod transcript_aggregation;
 pub mod types;
 
@@ -18,13 +26,16 @@ use aptos_event_notifications::{
     DbBackedOnChainConfig, EventNotificationListener, ReconfigNotificationListener,
 };
 use aptos_network::application::interface::{NetworkClient, NetworkServiceEvents};
+use aptos_types::chain_id::ChainId;
 use aptos_validator_transaction_pool::VTxnPoolState;
 use move_core_types::account_address::AccountAddress;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 pub use types::DKGMessage;
 
 pub fn start_dkg_runtime(
     my_addr: AccountAddress,
+    chain_id: ChainId,
     safety_rules_config: &SafetyRulesConfig,
     network_client: NetworkClient<DKGMessage>,
     network_service_events: NetworkServiceEvents<DKGMessage>,
@@ -33,6 +44,8 @@ pub fn start_dkg_runtime(
     vtxn_pool: VTxnPoolState,
     rb_config: ReliableBroadcastConfig,
     randomness_override_seq_num: u64,
+    max_concurrent_timelock_dkgs: usize,
+    timelock_reveal_deferral_timeout: Duration,
 ) -> Runtime {
     let runtime = aptos_runtimes::spawn_named_runtime("dkg".into(), Some(4));
     let (self_sender, self_receiver) = aptos_channels::new(1_024, &counters::PENDING_SELF_MESSAGES);
@@ -41,6 +54,7 @@ pub fn start_dkg_runtime(
     let dkg_epoch_manager = EpochManager::new(
         safety_rules_config,
         my_addr,
+        chain_id,
         reconfig_events,
         dkg_start_events,
         self_sender,
@@ -48,6 +62,8 @@ pub fn start_dkg_runtime(
         vtxn_pool,
         rb_config,
         randomness_override_seq_num,
+        max_concurrent_timelock_dkgs,
+        timelock_reveal_deferral_timeout,
     );
     let (network_task, network_receiver) = NetworkTask::new(network_service_events, self_receiver);
     runtime.spawn(network_task.start());
@@ -0,0 +1,61 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_logger::Schema;
+use serde::Serialize;
+
+#[derive(Schema)]
+pub struct TimelockLogSchema {
+    stage: TimelockStage,
+    interval: Option<u64>,
+    epoch: Option<u64>,
+    validator_index: Option<usize>,
+}
+
+impl TimelockLogSchema {
+    pub fn new(stage: TimelockStage) -> Self {
+        Self {
+            stage,
+            interval: None,
+            epoch: None,
+            validator_index: None,
+        }
+    }
+}
+
+/// The stages of a timelock interval, as seen by this validator.
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelockStage {
+    KeyGenRequested,
+    KeyGenQueued,
+    KeyGenStarted,
+    KeyGenCompleted,
+    KeyGenFailed,
+    ShareStored,
+    ShareRetrieved,
+    RevealRequested,
+    RevealDeferred,
+    ShareRevealed,
+    ShareResubmitted,
+    ShareOnChain,
+}
+
+impl TimelockStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimelockStage::KeyGenRequested => "key_gen_requested",
+            TimelockStage::KeyGenQueued => "key_gen_queued",
+            TimelockStage::KeyGenStarted => "key_gen_started",
+            TimelockStage::KeyGenCompleted => "key_gen_completed",
+            TimelockStage::KeyGenFailed => "key_gen_failed",
+            TimelockStage::ShareStored => "share_stored",
+            TimelockStage::ShareRetrieved => "share_retrieved",
+            TimelockStage::RevealRequested => "reveal_requested",
+            TimelockStage::RevealDeferred => "reveal_deferred",
+            TimelockStage::ShareRevealed => "share_revealed",
+            TimelockStage::ShareResubmitted => "share_resubmitted",
+            TimelockStage::ShareOnChain => "share_on_chain",
+        }
+    }
+}
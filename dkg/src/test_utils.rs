@@ -0,0 +1,117 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A real timelock DKG among a few validators, for the tests of the share lifecycle.
+
+use crate::epoch_manager::extract_timelock_share;
+use aptos_crypto::{
+    bls12381,
+    blstrs::{g1_proj_from_bytes, G1_PROJ_NUM_BYTES},
+    Uniform,
+};
+use aptos_dkg::{
+    ibe::{verify_timelock_key_share, TimelockIdentity},
+    pvss::{traits::Transcript, Player},
+};
+use aptos_types::{
+    dkg::{real_dkg::RealDKG, DKGSessionMetadata, DKGTrait, DefaultDKG, TimelockConfig},
+    validator_verifier::{ValidatorConsensusInfo, ValidatorVerifier},
+};
+use move_core_types::account_address::AccountAddress;
+use rand::thread_rng;
+use zeroize::Zeroizing;
+
+pub(crate) struct TimelockDKGFixture {
+    pub(crate) addrs: Vec<AccountAddress>,
+    pub(crate) private_keys: Vec<bls12381::PrivateKey>,
+    pub(crate) session_metadata: DKGSessionMetadata,
+    pub(crate) pub_params: <DefaultDKG as DKGTrait>::PublicParams,
+    pub(crate) transcript: <DefaultDKG as DKGTrait>::Transcript,
+}
+
+impl TimelockDKGFixture {
+    /// Runs the DKG of `config` in epoch 1 among `config.total_validators` validators of equal
+    /// voting power, each of which deals.
+    pub(crate) fn run(config: &TimelockConfig) -> Self {
+        let mut rng = thread_rng();
+        let num_validators = config.total_validators as usize;
+        let addrs: Vec<AccountAddress> = (0..num_validators)
+            .map(|_| AccountAddress::random())
+            .collect();
+        let private_keys: Vec<bls12381::PrivateKey> = (0..num_validators)
+            .map(|_| bls12381::PrivateKey::generate(&mut rng))
+            .collect();
+        let verifier = ValidatorVerifier::new(
+            addrs
+                .iter()
+                .zip(&private_keys)
+                .map(|(addr, sk)| {
+                    ValidatorConsensusInfo::new(*addr, bls12381::PublicKey::from(sk), 1)
+                })
+                .collect(),
+        );
+        let session_metadata = config.session_metadata(1, &verifier);
+        let pub_params = DefaultDKG::new_public_params(&session_metadata);
+
+        let mut transcript = RealDKG::sample_secret_and_generate_transcript(
+            &mut rng,
+            &pub_params,
+            0,
+            &private_keys[0],
+        );
+        for (i, sk) in private_keys.iter().enumerate().skip(1) {
+            let other =
+                RealDKG::sample_secret_and_generate_transcript(&mut rng, &pub_params, i as u64, sk);
+            DefaultDKG::aggregate_transcripts(&pub_params, &mut transcript, other);
+        }
+        DefaultDKG::verify_transcript(&pub_params, &transcript).unwrap();
+
+        Self {
+            addrs,
+            private_keys,
+            session_metadata,
+            pub_params,
+            transcript,
+        }
+    }
+
+    /// The secret share of validator `i`, as stored once its DKG session completes.
+    pub(crate) fn share(&self, i: usize) -> Zeroizing<Vec<u8>> {
+        extract_timelock_share(
+            &self.session_metadata,
+            &self.transcript,
+            i,
+            &self.private_keys[i],
+        )
+        .unwrap()
+    }
+
+    /// The compressed master public key, as published on-chain.
+    pub(crate) fn mpk_bytes(&self) -> Vec<u8> {
+        self.transcript.dealt_public_key_bytes()
+    }
+
+    /// Whether `key_share` is validator `i`'s decryption key share for `identity`, checked
+    /// against the public key shares of the transcript.
+    pub(crate) fn is_key_share_valid(
+        &self,
+        i: usize,
+        key_share: &[u8],
+        identity: &TimelockIdentity,
+    ) -> bool {
+        let pk_shares = self
+            .transcript
+            .main
+            .get_public_key_share(&self.pub_params.pvss_config.wconfig, &Player { id: i });
+        key_share.len() == pk_shares.len() * G1_PROJ_NUM_BYTES
+            && key_share
+                .chunks_exact(G1_PROJ_NUM_BYTES)
+                .zip(&pk_shares)
+                .all(|(bytes, pk_share)| {
+                    g1_proj_from_bytes(bytes).is_ok_and(|point| {
+                        verify_timelock_key_share(&point, pk_share.as_group_element(), identity)
+                            .is_ok()
+                    })
+                })
+    }
+}
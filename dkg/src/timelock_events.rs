@@ -0,0 +1,223 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sanity checks on the on-chain timelock events, before the epoch manager acts on them.
+
+use aptos_logger::warn;
+use aptos_types::dkg::{RequestRevealEvent, StartKeyGenEvent, TimelockConfig};
+use std::fmt;
+
+/// Why a timelock event was rejected.
+#[derive(Debug, Eq, PartialEq)]
+pub enum TimelockEventRejection {
+    ZeroTotalValidators,
+    ZeroThreshold,
+    ThresholdAboveTotal {
+        threshold: u64,
+        total: u64,
+    },
+    /// The interval is not newer than one we already acted on.
+    Replay {
+        interval: u64,
+        latest: u64,
+    },
+}
+
+impl TimelockEventRejection {
+    /// Metric label for the rejection reason.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ZeroTotalValidators => "zero_total_validators",
+            Self::ZeroThreshold => "zero_threshold",
+            Self::ThresholdAboveTotal { .. } => "threshold_above_total",
+            Self::Replay { .. } => "replay",
+        }
+    }
+}
+
+impl fmt::Display for TimelockEventRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ZeroTotalValidators => write!(f, "total_validators is 0"),
+            Self::ZeroThreshold => write!(f, "threshold is 0"),
+            Self::ThresholdAboveTotal { threshold, total } => {
+                write!(
+                    f,
+                    "threshold {} exceeds total_validators {}",
+                    threshold, total
+                )
+            },
+            Self::Replay { interval, latest } => write!(
+                f,
+                "interval {} is not newer than already processed interval {}",
+                interval, latest
+            ),
+        }
+    }
+}
+
+/// Validates timelock events, tracking the latest interval seen of each kind so that replayed
+/// events are rejected.
+#[derive(Default)]
+pub struct TimelockEventValidator {
+    latest_started: Option<u64>,
+    latest_revealed: Option<u64>,
+}
+
+impl TimelockEventValidator {
+    /// Validates a `StartKeyGenEvent`, returning the config to run the DKG with.
+    ///
+    /// If `total_validators` differs from the size of the current validator set, the config is
+    /// scaled to the actual size, keeping the threshold ratio.
+    pub fn validate_start(
+        &mut self,
+        event: &StartKeyGenEvent,
+        num_validators: u64,
+    ) -> Result<TimelockConfig, TimelockEventRejection> {
+        let TimelockConfig {
+            threshold,
+            total_validators: total,
+        } = event.config;
+        if total == 0 {
+            return Err(TimelockEventRejection::ZeroTotalValidators);
+        }
+        if threshold == 0 {
+            return Err(TimelockEventRejection::ZeroThreshold);
+        }
+        if threshold > total {
+            return Err(TimelockEventRejection::ThresholdAboveTotal { threshold, total });
+        }
+        Self::check_monotone(event.interval, self.latest_started)?;
+        self.latest_started = Some(event.interval);
+
+        let config = event.config.scaled_to(num_validators);
+        if config != event.config {
+            warn!(
+                "[Timelock] StartKeyGenEvent {:?} expects {} validators but the validator set has {}; using threshold {} of {}",
+                event, total, num_validators, config.threshold, config.total_validators
+            );
+        }
+        Ok(config)
+    }
+
+    /// Validates a `RequestRevealEvent`.
+    pub fn validate_reveal(
+        &mut self,
+        event: &RequestRevealEvent,
+    ) -> Result<(), TimelockEventRejection> {
+        Self::check_monotone(event.interval, self.latest_revealed)?;
+        self.latest_revealed = Some(event.interval);
+        Ok(())
+    }
+
+    /// Whether we saw the `StartKeyGenEvent` for `interval` since starting up.
+    pub fn key_gen_seen(&self, interval: u64) -> bool {
+        self.latest_started.is_some_and(|latest| interval <= latest)
+    }
+
+    /// Whether we processed a `RequestRevealEvent` for `interval` (or a later one) since
+    /// starting up.
+    pub fn reveal_seen(&self, interval: u64) -> bool {
+        self.latest_revealed
+            .is_some_and(|latest| interval <= latest)
+    }
+
+    fn check_monotone(interval: u64, latest: Option<u64>) -> Result<(), TimelockEventRejection> {
+        match latest {
+            Some(latest) if interval <= latest => {
+                Err(TimelockEventRejection::Replay { interval, latest })
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start_event(interval: u64, threshold: u64, total_validators: u64) -> StartKeyGenEvent {
+        StartKeyGenEvent {
+            interval,
+            config: TimelockConfig {
+                threshold,
+                total_validators,
+            },
+        }
+    }
+
+    #[test]
+    fn test_invalid_config_rejected() {
+        let mut validator = TimelockEventValidator::default();
+        assert_eq!(
+            validator.validate_start(&start_event(1, 5, 4), 4),
+            Err(TimelockEventRejection::ThresholdAboveTotal {
+                threshold: 5,
+                total: 4
+            })
+        );
+        assert_eq!(
+            validator.validate_start(&start_event(1, 0, 0), 4),
+            Err(TimelockEventRejection::ZeroTotalValidators)
+        );
+        assert_eq!(
+            validator.validate_start(&start_event(1, 0, 4), 4),
+            Err(TimelockEventRejection::ZeroThreshold)
+        );
+
+        // Rejected events do not count as seen
+        assert!(!validator.key_gen_seen(1));
+        assert!(validator.validate_start(&start_event(1, 3, 4), 4).is_ok());
+        assert!(validator.key_gen_seen(1));
+    }
+
+    #[test]
+    fn test_duplicate_start_rejected() {
+        let mut validator = TimelockEventValidator::default();
+        assert!(validator.validate_start(&start_event(5, 3, 4), 4).is_ok());
+        assert_eq!(
+            validator.validate_start(&start_event(5, 3, 4), 4),
+            Err(TimelockEventRejection::Replay {
+                interval: 5,
+                latest: 5
+            })
+        );
+        assert_eq!(
+            validator
+                .validate_start(&start_event(4, 3, 4), 4)
+                .unwrap_err()
+                .label(),
+            "replay"
+        );
+        assert!(validator.validate_start(&start_event(6, 3, 4), 4).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_reveal_rejected() {
+        let mut validator = TimelockEventValidator::default();
+        assert!(validator
+            .validate_reveal(&RequestRevealEvent { interval: 5 })
+            .is_ok());
+        assert!(validator
+            .validate_reveal(&RequestRevealEvent { interval: 5 })
+            .is_err());
+        assert!(validator
+            .validate_reveal(&RequestRevealEvent { interval: 6 })
+            .is_ok());
+    }
+
+    #[test]
+    fn test_total_clamped_to_validator_set() {
+        let mut validator = TimelockEventValidator::default();
+        let config = validator.validate_start(&start_event(1, 7, 10), 4).unwrap();
+        assert_eq!(config, TimelockConfig {
+            threshold: 3,
+            total_validators: 4,
+        });
+        let config = validator.validate_start(&start_event(2, 2, 3), 4).unwrap();
+        assert_eq!(config, TimelockConfig {
+            threshold: 3,
+            total_validators: 4,
+        });
+    }
+}
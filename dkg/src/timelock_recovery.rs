@@ -0,0 +1,105 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recovery of the timelock duties whose events were missed while the node was down.
+
+use crate::timelock_events::TimelockEventValidator;
+use aptos_types::dkg::TimelockState;
+
+/// A timelock duty recovered from the on-chain state.
+#[derive(Debug, Eq, PartialEq)]
+pub enum RecoveredTimelockDuty {
+    /// Join the key generation for the interval, with the on-chain `KeyGenConfig` that
+    /// `0x1::timelock::on_new_block` requested it with.
+    KeyGen { interval: u64 },
+    /// Reveal our share for the interval.
+    Reveal { interval: u64 },
+}
+
+/// Returns the duties implied by the on-chain timelock state that we have not acted on since
+/// starting up, oldest first:
+/// - a reveal for each of the `lookback` intervals before the current one for which we hold a
+///   share (`has_share`), and
+/// - the key generation for the current interval, unless we already hold its share.
+///
+/// The state does not say which secrets have already been aggregated, so a recovered reveal may
+/// be redundant; publishing a share twice is harmless.
+pub fn pending_timelock_duties(
+    state: &TimelockState,
+    validator: &TimelockEventValidator,
+    lookback: u64,
+    mut has_share: impl FnMut(u64) -> bool,
+) -> Vec<RecoveredTimelockDuty> {
+    let current = state.current_interval;
+    let mut duties: Vec<_> = (current.saturating_sub(lookback)..current)
+        .filter(|interval| !validator.reveal_seen(*interval) && has_share(*interval))
+        .map(|interval| RecoveredTimelockDuty::Reveal { interval })
+        .collect();
+    if !validator.key_gen_seen(current) && !has_share(current) {
+        duties.push(RecoveredTimelockDuty::KeyGen { interval: current });
+    }
+    duties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        dkg::{RequestRevealEvent, StartKeyGenEvent, TimelockConfig},
+        event::{EventHandle, EventKey},
+        state_store::table::TableHandle,
+    };
+    use move_core_types::account_address::AccountAddress;
+
+    fn on_chain_state(current_interval: u64) -> TimelockState {
+        TimelockState {
+            current_interval,
+            last_rotation_time: 1,
+            public_keys: TableHandle(AccountAddress::random()),
+            revealed_secrets: TableHandle(AccountAddress::random()),
+            start_keygen_events: EventHandle::new(EventKey::new(0, AccountAddress::ONE), 0),
+            request_reveal_events: EventHandle::new(EventKey::new(1, AccountAddress::ONE), 0),
+        }
+    }
+
+    #[test]
+    fn test_restarted_node_schedules_pending_reveal() {
+        // Restarted between the key-gen for interval 4 and its reveal: interval 5 is current and
+        // we hold the persisted share for interval 4 only.
+        let state = on_chain_state(5);
+        let validator = TimelockEventValidator::default();
+        let duties = pending_timelock_duties(&state, &validator, 2, |interval| interval == 4);
+        assert_eq!(duties, vec![
+            RecoveredTimelockDuty::Reveal { interval: 4 },
+            RecoveredTimelockDuty::KeyGen { interval: 5 },
+        ]);
+    }
+
+    #[test]
+    fn test_duties_seen_since_startup_not_recovered() {
+        let state = on_chain_state(5);
+        let mut validator = TimelockEventValidator::default();
+        validator
+            .validate_reveal(&RequestRevealEvent { interval: 4 })
+            .unwrap();
+        validator
+            .validate_start(
+                &StartKeyGenEvent {
+                    interval: 5,
+                    config: TimelockConfig {
+                        threshold: 2,
+                        total_validators: 3,
+                    },
+                },
+                4,
+            )
+            .unwrap();
+        assert!(pending_timelock_duties(&state, &validator, 2, |_| true).is_empty());
+
+        // Key-gen already completed locally
+        let validator = TimelockEventValidator::default();
+        assert!(
+            pending_timelock_duties(&state, &validator, 2, |interval| interval == 5).is_empty()
+        );
+    }
+}
@@ -0,0 +1,810 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bookkeeping for the timelock DKG sessions that run alongside the epoch DKG, one per interval.
+
+use crate::{
+    counters::{
+        set_timelock_latest_interval, TIMELOCK_DKG_QUEUE_DEPTH, TIMELOCK_KEY_GEN_SECONDS,
+        TIMELOCK_KEY_GEN_SESSIONS,
+    },
+    logging::{TimelockLogSchema, TimelockStage},
+    network::IncomingRpcRequest,
+};
+use anyhow::{anyhow, Result};
+use aptos_channels::aptos_channel;
+use aptos_logger::{debug, error, info, warn};
+use aptos_types::dkg::TimelockConfig;
+use futures::future::join_all;
+use futures_channel::{
+    mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
+use move_core_types::account_address::AccountAddress;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+};
+use tokio::time::timeout;
+use zeroize::Zeroizing;
+
+/// Maximum number of concurrently tracked timelock DKG sessions.
+pub const MAX_TIMELOCK_SESSIONS: usize = 16;
+
+/// How long to wait for the timelock DKG managers to acknowledge a close.
+pub const TIMELOCK_SESSION_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The outcome of a timelock DKG session: this validator's serialized secret share, or the
+/// reason it could not be obtained.
+pub struct TimelockDKGCompletion {
+    pub interval: u64,
+    // The session of the interval that reported, as the DKG of an interval may be restarted.
+    session_id: u64,
+    pub result: Result<Zeroizing<Vec<u8>>>,
+}
+
+/// Channels to a running timelock DKG manager.
+pub struct TimelockSession {
+    pub rpc_msg_tx: aptos_channel::Sender<AccountAddress, (AccountAddress, IncomingRpcRequest)>,
+    // Dropping this without a send also stops the manager.
+    pub close_tx: oneshot::Sender<oneshot::Sender<()>>,
+}
+
+struct TrackedSession {
+    id: u64,
+    session: TimelockSession,
+    // The on-chain config of the interval, to restart the DKG with if it is interrupted.
+    config: TimelockConfig,
+    // Whether the DKG produced our share; completed sessions keep serving peers until reveal
+    // but no longer count towards `max_running`.
+    completed: bool,
+    started_at: Instant,
+}
+
+/// The timelock DKG sessions, keyed by interval.
+///
+/// At most `max_running` sessions run their DKG at once; sessions started beyond that are queued
+/// in FIFO order until a running one completes or is closed. At most `max_sessions` are tracked;
+/// beyond that, the oldest interval's session is evicted.
+///
+/// Reveal requests for intervals whose DKG is still running or queued are deferred until it
+/// completes, for at most `reveal_deferral_timeout`.
+pub struct TimelockSessions {
+    sessions: BTreeMap<u64, TrackedSession>,
+    max_sessions: usize,
+    max_running: usize,
+    queued: VecDeque<(u64, TimelockConfig)>,
+    // Interval -> deadline of its deferred reveal request
+    deferred_reveals: BTreeMap<u64, Instant>,
+    reveal_deferral_timeout: Duration,
+    completion_tx: UnboundedSender<TimelockDKGCompletion>,
+    next_session_id: u64,
+}
+
+impl TimelockSessions {
+    /// Returns the sessions and the receiver on which their completions are reported.
+    pub fn new(
+        max_sessions: usize,
+        max_running: usize,
+        reveal_deferral_timeout: Duration,
+    ) -> (Self, UnboundedReceiver<TimelockDKGCompletion>) {
+        let (completion_tx, completion_rx) = unbounded();
+        let sessions = Self {
+            sessions: BTreeMap::new(),
+            max_sessions,
+            max_running,
+            queued: VecDeque::new(),
+            deferred_reveals: BTreeMap::new(),
+            reveal_deferral_timeout,
+            completion_tx,
+            next_session_id: 0,
+        };
+        (sessions, completion_rx)
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Number of sessions whose DKG has not completed yet.
+    pub fn num_running(&self) -> usize {
+        self.sessions.values().filter(|s| !s.completed).count()
+    }
+
+    /// Intervals waiting for a free slot, in the order they will be started.
+    pub fn queued_intervals(&self) -> Vec<u64> {
+        self.queued.iter().map(|(interval, _)| *interval).collect()
+    }
+
+    /// Whether the DKG for `interval` is queued, or running and not completed yet.
+    pub fn is_key_gen_pending(&self, interval: u64) -> bool {
+        self.sessions
+            .get(&interval)
+            .is_some_and(|tracked| !tracked.completed)
+            || self.queued.iter().any(|(queued, _)| *queued == interval)
+    }
+
+    /// Defers the reveal request for `interval` if its DKG is still pending, returning whether it
+    /// was deferred. The caller is expected to reveal once `take_deferred_reveal` returns true.
+    pub fn defer_reveal(&mut self, interval: u64, now: Instant) -> bool {
+        if !self.is_key_gen_pending(interval) {
+            return false;
+        }
+        info!(
+            TimelockLogSchema::new(TimelockStage::RevealDeferred).interval(interval),
+            "[Timelock] DKG for interval {} has not completed yet, deferring its reveal for up to {:?}",
+            interval,
+            self.reveal_deferral_timeout
+        );
+        self.deferred_reveals
+            .insert(interval, now + self.reveal_deferral_timeout);
+        true
+    }
+
+    /// Whether a reveal request for `interval` was deferred; it is no longer tracked afterwards.
+    pub fn take_deferred_reveal(&mut self, interval: u64) -> bool {
+        self.deferred_reveals.remove(&interval).is_some()
+    }
+
+    /// Gives up on the deferred reveal requests whose deadline has passed, stopping their DKG
+    /// sessions. Returns their intervals.
+    pub fn expire_deferred_reveals(&mut self, now: Instant) -> Vec<u64> {
+        let expired: Vec<u64> = self
+            .deferred_reveals
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(interval, _)| *interval)
+            .collect();
+        for interval in &expired {
+            self.deferred_reveals.remove(interval);
+            self.remove(*interval);
+            self.queued.retain(|(queued, _)| queued != interval);
+            error!(
+                TimelockLogSchema::new(TimelockStage::RevealDeferred).interval(*interval),
+                "[Timelock] DKG for interval {} did not complete within {:?} of its reveal request; not revealing a share for it",
+                interval,
+                self.reveal_deferral_timeout
+            );
+        }
+        if !expired.is_empty() {
+            self.update_queue_depth();
+        }
+        expired
+    }
+
+    /// Returns `config` if a session for `interval` may start now; the caller is then expected to
+    /// `insert` it. Otherwise the interval is queued and `None` is returned.
+    pub fn admit(&mut self, interval: u64, config: TimelockConfig) -> Option<TimelockConfig> {
+        if self.num_running() < self.max_running {
+            return Some(config);
+        }
+        info!(
+            TimelockLogSchema::new(TimelockStage::KeyGenQueued).interval(interval),
+            "[Timelock] {} DKG sessions already running, queueing interval {} behind {:?}",
+            self.max_running,
+            interval,
+            self.queued_intervals()
+        );
+        self.queued.push_back((interval, config));
+        self.update_queue_depth();
+        None
+    }
+
+    /// Drops the queued intervals for which `is_expired` holds, unless their reveal is deferred,
+    /// then returns the next queued interval to start if a slot is free.
+    pub fn next_queued(
+        &mut self,
+        is_expired: impl Fn(u64) -> bool,
+    ) -> Option<(u64, TimelockConfig)> {
+        let deferred_reveals = &self.deferred_reveals;
+        self.queued.retain(|(interval, _)| {
+            let expired = is_expired(*interval) && !deferred_reveals.contains_key(interval);
+            if expired {
+                warn!(
+                    "[Timelock] Dropping queued DKG for interval {}: its reveal was already requested",
+                    interval
+                );
+            }
+            !expired
+        });
+        let next = if self.num_running() < self.max_running {
+            self.queued.pop_front()
+        } else {
+            None
+        };
+        self.update_queue_depth();
+        next
+    }
+
+    /// Tracks the session for `interval`, run with `config`, and spawns a task that waits for its aggregated
+    /// transcript, extracts this validator's share from it with `extract_share`, and reports the
+    /// outcome as a `TimelockDKGCompletion`.
+    ///
+    /// If `transcript_rx` is dropped without a send (e.g., the manager stopped early), a failed
+    /// completion is reported.
+    pub fn insert<T, F>(
+        &mut self,
+        interval: u64,
+        config: TimelockConfig,
+        session: TimelockSession,
+        transcript_rx: oneshot::Receiver<T>,
+        extract_share: F,
+    ) where
+        T: Send + 'static,
+        F: FnOnce(T) -> Result<Zeroizing<Vec<u8>>> + Send + 'static,
+    {
+        if !self.sessions.contains_key(&interval) {
+            while self.sessions.len() >= self.max_sessions {
+                let Some((oldest, _)) = self.sessions.pop_first() else {
+                    break;
+                };
+                error!(
+                    "[Timelock] Tracking {} DKG sessions; evicting the one for interval {} to make room for interval {}. Sessions are not completing, or not being closed.",
+                    self.max_sessions, oldest, interval
+                );
+            }
+        }
+        let session_id = self.next_session_id;
+        self.next_session_id += 1;
+        self.sessions.insert(interval, TrackedSession {
+            id: session_id,
+            session,
+            config,
+            completed: false,
+            started_at: Instant::now(),
+        });
+        TIMELOCK_KEY_GEN_SESSIONS
+            .with_label_values(&["started"])
+            .inc();
+        set_timelock_latest_interval(TimelockStage::KeyGenStarted, interval);
+
+        let completion_tx = self.completion_tx.clone();
+        tokio::spawn(async move {
+            let result = match transcript_rx.await {
+                Ok(transcript) => extract_share(transcript),
+                Err(_) => Err(anyhow!(
+                    "DKG manager stopped before producing an aggregated transcript"
+                )),
+            };
+            let _ = completion_tx.unbounded_send(TimelockDKGCompletion {
+                interval,
+                session_id,
+                result,
+            });
+        });
+    }
+
+    /// Forwards an RPC request to the session for `interval`.
+    ///
+    /// Requests for intervals without a running session (e.g., already revealed, or that this
+    /// validator does not participate in) are dropped.
+    pub fn forward_rpc_request(
+        &self,
+        interval: u64,
+        peer_id: AccountAddress,
+        request: IncomingRpcRequest,
+    ) {
+        match self.sessions.get(&interval) {
+            Some(tracked) => {
+                let _ = tracked.session.rpc_msg_tx.push(peer_id, (peer_id, request));
+            },
+            None => debug!(
+                "[Timelock] Dropping {} from {} for interval {} without a running session",
+                request.msg.name(),
+                peer_id,
+                interval
+            ),
+        }
+    }
+
+    /// Stops tracking the session for `interval`, which also stops its manager.
+    pub fn remove(&mut self, interval: u64) -> Option<TimelockSession> {
+        self.sessions
+            .remove(&interval)
+            .map(|tracked| tracked.session)
+    }
+
+    /// Closes all sessions, waiting up to `ack_timeout` for their managers to acknowledge.
+    ///
+    /// The sessions whose DKG had not completed are queued again, ahead of the already queued
+    /// intervals, so that their DKG restarts in the next epoch; like the queued intervals, they
+    /// keep their deferred reveals.
+    pub async fn close_all(&mut self, ack_timeout: Duration) {
+        let sessions = std::mem::take(&mut self.sessions);
+        for (interval, tracked) in sessions.iter().rev() {
+            if !tracked.completed {
+                info!(
+                    TimelockLogSchema::new(TimelockStage::KeyGenQueued).interval(*interval),
+                    "[Timelock] DKG for interval {} interrupted, queueing it to restart in the next epoch",
+                    interval
+                );
+                self.queued.push_front((*interval, tracked.config.clone()));
+            }
+        }
+        self.update_queue_depth();
+        let mut ack_rxs = Vec::with_capacity(sessions.len());
+        for (interval, tracked) in sessions {
+            let (ack_tx, ack_rx) = oneshot::channel();
+            if tracked.session.close_tx.send(ack_tx).is_ok() {
+                ack_rxs.push(ack_rx);
+            } else {
+                debug!(
+                    "[Timelock] DKG manager for interval {} already stopped",
+                    interval
+                );
+            }
+        }
+
+        let num_sessions = ack_rxs.len();
+        if timeout(ack_timeout, join_all(ack_rxs)).await.is_err() {
+            warn!(
+                "[Timelock] Timed out after {:?} waiting for {} DKG managers to close",
+                ack_timeout, num_sessions
+            );
+        }
+    }
+
+    /// Handles a reported completion, returning the share to be stored on success.
+    ///
+    /// A successful session is kept running: its transcript stays in the validator txn pool, and
+    /// peers that have not aggregated yet may still request our transcript. It no longer counts
+    /// as running, though, so a queued interval may start. A failed session is removed, along with
+    /// its deferred reveal. Failures of sessions that were already closed are expected and only
+    /// logged at debug level; they leave a session restarted for the same interval running.
+    pub fn on_completion(
+        &mut self,
+        completion: TimelockDKGCompletion,
+    ) -> Option<(u64, Zeroizing<Vec<u8>>)> {
+        let TimelockDKGCompletion {
+            interval,
+            session_id,
+            result,
+        } = completion;
+        let is_tracked = self
+            .sessions
+            .get(&interval)
+            .is_some_and(|tracked| tracked.id == session_id);
+        match result {
+            Ok(share) => {
+                if let Some(tracked) = self.sessions.get_mut(&interval).filter(|_| is_tracked) {
+                    tracked.completed = true;
+                    TIMELOCK_KEY_GEN_SECONDS.observe(tracked.started_at.elapsed().as_secs_f64());
+                }
+                TIMELOCK_KEY_GEN_SESSIONS
+                    .with_label_values(&["completed"])
+                    .inc();
+                set_timelock_latest_interval(TimelockStage::KeyGenCompleted, interval);
+                info!(
+                    TimelockLogSchema::new(TimelockStage::KeyGenCompleted).interval(interval),
+                    "[Timelock] DKG for interval {} completed with a {}-byte share",
+                    interval,
+                    share.len()
+                );
+                Some((interval, share))
+            },
+            Err(e) => {
+                if is_tracked && self.remove(interval).is_some() {
+                    TIMELOCK_KEY_GEN_SESSIONS
+                        .with_label_values(&["failed"])
+                        .inc();
+                    error!(
+                        TimelockLogSchema::new(TimelockStage::KeyGenFailed).interval(interval),
+                        "[Timelock] DKG for interval {} failed: {}", interval, e
+                    );
+                    if self.take_deferred_reveal(interval) {
+                        error!(
+                            TimelockLogSchema::new(TimelockStage::KeyGenFailed).interval(interval),
+                            "[Timelock] Dropping deferred reveal for interval {}: its DKG failed",
+                            interval
+                        );
+                    }
+                } else {
+                    debug!(
+                        "[Timelock] Closed DKG session for interval {} ended: {}",
+                        interval, e
+                    );
+                }
+                None
+            },
+        }
+    }
+
+    fn update_queue_depth(&self) {
+        TIMELOCK_DKG_QUEUE_DEPTH.set(self.queued.len() as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        epoch_manager::{derive_timelock_share, extract_timelock_share},
+        network::DummyRpcResponseSender,
+        test_utils::TimelockDKGFixture,
+        timelock_share_storage::{
+            TimelockShareStorage, TimelockShareWrappingKey, DEFAULT_MAX_CACHED_TIMELOCK_SHARES,
+            DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS,
+        },
+        types::{DKGSessionId, DKGTranscriptRequest, TimelockDKGTranscriptRequest},
+        DKGMessage,
+    };
+    use aptos_channels::message_queues::QueueStyle;
+    use aptos_crypto::{bls12381, Uniform};
+    use aptos_dkg::ibe::compute_timelock_identity;
+    use aptos_infallible::RwLock;
+    use aptos_secure_storage::{InMemoryStorage, Storage};
+    use aptos_types::chain_id::ChainId;
+    use futures::{FutureExt, StreamExt};
+    use std::sync::Arc;
+
+    const REVEAL_DEFERRAL_TIMEOUT: Duration = Duration::from_secs(300);
+
+    const CONFIG: TimelockConfig = TimelockConfig {
+        threshold: 3,
+        total_validators: 4,
+    };
+
+    type RpcMsgRx = aptos_channel::Receiver<AccountAddress, (AccountAddress, IncomingRpcRequest)>;
+
+    /// Returns a session whose manager is represented only by the returned receivers.
+    fn stub_session() -> (
+        TimelockSession,
+        RpcMsgRx,
+        oneshot::Receiver<oneshot::Sender<()>>,
+    ) {
+        let (rpc_msg_tx, rpc_msg_rx) = aptos_channel::new(QueueStyle::FIFO, 10, None);
+        let (close_tx, close_rx) = oneshot::channel();
+        (
+            TimelockSession {
+                rpc_msg_tx,
+                close_tx,
+            },
+            rpc_msg_rx,
+            close_rx,
+        )
+    }
+
+    /// Inserts a stub session for `interval` whose share is its transcript.
+    fn start_stub_session(
+        sessions: &mut TimelockSessions,
+        interval: u64,
+    ) -> (
+        oneshot::Sender<Vec<u8>>,
+        RpcMsgRx,
+        oneshot::Receiver<oneshot::Sender<()>>,
+    ) {
+        let (session, rpc_msg_rx, close_rx) = stub_session();
+        let (transcript_tx, transcript_rx) = oneshot::channel::<Vec<u8>>();
+        sessions.insert(interval, CONFIG, session, transcript_rx, |transcript| {
+            Ok(Zeroizing::new(transcript))
+        });
+        (transcript_tx, rpc_msg_rx, close_rx)
+    }
+
+    fn share_storage() -> TimelockShareStorage<Storage> {
+        let mut storage = TimelockShareStorage::new(
+            Storage::from(InMemoryStorage::new()),
+            DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS,
+            DEFAULT_MAX_CACHED_TIMELOCK_SHARES,
+        );
+        storage
+            .set_wrapping_key(
+                TimelockShareWrappingKey::derive(&bls12381::PrivateKey::generate_for_testing())
+                    .unwrap(),
+                |_| unreachable!("no share is stored before the key is set"),
+            )
+            .unwrap();
+        storage
+    }
+
+    fn timelock_request(interval: u64, sender: AccountAddress) -> IncomingRpcRequest {
+        IncomingRpcRequest {
+            msg: DKGMessage::TimelockTranscriptRequest(TimelockDKGTranscriptRequest::new(
+                interval,
+                DKGTranscriptRequest::new(1),
+            )),
+            sender,
+            response_sender: Box::new(DummyRpcResponseSender::new(Arc::new(RwLock::new(vec![])))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rpc_requests_routed_by_interval() {
+        let (mut sessions, _completion_rx) = TimelockSessions::new(
+            MAX_TIMELOCK_SESSIONS,
+            MAX_TIMELOCK_SESSIONS,
+            REVEAL_DEFERRAL_TIMEOUT,
+        );
+        let mut rpc_msg_rxs = vec![];
+        let mut managers = vec![];
+        for interval in [1000, 1001] {
+            let (session, rpc_msg_rx, close_rx) = stub_session();
+            let (transcript_tx, transcript_rx) = oneshot::channel::<Vec<u8>>();
+            sessions.insert(interval, CONFIG, session, transcript_rx, |transcript| {
+                Ok(Zeroizing::new(transcript))
+            });
+            rpc_msg_rxs.push(rpc_msg_rx);
+            managers.push((transcript_tx, close_rx));
+        }
+
+        let peer_a = AccountAddress::random();
+        let peer_b = AccountAddress::random();
+        sessions.forward_rpc_request(1000, peer_a, timelock_request(1000, peer_a));
+        sessions.forward_rpc_request(1001, peer_b, timelock_request(1001, peer_b));
+        // No session for this interval: dropped
+        sessions.forward_rpc_request(1002, peer_a, timelock_request(1002, peer_a));
+
+        for ((interval, peer), rpc_msg_rx) in [(1000, peer_a), (1001, peer_b)]
+            .into_iter()
+            .zip(rpc_msg_rxs.iter_mut())
+        {
+            let (sender, request) = rpc_msg_rx.select_next_some().await;
+            assert_eq!(sender, peer);
+            assert_eq!(request.msg.session(), DKGSessionId::Timelock(interval));
+        }
+        for rpc_msg_rx in rpc_msg_rxs.iter_mut() {
+            assert!(rpc_msg_rx.select_next_some().now_or_never().is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_completed_share_is_stored() {
+        let (mut sessions, mut completion_rx) = TimelockSessions::new(
+            MAX_TIMELOCK_SESSIONS,
+            MAX_TIMELOCK_SESSIONS,
+            REVEAL_DEFERRAL_TIMEOUT,
+        );
+        let mut storage = share_storage();
+        let dkg = Arc::new(TimelockDKGFixture::run(&TimelockConfig {
+            threshold: 2,
+            total_validators: 4,
+        }));
+
+        // A stub DKG manager that completes immediately with the aggregated transcript
+        let (session, _rpc_msg_rx, _close_rx) = stub_session();
+        let (transcript_tx, transcript_rx) = oneshot::channel();
+        let session_dkg = dkg.clone();
+        sessions.insert(1000, CONFIG, session, transcript_rx, move |transcript| {
+            extract_timelock_share(
+                &session_dkg.session_metadata,
+                &transcript,
+                0,
+                &session_dkg.private_keys[0],
+            )
+        });
+        let transcript = dkg.transcript.clone();
+        tokio::spawn(async move { transcript_tx.send(transcript) });
+
+        let completion = completion_rx.next().await.unwrap();
+        let (interval, share) = sessions.on_completion(completion).unwrap();
+        storage.store(interval, &share).unwrap();
+
+        // The stored share is the one our key share for the reveal is derived from
+        let stored = storage.retrieve(1000).unwrap().unwrap();
+        assert_eq!(stored.as_slice(), dkg.share(0).as_slice());
+        let key_share = derive_timelock_share(
+            &stored,
+            dkg.addrs[0],
+            &dkg.private_keys[0],
+            1000,
+            ChainId::new(1),
+        )
+        .unwrap();
+        assert!(dkg.is_key_share_valid(0, &key_share.share, &compute_timelock_identity(1000, 1)));
+        assert!(sessions.remove(1000).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_close_all() {
+        let (mut sessions, _completion_rx) = TimelockSessions::new(
+            MAX_TIMELOCK_SESSIONS,
+            MAX_TIMELOCK_SESSIONS,
+            REVEAL_DEFERRAL_TIMEOUT,
+        );
+        let mut managers = vec![];
+        for interval in 1000..1003 {
+            let (session, rpc_msg_rx, close_rx) = stub_session();
+            let (transcript_tx, transcript_rx) = oneshot::channel::<Vec<u8>>();
+            sessions.insert(interval, CONFIG, session, transcript_rx, |transcript| {
+                Ok(Zeroizing::new(transcript))
+            });
+            managers.push((transcript_tx, rpc_msg_rx, close_rx));
+        }
+
+        // Stub managers that acknowledge the close, except for the last one
+        let acked = managers
+            .into_iter()
+            .enumerate()
+            .map(|(i, (_transcript_tx, _rpc_msg_rx, close_rx))| {
+                tokio::spawn(async move {
+                    let ack_tx = close_rx.await.unwrap();
+                    if i < 2 {
+                        ack_tx.send(()).unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        sessions.close_all(Duration::from_millis(100)).await;
+        assert!(sessions.is_empty());
+        // None of them completed, so they restart in the next epoch
+        assert_eq!(sessions.queued_intervals(), vec![1000, 1001, 1002]);
+        for handle in acked {
+            handle.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oldest_session_evicted() {
+        let (mut sessions, _completion_rx) = TimelockSessions::new(2, 2, REVEAL_DEFERRAL_TIMEOUT);
+        let mut close_rxs = vec![];
+        let mut transcript_txs = vec![];
+        for interval in [1000, 1001, 1002] {
+            let (session, _rpc_msg_rx, close_rx) = stub_session();
+            let (transcript_tx, transcript_rx) = oneshot::channel::<Vec<u8>>();
+            sessions.insert(interval, CONFIG, session, transcript_rx, |transcript| {
+                Ok(Zeroizing::new(transcript))
+            });
+            close_rxs.push(close_rx);
+            transcript_txs.push(transcript_tx);
+        }
+
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions.remove(1000).is_none());
+        // The evicted session's manager is told to stop
+        assert!(close_rxs.remove(0).await.is_err());
+        assert!(sessions.remove(1001).is_some());
+        assert!(sessions.remove(1002).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failed_session_is_removed() {
+        let (mut sessions, mut completion_rx) = TimelockSessions::new(
+            MAX_TIMELOCK_SESSIONS,
+            MAX_TIMELOCK_SESSIONS,
+            REVEAL_DEFERRAL_TIMEOUT,
+        );
+
+        // A stub DKG manager that stops before aggregating
+        let (session, _rpc_msg_rx, close_rx) = stub_session();
+        let (transcript_tx, transcript_rx) = oneshot::channel::<Vec<u8>>();
+        sessions.insert(1000, CONFIG, session, transcript_rx, |transcript| {
+            Ok(Zeroizing::new(transcript))
+        });
+        drop(transcript_tx);
+
+        let completion = completion_rx.next().await.unwrap();
+        assert_eq!(completion.interval, 1000);
+        assert!(sessions.on_completion(completion).is_none());
+        assert!(sessions.remove(1000).is_none());
+        // The manager is told to stop
+        assert!(close_rx.await.is_err());
+
+        // Share extraction failures are reported the same way
+        let (session, _rpc_msg_rx, _close_rx) = stub_session();
+        let (transcript_tx, transcript_rx) = oneshot::channel::<Vec<u8>>();
+        sessions.insert(1001, CONFIG, session, transcript_rx, |_| {
+            Err(anyhow!("bad share"))
+        });
+        transcript_tx.send(vec![]).unwrap();
+
+        let completion = completion_rx.next().await.unwrap();
+        assert!(sessions.on_completion(completion).is_none());
+        assert!(sessions.remove(1001).is_none());
+    }
+
+    /// Scrapes the metrics registry for the count of key-gen sessions with `result`.
+    fn scrape_key_gen_sessions(result: &str) -> f64 {
+        aptos_metrics_core::gather()
+            .iter()
+            .filter(|family| family.get_name() == "aptos_dkg_timelock_key_gen_sessions")
+            .flat_map(|family| family.get_metric())
+            .filter(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.get_name() == "result" && label.get_value() == result)
+            })
+            .map(|metric| metric.get_counter().get_value())
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_key_gen_metrics() {
+        // Other tests may move the counters concurrently, so only lower bounds are checked.
+        let before: Vec<f64> = ["started", "completed", "failed"]
+            .into_iter()
+            .map(scrape_key_gen_sessions)
+            .collect();
+
+        let (mut sessions, mut completion_rx) =
+            TimelockSessions::new(MAX_TIMELOCK_SESSIONS, 2, REVEAL_DEFERRAL_TIMEOUT);
+        let (transcript_tx, _rpc_msg_rx, _close_rx) = start_stub_session(&mut sessions, 1000);
+        transcript_tx.send(vec![1]).unwrap();
+        let completion = completion_rx.next().await.unwrap();
+        assert!(sessions.on_completion(completion).is_some());
+
+        let (transcript_tx, _rpc_msg_rx, _close_rx) = start_stub_session(&mut sessions, 1001);
+        drop(transcript_tx);
+        let completion = completion_rx.next().await.unwrap();
+        assert!(sessions.on_completion(completion).is_none());
+
+        for (result, (before, moved_by)) in ["started", "completed", "failed"]
+            .into_iter()
+            .zip(before.into_iter().zip([2.0, 1.0, 1.0]))
+        {
+            assert!(
+                scrape_key_gen_sessions(result) >= before + moved_by,
+                "{} sessions counter did not move",
+                result
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sessions_beyond_limit_are_queued() {
+        let (mut sessions, mut completion_rx) =
+            TimelockSessions::new(MAX_TIMELOCK_SESSIONS, 2, REVEAL_DEFERRAL_TIMEOUT);
+        let mut managers = BTreeMap::new();
+
+        for interval in 1..=5 {
+            if sessions.admit(interval, CONFIG).is_some() {
+                managers.insert(interval, start_stub_session(&mut sessions, interval));
+            }
+        }
+        assert_eq!(sessions.num_running(), 2);
+        assert_eq!(sessions.queued_intervals(), vec![3, 4, 5]);
+        assert!(sessions.next_queued(|_| false).is_none());
+
+        // A completed session frees its slot but stays tracked
+        let (transcript_tx, _, _) = managers.remove(&1).unwrap();
+        transcript_tx.send(vec![1]).unwrap();
+        let completion = completion_rx.next().await.unwrap();
+        assert!(sessions.on_completion(completion).is_some());
+        assert_eq!(sessions.len(), 2);
+        let (interval, queued_config) = sessions.next_queued(|_| false).unwrap();
+        assert_eq!((interval, queued_config), (3, CONFIG));
+        managers.insert(interval, start_stub_session(&mut sessions, interval));
+        assert!(sessions.next_queued(|_| false).is_none());
+
+        // Intervals whose reveal was requested are dropped from the queue
+        assert!(sessions.next_queued(|interval| interval <= 4).is_none());
+        assert_eq!(sessions.queued_intervals(), vec![5]);
+
+        // A closed session frees its slot
+        assert!(sessions.remove(2).is_some());
+        assert_eq!(sessions.next_queued(|_| false).unwrap().0, 5);
+        assert!(sessions.queued_intervals().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deferred_reveal_expires() {
+        let (mut sessions, _completion_rx) =
+            TimelockSessions::new(MAX_TIMELOCK_SESSIONS, 1, REVEAL_DEFERRAL_TIMEOUT);
+        assert!(sessions.admit(1, CONFIG).is_some());
+        let _manager = start_stub_session(&mut sessions, 1);
+        assert!(sessions.admit(2, CONFIG).is_none());
+
+        let now = Instant::now();
+        assert!(sessions.defer_reveal(1, now));
+        assert!(sessions.defer_reveal(2, now));
+        // A queued interval whose reveal is deferred stays queued
+        assert!(sessions.next_queued(|_| true).is_none());
+        assert_eq!(sessions.queued_intervals(), vec![2]);
+
+        assert!(sessions
+            .expire_deferred_reveals(now + REVEAL_DEFERRAL_TIMEOUT - Duration::from_secs(1))
+            .is_empty());
+        assert_eq!(
+            sessions.expire_deferred_reveals(now + REVEAL_DEFERRAL_TIMEOUT),
+            vec![1, 2]
+        );
+        assert!(sessions.is_empty());
+        assert!(sessions.queued_intervals().is_empty());
+        assert!(!sessions.take_deferred_reveal(1));
+    }
+}
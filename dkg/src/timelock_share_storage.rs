@@ -0,0 +1,738 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Persistent storage for this validator's timelock DKG secret shares, so that a share survives
+//! a restart between key generation and reveal.
+//!
+//! Shares are encrypted at rest with a key derived from the validator's consensus key: a share
+//! read from disk would otherwise reveal the decryption key for its interval early. An index of
+//! the stored intervals and of the consensus public key they are wrapped under is persisted next
+//! to them, so that they can be re-wrapped when the consensus key is rotated, even across a
+//! restart.
+
+use crate::counters::{TIMELOCK_SHARES_CACHED, TIMELOCK_SHARES_PERSISTED};
+use aes_gcm::{
+    aead::{Aead, Payload},
+    Aes256Gcm, Key, KeyInit, Nonce,
+};
+use anyhow::{anyhow, bail, ensure, Result};
+use aptos_crypto::{bls12381, hkdf::Hkdf, HashValue};
+use aptos_secure_storage::{from_base64, to_base64, Error as StorageError, KVStorage};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha3::Sha3_256;
+use std::collections::{BTreeMap, BTreeSet};
+use zeroize::Zeroizing;
+
+/// Prefix of the secure-storage key under which the share for an interval is stored.
+const TIMELOCK_SHARE_KEY_PREFIX: &str = "timelock_share_";
+
+/// Secure-storage key under which the index of the stored shares is persisted.
+const TIMELOCK_SHARE_INDEX_KEY: &str = "timelock_share_index";
+
+/// HKDF salt for deriving the share wrapping key from the consensus secret key.
+const WRAPPING_KEY_DERIVATION_SALT: &[u8] = b"APTOS_TIMELOCK_SHARE_WRAPPING_KEY_SALT";
+
+/// HKDF info for deriving the share wrapping key from the consensus secret key.
+const WRAPPING_KEY_DERIVATION_INFO: &[u8] = b"APTOS_TIMELOCK_SHARE_WRAPPING_KEY";
+
+/// Domain separator for the public identifier of a wrapping key.
+const WRAPPING_KEY_ID_DST: &[u8] = b"APTOS_TIMELOCK_SHARE_WRAPPING_KEY_ID";
+
+const WRAPPING_KEY_NUM_BYTES: usize = 32;
+
+const WRAPPING_NONCE_NUM_BYTES: usize = 12;
+
+/// Default number of intervals a share is kept after its interval has been revealed.
+pub const DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS: u64 = 2;
+
+/// Default number of shares kept in memory; older ones are only kept in secure storage.
+pub const DEFAULT_MAX_CACHED_TIMELOCK_SHARES: usize = 8;
+
+/// AES-256-GCM key that timelock shares are wrapped with before being persisted, derived from
+/// the validator's consensus secret key with HKDF-SHA3-256.
+pub struct TimelockShareWrappingKey {
+    key: Zeroizing<Vec<u8>>,
+    // Identifies the key in stored shares, without revealing it
+    id: HashValue,
+    // Public key of the consensus key this key is derived from
+    consensus_pk: bls12381::PublicKey,
+}
+
+impl TimelockShareWrappingKey {
+    pub fn derive(consensus_sk: &bls12381::PrivateKey) -> Result<Self> {
+        let ikm = Zeroizing::new(consensus_sk.to_bytes());
+        let key = Hkdf::<Sha3_256>::extract_then_expand(
+            Some(WRAPPING_KEY_DERIVATION_SALT),
+            ikm.as_slice(),
+            Some(WRAPPING_KEY_DERIVATION_INFO),
+            WRAPPING_KEY_NUM_BYTES,
+        )
+        .map(Zeroizing::new)
+        .map_err(|e| anyhow!("Timelock share wrapping key derivation failed: {}", e))?;
+        let id = HashValue::sha3_256_of(&[WRAPPING_KEY_ID_DST, key.as_slice()].concat());
+        Ok(Self {
+            key,
+            id,
+            consensus_pk: bls12381::PublicKey::from(consensus_sk),
+        })
+    }
+
+    pub fn id(&self) -> HashValue {
+        self.id
+    }
+
+    pub fn consensus_pk(&self) -> &bls12381::PublicKey {
+        &self.consensus_pk
+    }
+
+    /// Encrypts the share for `interval`, authenticating the interval as associated data so that
+    /// a share cannot be passed off as another interval's.
+    fn wrap(&self, interval: u64, share: &[u8]) -> Result<StoredTimelockShare> {
+        let mut nonce = [0u8; WRAPPING_NONCE_NUM_BYTES];
+        thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), Payload {
+                msg: share,
+                aad: &interval.to_le_bytes(),
+            })
+            .map_err(|e| anyhow!("Failed to encrypt timelock share: {}", e))?;
+        Ok(StoredTimelockShare {
+            key_id: self.id,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+
+    fn unwrap(&self, interval: u64, stored: &StoredTimelockShare) -> Result<Zeroizing<Vec<u8>>> {
+        ensure!(
+            stored.key_id == self.id,
+            "Stored timelock share for interval {} is wrapped with a different key ({}); was the consensus key rotated?",
+            interval,
+            stored.key_id
+        );
+        ensure!(
+            stored.nonce.len() == WRAPPING_NONCE_NUM_BYTES,
+            "Stored timelock share for interval {} has a malformed nonce",
+            interval
+        );
+        self.cipher()
+            .decrypt(Nonce::from_slice(&stored.nonce), Payload {
+                msg: &stored.ciphertext,
+                aad: &interval.to_le_bytes(),
+            })
+            .map(Zeroizing::new)
+            .map_err(|_| {
+                anyhow!(
+                    "Stored timelock share for interval {} failed authentication: it is corrupted or belongs to another interval",
+                    interval
+                )
+            })
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key))
+    }
+}
+
+/// A share as persisted: encrypted with the wrapping key identified by `key_id`.
+#[derive(Deserialize, Serialize)]
+struct StoredTimelockShare {
+    key_id: HashValue,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    nonce: Vec<u8>,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    ciphertext: Vec<u8>,
+}
+
+/// The intervals with a share in secure storage, and the consensus key their wrapping key is
+/// derived from.
+#[derive(Default, Deserialize, Serialize)]
+struct TimelockShareIndex {
+    consensus_pk: Option<bls12381::PublicKey>,
+    intervals: BTreeSet<u64>,
+}
+
+/// Stores timelock secret shares in a secure-storage backend (the same backend as
+/// `PersistentSafetyStorage`), keyed by interval, with an in-memory cache of the most recent
+/// `max_cached` shares.
+///
+/// Secure storage has no delete operation, so deleted shares are overwritten with a tombstone.
+///
+/// Shares can only be stored and read from secure storage once a wrapping key is set (see
+/// `set_wrapping_key`).
+pub struct TimelockShareStorage<S> {
+    storage: S,
+    wrapping_key: Option<TimelockShareWrappingKey>,
+    retention_intervals: u64,
+    max_cached: usize,
+    cache: BTreeMap<u64, Zeroizing<Vec<u8>>>,
+    // Mirror of the persisted index, loaded when the wrapping key is set
+    index: TimelockShareIndex,
+}
+
+impl<S: KVStorage> TimelockShareStorage<S> {
+    /// Shares for interval `i` are deleted once interval `i + retention_intervals` is revealed.
+    pub fn new(storage: S, retention_intervals: u64, max_cached: usize) -> Self {
+        Self {
+            storage,
+            wrapping_key: None,
+            retention_intervals,
+            max_cached,
+            cache: BTreeMap::new(),
+            index: TimelockShareIndex::default(),
+        }
+    }
+
+    /// Number of shares held in memory.
+    pub fn num_cached(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Number of shares known to be in secure storage.
+    pub fn num_persisted(&self) -> usize {
+        self.index.intervals.len()
+    }
+
+    fn update_metrics(&self) {
+        TIMELOCK_SHARES_CACHED.set(self.num_cached() as i64);
+        TIMELOCK_SHARES_PERSISTED.set(self.num_persisted() as i64);
+    }
+
+    fn key(interval: u64) -> String {
+        format!("{}{}", TIMELOCK_SHARE_KEY_PREFIX, interval)
+    }
+
+    fn wrapping_key(&self) -> Result<&TimelockShareWrappingKey> {
+        self.wrapping_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("No timelock share wrapping key set"))
+    }
+
+    /// Sets the key shares are wrapped with, e.g., at epoch start, and loads the index of the
+    /// stored shares.
+    ///
+    /// If the stored shares are wrapped under a different consensus key (it was rotated, maybe
+    /// while the node was down), they are re-wrapped with the new key. The previous key is the one
+    /// set before in this process, or else is derived through `previous_key` from the consensus
+    /// public key recorded in the index. Shares that fail to re-wrap are reported in the returned
+    /// error; the new key is set regardless.
+    pub fn set_wrapping_key(
+        &mut self,
+        key: TimelockShareWrappingKey,
+        previous_key: impl FnOnce(&bls12381::PublicKey) -> Result<TimelockShareWrappingKey>,
+    ) -> Result<()> {
+        let current_key = self.wrapping_key.take();
+        self.index = match self.read_index() {
+            Ok(index) => index,
+            Err(e) => {
+                self.wrapping_key = Some(key);
+                return Err(e);
+            },
+        };
+        let rewrapped = match self.index.consensus_pk.clone() {
+            Some(old_pk) if old_pk != key.consensus_pk && !self.index.intervals.is_empty() => {
+                let old_key = match current_key {
+                    Some(current_key) if current_key.consensus_pk == old_pk => Ok(current_key),
+                    _ => previous_key(&old_pk).map_err(|e| {
+                        anyhow!(
+                            "No previous key to re-wrap the timelock shares for intervals {:?} with: {}",
+                            self.index.intervals,
+                            e
+                        )
+                    }),
+                };
+                old_key.and_then(|old_key| self.rewrap(&old_key, &key))
+            },
+            _ => Ok(()),
+        };
+        self.index.consensus_pk = Some(key.consensus_pk.clone());
+        self.wrapping_key = Some(key);
+        self.put_index()?;
+        self.update_metrics();
+        rewrapped
+    }
+
+    /// Re-wraps the shares in the index, wrapped with `old_key`, with `new_key`.
+    fn rewrap(
+        &mut self,
+        old_key: &TimelockShareWrappingKey,
+        new_key: &TimelockShareWrappingKey,
+    ) -> Result<()> {
+        let mut failed = vec![];
+        for interval in self.index.intervals.clone() {
+            // Already re-wrapped, e.g., before a crash that left the index unchanged
+            if self.read(interval, new_key).is_ok() {
+                continue;
+            }
+            let rewrapped = self
+                .read(interval, old_key)
+                .and_then(|share| share.ok_or_else(|| anyhow!("share not found")))
+                .and_then(|share| new_key.wrap(interval, &share))
+                .and_then(|stored| self.put(interval, &stored));
+            if let Err(e) = rewrapped {
+                failed.push(format!("{}: {}", interval, e));
+            }
+        }
+        if !failed.is_empty() {
+            bail!(
+                "Failed to re-wrap timelock shares with the new key: {}",
+                failed.join("; ")
+            );
+        }
+        Ok(())
+    }
+
+    fn read_index(&self) -> Result<TimelockShareIndex> {
+        match self
+            .storage
+            .get::<TimelockShareIndex>(TIMELOCK_SHARE_INDEX_KEY)
+        {
+            Ok(response) => Ok(response.value),
+            Err(StorageError::KeyNotSet(_)) => Ok(TimelockShareIndex::default()),
+            Err(e) => Err(anyhow!("Failed to read timelock share index: {}", e)),
+        }
+    }
+
+    fn put_index(&mut self) -> Result<()> {
+        self.storage
+            .set(TIMELOCK_SHARE_INDEX_KEY, &self.index)
+            .map_err(|e| anyhow!("Failed to persist timelock share index: {}", e))
+    }
+
+    /// Reads the share for `interval` from secure storage, or `None` if it was never stored or
+    /// has been deleted.
+    fn read(
+        &self,
+        interval: u64,
+        key: &TimelockShareWrappingKey,
+    ) -> Result<Option<Zeroizing<Vec<u8>>>> {
+        let stored = match self
+            .storage
+            .get::<Option<StoredTimelockShare>>(&Self::key(interval))
+        {
+            Ok(response) => response.value,
+            Err(StorageError::KeyNotSet(_)) => None,
+            Err(e) => {
+                return Err(anyhow!(
+                    "Failed to read timelock share for interval {}: {}",
+                    interval,
+                    e
+                ))
+            },
+        };
+        stored
+            .map(|stored| key.unwrap(interval, &stored))
+            .transpose()
+    }
+
+    fn put(&mut self, interval: u64, stored: &StoredTimelockShare) -> Result<()> {
+        self.storage
+            .set(&Self::key(interval), Some(stored))
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to persist timelock share for interval {}: {}",
+                    interval,
+                    e
+                )
+            })
+    }
+
+    /// Persists and caches the share for `interval`, overwriting any previous one.
+    ///
+    /// If the cache is full, the oldest interval's share is evicted from memory; it remains in
+    /// secure storage.
+    pub fn store(&mut self, interval: u64, share: &[u8]) -> Result<()> {
+        let stored = self.wrapping_key()?.wrap(interval, share)?;
+        self.put(interval, &stored)?;
+        if self.index.intervals.insert(interval) {
+            self.put_index()?;
+        }
+
+        self.cache.insert(interval, Zeroizing::new(share.to_vec()));
+        while self.cache.len() > self.max_cached {
+            self.cache.pop_first();
+        }
+        self.update_metrics();
+        Ok(())
+    }
+
+    /// Returns the share for `interval`, or `None` if it was never stored or has been deleted.
+    ///
+    /// Looks up the cache first, then secure storage (e.g., after a restart or an eviction).
+    /// Fails if the backend is unavailable, or the stored share fails authentication (it was
+    /// tampered with, or is another interval's) or was wrapped with a different key.
+    pub fn retrieve(&mut self, interval: u64) -> Result<Option<Zeroizing<Vec<u8>>>> {
+        if let Some(share) = self.cache.get(&interval) {
+            return Ok(Some(share.clone()));
+        }
+
+        let Some(share) = self.read(interval, self.wrapping_key()?)? else {
+            return Ok(None);
+        };
+        // Shares stored before the index existed
+        if self.index.intervals.insert(interval) {
+            self.put_index()?;
+            self.update_metrics();
+        }
+        Ok(Some(share))
+    }
+
+    /// Deletes the share for `interval` from memory and secure storage, if any.
+    pub fn delete(&mut self, interval: u64) -> Result<()> {
+        self.cache.remove(&interval);
+        self.storage
+            .set(&Self::key(interval), None::<StoredTimelockShare>)
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to delete timelock share for interval {}: {}",
+                    interval,
+                    e
+                )
+            })?;
+        if self.index.intervals.remove(&interval) {
+            self.put_index()?;
+        }
+        self.update_metrics();
+        Ok(())
+    }
+
+    /// Deletes the shares whose retention period has ended with the reveal of
+    /// `revealed_interval`: the share for `revealed_interval - retention_intervals`, and any
+    /// older share in the index.
+    ///
+    /// Returns the deleted intervals.
+    pub fn prune_after_reveal(&mut self, revealed_interval: u64) -> Result<Vec<u64>> {
+        let Some(expired) = revealed_interval.checked_sub(self.retention_intervals) else {
+            return Ok(vec![]);
+        };
+        let mut intervals: BTreeSet<u64> = self
+            .cache
+            .keys()
+            .chain(self.index.intervals.iter())
+            .copied()
+            .filter(|interval| *interval < expired)
+            .collect();
+        intervals.insert(expired);
+
+        for interval in &intervals {
+            self.delete(*interval)?;
+        }
+        Ok(intervals.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{epoch_manager::derive_timelock_share, test_utils::TimelockDKGFixture};
+    use aptos_crypto::Uniform;
+    use aptos_dkg::ibe::compute_timelock_identity;
+    use aptos_secure_storage::{OnDiskStorage, Storage};
+    use aptos_temppath::TempPath;
+    use aptos_types::{chain_id::ChainId, dkg::TimelockConfig};
+    use serde_json::Value;
+    use std::{collections::HashMap, fs};
+
+    /// The same key on every call, standing in for an unchanged consensus key.
+    fn test_wrapping_key() -> TimelockShareWrappingKey {
+        TimelockShareWrappingKey::derive(&bls12381::PrivateKey::generate_for_testing()).unwrap()
+    }
+
+    /// Stands in for a previous consensus key that is no longer in secure storage.
+    fn no_previous_key(_: &bls12381::PublicKey) -> Result<TimelockShareWrappingKey> {
+        bail!("previous consensus key not found")
+    }
+
+    fn on_disk_storage_without_key(temp_path: &TempPath) -> TimelockShareStorage<Storage> {
+        TimelockShareStorage::new(
+            Storage::from(OnDiskStorage::new(temp_path.path().to_path_buf())),
+            DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS,
+            DEFAULT_MAX_CACHED_TIMELOCK_SHARES,
+        )
+    }
+
+    fn on_disk_storage_with(
+        temp_path: &TempPath,
+        retention_intervals: u64,
+        max_cached: usize,
+    ) -> TimelockShareStorage<Storage> {
+        let mut storage = TimelockShareStorage::new(
+            Storage::from(OnDiskStorage::new(temp_path.path().to_path_buf())),
+            retention_intervals,
+            max_cached,
+        );
+        storage
+            .set_wrapping_key(test_wrapping_key(), no_previous_key)
+            .unwrap();
+        storage
+    }
+
+    fn on_disk_storage(temp_path: &TempPath) -> TimelockShareStorage<Storage> {
+        on_disk_storage_with(
+            temp_path,
+            DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS,
+            DEFAULT_MAX_CACHED_TIMELOCK_SHARES,
+        )
+    }
+
+    /// Applies `f` to the stored entry for `interval` directly in the storage file.
+    fn edit_stored_share(temp_path: &TempPath, f: impl FnOnce(&mut HashMap<String, Value>)) {
+        let contents = fs::read_to_string(temp_path.path()).unwrap();
+        let mut data: HashMap<String, Value> = serde_json::from_str(&contents).unwrap();
+        f(&mut data);
+        fs::write(temp_path.path(), serde_json::to_string(&data).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_share_survives_restart() {
+        let temp_path = TempPath::new();
+        let dkg = TimelockDKGFixture::run(&TimelockConfig {
+            threshold: 2,
+            total_validators: 4,
+        });
+        let share = dkg.share(0);
+
+        let mut storage = on_disk_storage(&temp_path);
+        assert!(storage.retrieve(1000).unwrap().is_none());
+        storage.store(1000, &share).unwrap();
+        drop(storage);
+
+        // A fresh instance (e.g., after a restart) reads the share back from disk...
+        let mut storage = on_disk_storage(&temp_path);
+        let retrieved = storage.retrieve(1000).unwrap().unwrap();
+        assert_eq!(retrieved.as_slice(), share.as_slice());
+
+        // ...and can reveal with it
+        let key_share = derive_timelock_share(
+            &retrieved,
+            dkg.addrs[0],
+            &dkg.private_keys[0],
+            1000,
+            ChainId::new(1),
+        )
+        .unwrap();
+        assert!(dkg.is_key_share_valid(0, &key_share.share, &compute_timelock_identity(1000, 1)));
+    }
+
+    #[test]
+    fn test_prune_after_reveal() {
+        let temp_path = TempPath::new();
+        let mut storage = on_disk_storage(&temp_path);
+        for interval in 0..4 {
+            storage.store(interval, &[interval as u8; 32]).unwrap();
+        }
+
+        assert!(storage.prune_after_reveal(1).unwrap().is_empty());
+        assert_eq!(storage.prune_after_reveal(3).unwrap(), vec![0, 1]);
+        for interval in [0, 1] {
+            assert!(storage.retrieve(interval).unwrap().is_none());
+        }
+        for interval in [2, 3] {
+            assert!(storage.retrieve(interval).unwrap().is_some());
+        }
+    }
+
+    #[test]
+    fn test_shares_dropped_after_grace_period() {
+        let temp_path = TempPath::new();
+        let mut storage = on_disk_storage_with(&temp_path, 2, 3);
+
+        // Key-gen runs one interval ahead of the reveal
+        storage.store(0, &[0u8; 32]).unwrap();
+        for interval in 0..10u64 {
+            storage
+                .store(interval + 1, &[interval as u8 + 1; 32])
+                .unwrap();
+            storage.prune_after_reveal(interval).unwrap();
+            // The revealed interval, the one before it and the next one
+            assert!(storage.num_cached() <= 3);
+            assert!(storage.num_persisted() <= 3);
+        }
+
+        // Only shares within the grace period are left on disk
+        let mut storage = on_disk_storage(&temp_path);
+        for interval in 0..=7 {
+            assert!(storage.retrieve(interval).unwrap().is_none());
+        }
+        for interval in 8..=10 {
+            assert!(storage.retrieve(interval).unwrap().is_some());
+        }
+    }
+
+    #[test]
+    fn test_cache_evicts_to_storage() {
+        let temp_path = TempPath::new();
+        let mut storage =
+            on_disk_storage_with(&temp_path, DEFAULT_TIMELOCK_SHARE_RETENTION_INTERVALS, 2);
+        for interval in 0..4 {
+            storage.store(interval, &[interval as u8; 32]).unwrap();
+        }
+        assert_eq!(storage.num_cached(), 2);
+        assert_eq!(storage.num_persisted(), 4);
+
+        // Evicted shares are still read back from secure storage
+        assert_eq!(storage.retrieve(0).unwrap().unwrap().as_slice(), &[0u8; 32]);
+    }
+
+    #[test]
+    fn test_share_encrypted_at_rest() {
+        let temp_path = TempPath::new();
+        let mut storage = on_disk_storage(&temp_path);
+        storage.store(1000, &[7u8; 32]).unwrap();
+
+        let plaintext = to_base64(&[7u8; 32], serde_json::value::Serializer).unwrap();
+        let contents = fs::read_to_string(temp_path.path()).unwrap();
+        assert!(!contents.contains(plaintext.as_str().unwrap()));
+    }
+
+    #[test]
+    fn test_corruption_detected() {
+        let temp_path = TempPath::new();
+        let mut storage = on_disk_storage(&temp_path);
+        storage.store(1000, &[7u8; 32]).unwrap();
+
+        // Flip bits of the first ciphertext byte directly in the file
+        edit_stored_share(&temp_path, |data| {
+            let ciphertext = data
+                .get_mut("timelock_share_1000")
+                .and_then(|entry| entry.pointer_mut("/value/ciphertext"))
+                .unwrap();
+            let mut encoded = ciphertext.as_str().unwrap().to_string();
+            let first = if encoded.starts_with('A') { "B" } else { "A" };
+            encoded.replace_range(0..1, first);
+            *ciphertext = Value::String(encoded);
+        });
+
+        // Read through a fresh instance, so the share is not served from the cache
+        let mut storage = on_disk_storage(&temp_path);
+        let err = storage.retrieve(1000).unwrap_err();
+        assert!(err.to_string().contains("failed authentication"));
+    }
+
+    #[test]
+    fn test_associated_data_mismatch_detected() {
+        let temp_path = TempPath::new();
+        let mut storage = on_disk_storage(&temp_path);
+        storage.store(1000, &[7u8; 32]).unwrap();
+
+        // Pass the share for interval 1000 off as the one for 1001
+        edit_stored_share(&temp_path, |data| {
+            let entry = data["timelock_share_1000"].clone();
+            data.insert("timelock_share_1001".to_string(), entry);
+        });
+
+        let mut storage = on_disk_storage(&temp_path);
+        assert_eq!(
+            storage.retrieve(1000).unwrap().unwrap().as_slice(),
+            &[7u8; 32]
+        );
+        assert!(storage.retrieve(1001).is_err());
+    }
+
+    #[test]
+    fn test_shares_rewrapped_on_key_rotation() {
+        let temp_path = TempPath::new();
+        let mut storage = on_disk_storage_without_key(&temp_path);
+        // Nothing is persisted without a key
+        assert!(storage.store(1000, &[7u8; 32]).is_err());
+
+        storage
+            .set_wrapping_key(test_wrapping_key(), no_previous_key)
+            .unwrap();
+        storage.store(1000, &[7u8; 32]).unwrap();
+
+        let new_sk = bls12381::PrivateKey::generate(&mut thread_rng());
+        storage
+            .set_wrapping_key(
+                TimelockShareWrappingKey::derive(&new_sk).unwrap(),
+                no_previous_key,
+            )
+            .unwrap();
+
+        // Only the new key can read the share now
+        let mut new_key_storage = on_disk_storage_without_key(&temp_path);
+        new_key_storage
+            .set_wrapping_key(
+                TimelockShareWrappingKey::derive(&new_sk).unwrap(),
+                no_previous_key,
+            )
+            .unwrap();
+        assert_eq!(
+            new_key_storage.retrieve(1000).unwrap().unwrap().as_slice(),
+            &[7u8; 32]
+        );
+        let mut old_key_storage = on_disk_storage_without_key(&temp_path);
+        assert!(old_key_storage
+            .set_wrapping_key(test_wrapping_key(), no_previous_key)
+            .is_err());
+        assert!(old_key_storage.retrieve(1000).is_err());
+    }
+
+    #[test]
+    fn test_shares_rewrapped_on_key_rotation_across_restart() {
+        let temp_path = TempPath::new();
+        let old_sk = bls12381::PrivateKey::generate(&mut thread_rng());
+        let mut storage = on_disk_storage_without_key(&temp_path);
+        storage
+            .set_wrapping_key(
+                TimelockShareWrappingKey::derive(&old_sk).unwrap(),
+                no_previous_key,
+            )
+            .unwrap();
+        storage.store(1000, &[7u8; 32]).unwrap();
+        storage.store(1001, &[8u8; 32]).unwrap();
+        drop(storage);
+
+        // The consensus key is rotated while the node is down
+        let new_sk = bls12381::PrivateKey::generate(&mut thread_rng());
+        let old_pk = bls12381::PublicKey::from(&old_sk);
+        let mut storage = on_disk_storage_without_key(&temp_path);
+        assert_eq!(storage.num_persisted(), 0);
+        storage
+            .set_wrapping_key(TimelockShareWrappingKey::derive(&new_sk).unwrap(), |pk| {
+                ensure!(*pk == old_pk, "unexpected previous consensus key");
+                TimelockShareWrappingKey::derive(&old_sk)
+            })
+            .unwrap();
+        assert_eq!(storage.num_persisted(), 2);
+        drop(storage);
+
+        // Once re-wrapped, the shares no longer need the previous key
+        let mut storage = on_disk_storage_without_key(&temp_path);
+        storage
+            .set_wrapping_key(
+                TimelockShareWrappingKey::derive(&new_sk).unwrap(),
+                no_previous_key,
+            )
+            .unwrap();
+        assert_eq!(
+            storage.retrieve(1000).unwrap().unwrap().as_slice(),
+            &[7u8; 32]
+        );
+        assert_eq!(
+            storage.retrieve(1001).unwrap().unwrap().as_slice(),
+            &[8u8; 32]
+        );
+    }
+
+    #[test]
+    fn test_rotation_across_restart_without_previous_key() {
+        let temp_path = TempPath::new();
+        on_disk_storage(&temp_path).store(1000, &[7u8; 32]).unwrap();
+
+        let new_sk = bls12381::PrivateKey::generate(&mut thread_rng());
+        let mut storage = on_disk_storage_without_key(&temp_path);
+        let err = storage
+            .set_wrapping_key(
+                TimelockShareWrappingKey::derive(&new_sk).unwrap(),
+                no_previous_key,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("{1000}"));
+        // The new key is set regardless, for the shares to come
+        storage.store(1001, &[8u8; 32]).unwrap();
+        assert!(storage.retrieve(1000).is_err());
+    }
+}
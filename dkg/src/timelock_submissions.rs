@@ -0,0 +1,289 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Keeps this validator's timelock decryption key shares in the validator transaction pool until
+//! the chain shows them committed, re-submitting them with backoff.
+
+use crate::{
+    counters::{TIMELOCK_REVEAL_SECONDS, TIMELOCK_SHARE_SUBMISSION_RETRIES},
+    logging::{TimelockLogSchema, TimelockStage},
+};
+use aptos_logger::{error, warn};
+use aptos_types::{
+    dkg::TimelockShare,
+    validator_txn::{Topic, ValidatorTransaction},
+};
+use aptos_validator_transaction_pool::{TxnGuard, VTxnPoolState};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Maximum number of times a share is put into the pool, including the first submission.
+pub const MAX_TIMELOCK_SHARE_SUBMISSIONS: u32 = 5;
+
+/// How long to wait for the share to show up on-chain before the first re-submission.
+pub const TIMELOCK_SHARE_RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+
+/// Upper bound on the delay between two re-submissions.
+pub const TIMELOCK_SHARE_RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// How often the epoch manager checks for shares due for re-submission.
+pub const TIMELOCK_SHARE_RETRY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where timelock shares are submitted. Implemented by the validator transaction pool; the
+/// returned guard keeps the transaction in the pool until dropped.
+pub trait TimelockSharePool {
+    type Guard;
+
+    fn put_share(&self, share: TimelockShare) -> Self::Guard;
+}
+
+impl TimelockSharePool for VTxnPoolState {
+    type Guard = TxnGuard;
+
+    // Note: the pool holds one txn per topic, so a re-submitted share replaces the previous
+    // submission for its interval, while the shares of other intervals stay.
+    fn put_share(&self, share: TimelockShare) -> TxnGuard {
+        let topic = Topic::TIMELOCK_SHARE {
+            author: share.author,
+            interval: share.interval,
+        };
+        self.put(
+            topic,
+            Arc::new(ValidatorTransaction::TimelockShare(share)),
+            None,
+        )
+    }
+}
+
+struct PendingSubmission<G> {
+    share: TimelockShare,
+    _guard: G,
+    attempts: u32,
+    submitted_at: Instant,
+    next_retry: Instant,
+}
+
+/// The shares submitted and not yet seen on-chain, keyed by interval.
+pub struct TimelockShareSubmissions<P: TimelockSharePool> {
+    pool: P,
+    pending: BTreeMap<u64, PendingSubmission<P::Guard>>,
+    max_submissions: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl<P: TimelockSharePool> TimelockShareSubmissions<P> {
+    pub fn new(pool: P, max_submissions: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            pool,
+            pending: BTreeMap::new(),
+            max_submissions,
+            base_delay,
+            max_delay,
+        }
+    }
+
+    pub fn is_pending(&self, interval: u64) -> bool {
+        self.pending.contains_key(&interval)
+    }
+
+    /// Puts `share` into the pool and tracks it until `on_share_committed` is called for its
+    /// interval. Replaces any share already pending for the interval.
+    pub fn submit(&mut self, share: TimelockShare, now: Instant) {
+        let interval = share.interval;
+        let guard = self.pool.put_share(share.clone());
+        self.pending.insert(interval, PendingSubmission {
+            share,
+            _guard: guard,
+            attempts: 1,
+            submitted_at: now,
+            next_retry: now + self.base_delay,
+        });
+    }
+
+    /// Our share for `interval` is on-chain, so it no longer needs to be submitted. Returns
+    /// whether a submission was pending.
+    pub fn on_share_committed(&mut self, interval: u64) -> bool {
+        match self.pending.remove(&interval) {
+            Some(pending) => {
+                TIMELOCK_REVEAL_SECONDS.observe(pending.submitted_at.elapsed().as_secs_f64());
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Re-submits the shares whose retry delay has passed and stops tracking those that used up
+    /// all their attempts. Returns the intervals that were given up on.
+    pub fn retry_due(&mut self, now: Instant) -> Vec<u64> {
+        let mut given_up = vec![];
+        for (interval, pending) in self.pending.iter_mut() {
+            if now < pending.next_retry {
+                continue;
+            }
+            if pending.attempts >= self.max_submissions {
+                error!(
+                    "[Timelock] Share for interval {} not seen on-chain after {} submissions, giving up",
+                    interval, pending.attempts
+                );
+                given_up.push(*interval);
+                continue;
+            }
+            pending.attempts += 1;
+            TIMELOCK_SHARE_SUBMISSION_RETRIES.inc();
+            warn!(
+                TimelockLogSchema::new(TimelockStage::ShareResubmitted).interval(*interval),
+                "[Timelock] Share for interval {} not seen on-chain, re-submitting (attempt {}/{})",
+                interval,
+                pending.attempts,
+                self.max_submissions
+            );
+            // The new guard replaces the old one only after the new transaction is in the pool.
+            pending._guard = self.pool.put_share(pending.share.clone());
+            pending.next_retry =
+                now + retry_delay(self.base_delay, self.max_delay, pending.attempts);
+        }
+        for interval in &given_up {
+            self.pending.remove(interval);
+        }
+        given_up
+    }
+}
+
+/// Delay after the `attempts`-th submission: doubles each time, capped at `max_delay`.
+fn retry_delay(base_delay: Duration, max_delay: Duration, attempts: u32) -> Duration {
+    base_delay
+        .saturating_mul(1 << attempts.saturating_sub(1).min(16))
+        .min(max_delay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{bls12381, Uniform};
+    use move_core_types::account_address::AccountAddress;
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Records every share put into it, and which of them are still in the pool.
+    #[derive(Clone, Default)]
+    struct FakePool {
+        puts: Rc<RefCell<Vec<u64>>>,
+        live: Rc<RefCell<BTreeMap<u64, usize>>>,
+    }
+
+    struct FakeGuard {
+        live: Rc<RefCell<BTreeMap<u64, usize>>>,
+        interval: u64,
+    }
+
+    impl Drop for FakeGuard {
+        fn drop(&mut self) {
+            let mut live = self.live.borrow_mut();
+            let count = live.get_mut(&self.interval).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                live.remove(&self.interval);
+            }
+        }
+    }
+
+    impl TimelockSharePool for FakePool {
+        type Guard = FakeGuard;
+
+        fn put_share(&self, share: TimelockShare) -> FakeGuard {
+            self.puts.borrow_mut().push(share.interval);
+            *self.live.borrow_mut().entry(share.interval).or_default() += 1;
+            FakeGuard {
+                live: self.live.clone(),
+                interval: share.interval,
+            }
+        }
+    }
+
+    fn share(interval: u64) -> TimelockShare {
+        TimelockShare::sign(
+            AccountAddress::ONE,
+            interval,
+            vec![interval as u8; 48],
+            &bls12381::PrivateKey::generate_for_testing(),
+        )
+        .unwrap()
+    }
+
+    fn submissions(pool: &FakePool) -> TimelockShareSubmissions<FakePool> {
+        TimelockShareSubmissions::new(
+            pool.clone(),
+            3,
+            Duration::from_secs(5),
+            Duration::from_secs(8),
+        )
+    }
+
+    #[test]
+    fn test_committed_first_try() {
+        let pool = FakePool::default();
+        let mut submissions = submissions(&pool);
+        let start = Instant::now();
+
+        submissions.submit(share(7), start);
+        assert!(pool.live.borrow().contains_key(&7));
+        assert!(submissions
+            .retry_due(start + Duration::from_secs(1))
+            .is_empty());
+
+        assert!(submissions.on_share_committed(7));
+        assert!(!submissions.is_pending(7));
+        assert!(!pool.live.borrow().contains_key(&7));
+
+        assert!(submissions
+            .retry_due(start + Duration::from_secs(60))
+            .is_empty());
+        assert_eq!(*pool.puts.borrow(), vec![7]);
+    }
+
+    #[test]
+    fn test_retry_with_backoff_until_committed() {
+        let pool = FakePool::default();
+        let mut submissions = submissions(&pool);
+        let start = Instant::now();
+
+        submissions.submit(share(7), start);
+        // Retried after the base delay
+        assert!(submissions
+            .retry_due(start + Duration::from_secs(5))
+            .is_empty());
+        assert_eq!(*pool.puts.borrow(), vec![7, 7]);
+        // The old transaction was replaced, not duplicated
+        assert_eq!(pool.live.borrow().get(&7), Some(&1));
+        // The next retry waits for the doubled (capped) delay
+        submissions.retry_due(start + Duration::from_secs(12));
+        assert_eq!(pool.puts.borrow().len(), 2);
+        submissions.retry_due(start + Duration::from_secs(13));
+        assert_eq!(pool.puts.borrow().len(), 3);
+
+        assert!(submissions.on_share_committed(7));
+        assert!(pool.live.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_gives_up_after_max_submissions() {
+        let pool = FakePool::default();
+        let mut submissions = submissions(&pool);
+        let start = Instant::now();
+
+        submissions.submit(share(7), start);
+        submissions.submit(share(8), start + Duration::from_secs(100));
+        let mut given_up = vec![];
+        for secs in [5, 13, 21] {
+            given_up.extend(submissions.retry_due(start + Duration::from_secs(secs)));
+        }
+        assert_eq!(given_up, vec![7]);
+        assert_eq!(*pool.puts.borrow(), vec![7, 8, 7, 7]);
+        assert!(!submissions.is_pending(7));
+        assert!(submissions.is_pending(8));
+        assert_eq!(pool.live.borrow().keys().collect::<Vec<_>>(), vec![&8]);
+    }
+}
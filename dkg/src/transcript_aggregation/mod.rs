@@ -1,7 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{counters::DKG_STAGE_SECONDS, types::DKGTranscriptRequest, DKGMessage};
+use crate::{
+    counters::DKG_STAGE_SECONDS,
+    types::{DKGTranscriptRequest, TimelockDKGTranscript, TimelockDKGTranscriptRequest},
+    DKGMessage,
+};
 use anyhow::{anyhow, ensure, Context};
 use aptos_consensus_types::common::Author;
 use aptos_infallible::{duration_since_epoch, Mutex};
@@ -153,5 +157,38 @@ impl<S: DKGTrait> BroadcastStatus<DKGMessage> for Arc<TranscriptAggregationState
     }
 }
 
+/// Aggregates the transcripts of the timelock DKG session of an interval, whose messages are
+/// tagged with that interval.
+pub struct TimelockTranscriptAggregationState<DKG: DKGTrait> {
+    interval: u64,
+    inner: Arc<TranscriptAggregationState<DKG>>,
+}
+
+impl<DKG: DKGTrait> TimelockTranscriptAggregationState<DKG> {
+    pub fn new(interval: u64, inner: Arc<TranscriptAggregationState<DKG>>) -> Self {
+        Self { interval, inner }
+    }
+}
+
+impl<S: DKGTrait> BroadcastStatus<DKGMessage> for Arc<TimelockTranscriptAggregationState<S>> {
+    type Aggregated = S::Transcript;
+    type Message = TimelockDKGTranscriptRequest;
+    type Response = TimelockDKGTranscript;
+
+    fn add(
+        &self,
+        sender: Author,
+        response: TimelockDKGTranscript,
+    ) -> anyhow::Result<Option<Self::Aggregated>> {
+        ensure!(
+            response.interval == self.interval,
+            "[DKG] adding peer transcript failed with timelock interval mismatch: expected {}, got {}",
+            self.interval,
+            response.interval
+        );
+        self.inner.add(sender, response.transcript)
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -21,11 +21,53 @@ impl DKGTranscriptRequest {
     }
 }
 
+/// A `DKGTranscriptRequest` for the timelock DKG session of an interval.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct TimelockDKGTranscriptRequest {
+    pub interval: u64,
+    pub request: DKGTranscriptRequest,
+}
+
+impl TimelockDKGTranscriptRequest {
+    pub fn new(interval: u64, request: DKGTranscriptRequest) -> Self {
+        Self { interval, request }
+    }
+}
+
+/// A transcript from the timelock DKG session of an interval.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct TimelockDKGTranscript {
+    pub interval: u64,
+    pub transcript: DKGTranscript,
+}
+
+impl TimelockDKGTranscript {
+    pub fn new(interval: u64, transcript: DKGTranscript) -> Self {
+        Self {
+            interval,
+            transcript,
+        }
+    }
+}
+
+/// The DKG session a message belongs to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DKGSessionId {
+    /// The per-epoch randomness DKG.
+    Epoch,
+    /// The timelock DKG of an interval.
+    Timelock(u64),
+}
+
 /// The DKG network message.
+///
+/// Timelock variants are appended, so the encoding of the epoch DKG messages is unchanged.
 #[derive(Clone, Serialize, Deserialize, Debug, EnumConversion, PartialEq)]
 pub enum DKGMessage {
     TranscriptRequest(DKGTranscriptRequest),
     TranscriptResponse(DKGTranscript),
+    TimelockTranscriptRequest(TimelockDKGTranscriptRequest),
+    TimelockTranscriptResponse(TimelockDKGTranscript),
 }
 
 impl DKGMessage {
@@ -33,6 +75,22 @@ impl DKGMessage {
         match self {
             DKGMessage::TranscriptRequest(request) => request.dealer_epoch,
             DKGMessage::TranscriptResponse(response) => response.metadata.epoch,
+            DKGMessage::TimelockTranscriptRequest(request) => request.request.dealer_epoch,
+            DKGMessage::TimelockTranscriptResponse(response) => response.transcript.metadata.epoch,
+        }
+    }
+
+    pub fn session(&self) -> DKGSessionId {
+        match self {
+            DKGMessage::TranscriptRequest(_) | DKGMessage::TranscriptResponse(_) => {
+                DKGSessionId::Epoch
+            },
+            DKGMessage::TimelockTranscriptRequest(request) => {
+                DKGSessionId::Timelock(request.interval)
+            },
+            DKGMessage::TimelockTranscriptResponse(response) => {
+                DKGSessionId::Timelock(response.interval)
+            },
         }
     }
 
@@ -40,6 +98,8 @@ impl DKGMessage {
         match self {
             DKGMessage::TranscriptRequest(_) => "DKGTranscriptRequest",
             DKGMessage::TranscriptResponse(_) => "DKGTranscriptResponse",
+            DKGMessage::TimelockTranscriptRequest(_) => "TimelockDKGTranscriptRequest",
+            DKGMessage::TimelockTranscriptResponse(_) => "TimelockDKGTranscriptResponse",
         }
     }
 }
@@ -12,118 +12,147 @@
 //! 6. On-chain aggregation produces decryption key
 
 use crate::smoke_test_environment::SwarmBuilder;
-use aptos_forge::{NodeExt, SwarmExt};
+use aptos::test::CliTestFramework;
+use aptos_forge::{Node, NodeExt, Swarm, SwarmExt};
+use aptos_genesis::builder::TimelockGenesisConfig;
+use aptos_inspection_service::inspection_client::InspectionClient;
 use aptos_logger::info;
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::time::sleep;
+
+/// Size of the validator set the tests start with.
+const NUM_VALIDATORS: u64 = 4;
 
 /// Test basic timelock flow with fast interval for testing.
 ///
 /// This test:
-/// - Starts a 4-validator network
-/// - Verifies timelock is initialized at genesis
-/// - Waits for first rotation
-/// - Verifies public key is published
-/// - Waits for reveal
-/// - Verifies secret is aggregated
+/// - Starts a 4-validator network with a 5-second interval
+/// - Verifies timelock is initialized at genesis, at interval 0
+/// - Waits for the rotation to interval 1, which requests its key generation
+/// - Verifies the public key of interval 1 is published
+/// - Waits for the rotation to interval 2, which requests the reveal of interval 1
+/// - Verifies the secret of interval 1 is aggregated from the configured threshold of shares, and
+///   not from fewer
 #[tokio::test]
 async fn test_timelock_basic_flow() {
     let interval_secs = 5;
+    // Key generation and reveal each take a few intervals to land on-chain
+    let step_timeout_secs = interval_secs * 12;
 
-    info!(
-        "Building swarm with 4 validators and {}-second interval",
-        interval_secs
-    );
-
-    let (swarm, _cli, _faucet) = SwarmBuilder::new_local(4)
-        .with_num_fullnodes(1)
-        .with_aptos()
-        .with_init_genesis_config(Arc::new(move |conf| {
-            // Enable validator transactions (required for timelock)
-            conf.consensus_config.enable_validator_txns();
-
-            // TODO: Add timelock configuration for shorter intervals
-            // This would require adding timelock_config to GenesisConfiguration
-            // For now, we rely on the default interval
-        }))
-        .build_with_cli(0)
-        .await;
-
+    let swarm = super::timelock_swarm(NUM_VALIDATORS as usize, interval_secs).await;
     let client = swarm.validators().next().unwrap().rest_client();
 
-    info!("Swarm started, verifying timelock is initialized at genesis");
-
     // Step 1 - Verify timelock initialized at genesis
-    let initialized = super::is_timelock_initialized(&client).await.unwrap();
-    assert!(initialized, "Timelock should be initialized at genesis");
+    let genesis_interval = super::get_current_interval_at_version(&client, Some(0))
+        .await
+        .unwrap_or_else(|e| panic!("Step 1: timelock not initialized at genesis: {}", e));
+    assert_eq!(genesis_interval, 0, "Step 1: genesis interval should be 0");
 
-    let initial_interval = super::get_current_interval(&client).await.unwrap();
-    info!("Initial interval: {}", initial_interval);
-    // Note: initial_interval may be > 0 if genesis took time
+    // Step 2 - Wait for rotation to interval 1
+    let keygen_interval = 1;
+    let state = super::wait_for_interval_rotation(&client, keygen_interval, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| {
+            panic!(
+                "Step 2: rotation to interval {} timed out: {}",
+                keygen_interval, e
+            )
+        });
+    assert!(
+        state.last_rotation_time > 0,
+        "Step 2: rotation time of interval {} not recorded",
+        state.current_interval
+    );
 
-    info!("Waiting for first interval rotation");
+    // Step 3 - Verify the public key of interval 1 is published
+    let public_key = super::wait_for_public_key(&client, keygen_interval, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| {
+            panic!(
+                "Step 3: public key of interval {} not published: {}",
+                keygen_interval, e
+            )
+        });
+    // BLS12-381 G2 point (compressed)
+    assert_eq!(
+        public_key.len(),
+        96,
+        "Step 3: public key of interval {} should be a compressed G2 point",
+        keygen_interval
+    );
 
-    // Step 2 - Wait for rotation to next interval
-    // Use longer timeout since we can't configure short intervals yet
-    let target_interval = initial_interval + 1;
-    let timeout_secs = 120; // 2 minutes - may need adjustment
+    // Step 4 - Wait for rotation to interval 2, which requests the reveal of interval 1
+    super::wait_for_interval_rotation(&client, keygen_interval + 1, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| {
+            panic!(
+                "Step 4: rotation to interval {} timed out: {}",
+                keygen_interval + 1,
+                e
+            )
+        });
 
-    let state = super::wait_for_interval_rotation(&client, target_interval, timeout_secs)
+    // Step 5 - Verify the secret of interval 1 is aggregated from the configured threshold of
+    // shares
+    let threshold = super::get_share_threshold(&client, keygen_interval)
         .await
         .unwrap();
-    assert!(
-        state.current_interval >= target_interval,
-        "Should have rotated to interval {}",
-        target_interval
+    let secret =
+        super::wait_for_secret_aggregated(&client, keygen_interval, threshold, step_timeout_secs)
+            .await
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Step 5: secret of interval {} not aggregated: {}",
+                    keygen_interval, e
+                )
+            });
+    // BLS12-381 G1 point (compressed)
+    assert_eq!(
+        secret.len(),
+        48,
+        "Step 5: secret of interval {} should be a compressed G1 point",
+        keygen_interval
     );
+}
 
-    info!("First rotation complete, verifying public key published");
-
-    // Step 3 - Verify public key for the new interval is published
-    // Note: This may fail if validators haven't published yet
-    match super::verify_public_key_published(&client, target_interval).await {
-        Ok(public_key) => {
-            info!(
-                "Public key published for interval {}: {} bytes",
-                target_interval,
-                public_key.len()
-            );
-            // BLS12-381 G2 point should be 96 bytes (compressed)
-            assert!(
-                public_key.len() == 48 || public_key.len() == 96,
-                "Public key should be 48 or 96 bytes, got {}",
-                public_key.len()
-            );
-        }
-        Err(e) => {
-            info!(
-                "Public key not yet published for interval {}: {}",
-                target_interval, e
-            );
-            // This is expected if DKG hasn't completed
-        }
-    };
+/// Test that the timelock interval configured at genesis drives the rotation.
+#[tokio::test]
+async fn test_timelock_genesis_interval() {
+    let interval_secs = 5;
 
-    // Step 4 - Check if secret is revealed for previous interval
-    if initial_interval > 0 {
-        match super::verify_secret_aggregated(&client, initial_interval - 1, 3).await {
-            Ok(secret) => {
-                info!(
-                    "Secret revealed for interval {}: {} bytes",
-                    initial_interval - 1,
-                    secret.len()
-                );
-            }
-            Err(e) => {
-                info!(
-                    "Secret not yet revealed for interval {}: {}",
-                    initial_interval - 1,
-                    e
-                );
-            }
-        }
-    }
+    let (swarm, _cli, _faucet) = SwarmBuilder::new_local(1)
+        .with_aptos()
+        .with_init_genesis_config(Arc::new(move |conf| {
+            conf.timelock_config = Some(TimelockGenesisConfig {
+                interval_microseconds: interval_secs * 1_000_000,
+                enabled: true,
+            });
+        }))
+        .build_with_cli(0)
+        .await;
 
-    info!("✅ Test completed - basic timelock flow verified");
+    let client = swarm.validators().next().unwrap().rest_client();
+    let initial_interval = super::get_current_interval(&client).await.unwrap();
+
+    // The first block only records the rotation time, so allow for a few intervals to pass
+    let rotated =
+        super::wait_for_interval_rotation(&client, initial_interval + 2, interval_secs * 6)
+            .await
+            .unwrap();
+    let next =
+        super::wait_for_interval_rotation(&client, rotated.current_interval + 1, interval_secs * 6)
+            .await
+            .unwrap();
+    assert!(
+        next.last_rotation_time - rotated.last_rotation_time >= interval_secs * 1_000_000,
+        "rotated from interval {} to {} after less than {}s",
+        rotated.current_interval,
+        next.current_interval,
+        interval_secs
+    );
 }
 
 /// Test that timelock config can be updated on testnet (not mainnet).
@@ -136,28 +165,300 @@ async fn test_timelock_config_override() {
     // TODO: Verify it aborts on mainnet (chain_id == 1)
 }
 
-/// Test that timelock handles validator set changes gracefully.
+/// Test that timelock keeps working across a validator-set change.
 ///
-/// TODO: Implement when DKG integration is complete
+/// Expected semantics:
+/// - The key of an interval is dealt by the validator set of the epoch its key generation ran
+///   in, and its reveal is made of the shares of that dealer set: an epoch change between the
+///   key generation and the reveal of an interval does not stop the remaining dealers from
+///   revealing their shares. The threshold is below the number of dealers, so the secret of such
+///   an interval still aggregates from the shares of the old dealers that remain.
+/// - The key generation of every later interval runs with the validator set of the epoch it
+///   starts in, so a validator that left the set deals and reveals no share for it.
 #[tokio::test]
-#[ignore]
 async fn test_timelock_with_validator_changes() {
-    // TODO: Start with 4 validators
-    // TODO: Trigger DKG for interval 1
-    // TODO: Add validator during DKG
-    // TODO: Verify new validator doesn't break DKG
-    // TODO: Verify reveal still works with threshold
+    // The epoch changes within an interval, between its key generation and its reveal
+    let epoch_duration_secs = 20;
+    let interval_secs = 60;
+    let step_timeout_secs = interval_secs * 3;
+
+    let swarm = SwarmBuilder::new_local(NUM_VALIDATORS as usize)
+        .with_num_fullnodes(1)
+        .with_aptos()
+        .with_init_genesis_config(Arc::new(move |conf| {
+            conf.epoch_duration_secs = epoch_duration_secs;
+            conf.allow_new_validators = true;
+            conf.consensus_config.enable_validator_txns();
+            conf.timelock_config = Some(TimelockGenesisConfig {
+                interval_microseconds: interval_secs * 1_000_000,
+                enabled: true,
+            });
+        }))
+        .build()
+        .await;
+    let client_endpoint = swarm.validators().nth(1).unwrap().rest_api_endpoint();
+    let client = aptos_rest_client::Client::new(client_endpoint.clone());
+
+    // Key generation of interval 1 by the 4 genesis validators
+    let interval = 1;
+    super::wait_for_public_key(&client, interval, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| panic!("key generation of interval {} timed out: {}", interval, e));
+
+    info!("Letting one of the validators leave.");
+    let (victim_sk, victim_addr) = {
+        let victim = swarm.validators().next().unwrap();
+        let sk = victim.account_private_key().clone().unwrap().private_key();
+        (sk, victim.peer_id())
+    };
+    let mut public_info = swarm.chain_info().into_aptos_public_info();
+    public_info
+        .mint(victim_addr, 100000000000000)
+        .await
+        .unwrap();
+    let faucet_endpoint: reqwest::Url = "http://localhost:8081".parse().unwrap();
+    let mut cli = CliTestFramework::new(
+        client_endpoint,
+        faucet_endpoint,
+        /*num_cli_accounts=*/ 0,
+    )
+    .await;
+    let idx = cli.add_account_to_cli(victim_sk);
+    cli.leave_validator_set(idx, None).await.unwrap();
+
+    // The leave takes effect at the next epoch
+    let epoch = client
+        .get_ledger_information()
+        .await
+        .unwrap()
+        .into_inner()
+        .epoch;
+    swarm
+        .wait_for_all_nodes_to_catchup_to_epoch(
+            epoch + 1,
+            Duration::from_secs(epoch_duration_secs * 3),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        super::get_current_interval(&client).await.unwrap(),
+        interval,
+        "reveal of interval {} was requested before the validator set changed",
+        interval
+    );
+
+    // (a) The remaining dealers of interval 1 reveal their shares, which reach its threshold
+    let threshold = super::get_share_threshold(&client, interval).await.unwrap();
+    assert!(
+        threshold < NUM_VALIDATORS,
+        "threshold {} of interval {} leaves no room for a dealer to leave",
+        threshold,
+        interval
+    );
+    for validator in swarm.validators().skip(1) {
+        super::wait_for_committed_share(&client, validator.peer_id(), interval, step_timeout_secs)
+            .await
+            .unwrap_or_else(|e| {
+                panic!(
+                    "share of validator {} for interval {} not committed after the validator set changed: {}",
+                    validator.peer_id(),
+                    interval,
+                    e
+                )
+            });
+    }
+    super::wait_for_secret_aggregated(&client, interval, threshold, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| {
+            panic!(
+                "secret of interval {} not aggregated after validator {} left: {}",
+                interval, victim_addr, e
+            )
+        });
+
+    // (b) The key generation of interval 2 runs with the remaining validators
+    let next_interval = interval + 1;
+    super::wait_for_public_key(&client, next_interval, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| {
+            panic!(
+                "key generation of interval {} with the new validator set timed out: {}",
+                next_interval, e
+            )
+        });
+    let threshold = super::get_share_threshold(&client, next_interval)
+        .await
+        .unwrap();
+    super::wait_for_secret_aggregated(&client, next_interval, threshold, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| panic!("secret of interval {} not aggregated: {}", next_interval, e));
+    for validator in swarm.validators().skip(1) {
+        super::wait_for_committed_share(
+            &client,
+            validator.peer_id(),
+            next_interval,
+            step_timeout_secs,
+        )
+        .await
+        .unwrap_or_else(|e| {
+            panic!(
+                "share of validator {} for interval {} not committed: {}",
+                validator.peer_id(),
+                next_interval,
+                e
+            )
+        });
+    }
+    assert!(
+        super::find_committed_share(&client, victim_addr, next_interval, 0)
+            .await
+            .unwrap()
+            .is_none(),
+        "validator {} revealed a share for interval {} after leaving the validator set",
+        victim_addr,
+        next_interval
+    );
 }
 
-/// Test that timelock handles DKG failures gracefully.
+/// Test that timelock recovers from a key generation that cannot complete on a live chain.
 ///
-/// TODO: Implement when DKG integration is complete
+/// Expected semantics:
+/// - While validators holding too little voting power deal to reach the key generation
+///   threshold, no public key is published for the interval being dealt, yet blocks keep being
+///   committed: the validators that do not deal still take part in consensus.
+/// - Once every validator deals again, key generation resumes: the key of a later interval is
+///   dealt and its public key is published.
+/// - The validators that kept dealing run at most one DKG session per interval, so the failure
+///   does not make them retry in a loop.
 #[tokio::test]
-#[ignore]
 async fn test_timelock_dkg_failure_recovery() {
-    // TODO: Start with 4 validators
-    // TODO: Kill 2 validators during DKG
-    // TODO: Verify DKG fails (below threshold)
-    // TODO: Restart validators
-    // TODO: Verify next interval DKG succeeds
+    let interval_secs = 20;
+    let step_timeout_secs = interval_secs * 6;
+
+    let swarm = SwarmBuilder::new_local(NUM_VALIDATORS as usize)
+        .with_aptos()
+        .with_init_config(Arc::new(|_, conf, _| {
+            conf.api.failpoints_enabled = true;
+        }))
+        .with_init_genesis_config(Arc::new(move |conf| {
+            conf.consensus_config.enable_validator_txns();
+            conf.timelock_config = Some(TimelockGenesisConfig {
+                interval_microseconds: interval_secs * 1_000_000,
+                enabled: true,
+            });
+        }))
+        .build()
+        .await;
+    let client = swarm.validators().next().unwrap().rest_client();
+
+    // Let the first key generation complete, then make half of the validators ignore the key
+    // generation requests. A DKG transcript needs the contributions of a quorum of the voting
+    // power, which the other half does not hold.
+    super::wait_for_public_key(&client, 1, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| panic!("key generation of interval 1 timed out: {}", e));
+    let non_dealers: Vec<_> = swarm
+        .validators()
+        .skip(NUM_VALIDATORS as usize / 2)
+        .map(|validator| validator.rest_client())
+        .collect();
+    for non_dealer in &non_dealers {
+        non_dealer
+            .set_failpoint("dkg::start_timelock_dkg".to_string(), "return".to_string())
+            .await
+            .unwrap();
+    }
+    // The StartKeyGenEvent of an interval is emitted by the block that rotates to it
+    let interval = super::wait_for_interval_rotation(&client, 2, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| panic!("rotation to interval 2 timed out: {}", e))
+        .current_interval;
+
+    // Below the threshold, the key generation of the interval cannot complete, while the chain
+    // keeps going
+    let start_height = block_height(&client).await;
+    let window = Duration::from_secs(interval_secs * 2);
+    let start = Instant::now();
+    while start.elapsed() < window {
+        assert!(
+            super::get_public_key(&client, interval)
+                .await
+                .unwrap()
+                .is_none(),
+            "public key of interval {} published with {} of {} validators dealing",
+            interval,
+            NUM_VALIDATORS as usize - non_dealers.len(),
+            NUM_VALIDATORS
+        );
+        sleep(Duration::from_secs(1)).await;
+    }
+    let end_height = block_height(&client).await;
+    assert!(
+        end_height > start_height,
+        "no block committed while the key generation of interval {} was stalled",
+        interval
+    );
+
+    for non_dealer in &non_dealers {
+        non_dealer
+            .set_failpoint("dkg::start_timelock_dkg".to_string(), "off".to_string())
+            .await
+            .unwrap();
+    }
+
+    // The key generation of a later interval completes
+    let last_interval = super::get_current_interval(&client).await.unwrap() + 1;
+    let start = Instant::now();
+    let recovered_interval = 'wait: loop {
+        for candidate in interval..=last_interval {
+            if super::get_public_key(&client, candidate)
+                .await
+                .unwrap()
+                .is_some()
+            {
+                break 'wait candidate;
+            }
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(step_timeout_secs),
+            "no public key published for intervals {} to {} once every validator dealt again",
+            interval,
+            last_interval
+        );
+        sleep(Duration::from_secs(1)).await;
+    };
+    info!(
+        "Key generation recovered with the key of interval {}",
+        recovered_interval
+    );
+
+    // No runaway retries on the validators that kept dealing
+    let current_interval = super::get_current_interval(&client).await.unwrap();
+    for validator in swarm
+        .validators()
+        .take(NUM_VALIDATORS as usize - non_dealers.len())
+    {
+        let started = InspectionClient::new(validator.inspection_service_endpoint())
+            .get_node_metric_i64("aptos_dkg_timelock_key_gen_sessions{result=started}")
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        assert!(
+            started as u64 <= current_interval,
+            "validator {} started {} DKG sessions for {} intervals",
+            validator.peer_id(),
+            started,
+            current_interval
+        );
+    }
+}
+
+/// The height of the latest committed block.
+async fn block_height(client: &aptos_rest_client::Client) -> u64 {
+    client
+        .get_ledger_information()
+        .await
+        .unwrap()
+        .into_inner()
+        .block_height
 }
@@ -0,0 +1,94 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Timelock encryption round trip E2E test
+//!
+//! Encrypts to an interval with its published public key, as a bidder would, and decrypts with
+//! the secret aggregated on-chain once the interval is revealed.
+
+use aptos_dkg::ibe;
+use aptos_forge::{NodeExt, SwarmExt};
+
+const BID: &[u8] = b"secret bid";
+
+/// Size of the validator set of the test.
+const NUM_VALIDATORS: u64 = 4;
+
+/// Size of a compressed BLS12-381 G1 point, the unit of a published share.
+const G1_NUM_BYTES: usize = 48;
+
+/// Test that a message encrypted to an interval decrypts with the secret aggregated from the
+/// configured threshold of shares, and neither with the share of a single validator nor with the
+/// secret of another interval.
+#[tokio::test]
+async fn test_timelock_encrypt_decrypt_roundtrip() {
+    let interval_secs = 5;
+    let step_timeout_secs = interval_secs * 12;
+
+    let swarm = super::timelock_swarm(NUM_VALIDATORS as usize, interval_secs).await;
+    let client = swarm.validators().next().unwrap().rest_client();
+    let chain_id = swarm.chain_id().id();
+
+    // The first interval with a key generation
+    let interval = 1;
+    let public_key = super::wait_for_public_key(&client, interval, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| panic!("public key of interval {} not published: {}", interval, e));
+    let ciphertext = ibe::encrypt_for_timelock(&public_key, interval, chain_id, BID)
+        .expect("published public key should be a valid MPK")
+        .to_bytes();
+
+    let threshold = super::get_share_threshold(&client, interval).await.unwrap();
+    let secret = super::wait_for_secret_aggregated(&client, interval, threshold, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| panic!("secret of interval {} not aggregated: {}", interval, e));
+    let decrypted = ibe::decrypt_for_timelock(&secret, interval, chain_id, &ciphertext)
+        .expect("aggregated secret should decrypt its interval's ciphertext");
+    assert_eq!(decrypted, BID);
+
+    // The share of a single validator does not decrypt it
+    for validator in swarm.validators() {
+        let share = super::get_share(&client, interval, validator.peer_id())
+            .await
+            .unwrap()
+            .unwrap_or_else(|| {
+                panic!(
+                    "share of validator {} for interval {} not published",
+                    validator.peer_id(),
+                    interval
+                )
+            });
+        if let Ok(decrypted) =
+            ibe::decrypt_for_timelock(&share[..G1_NUM_BYTES], interval, chain_id, &ciphertext)
+        {
+            assert_ne!(
+                decrypted,
+                BID,
+                "share of validator {} alone decrypted a ciphertext of interval {}",
+                validator.peer_id(),
+                interval
+            );
+        }
+    }
+
+    // The secret of the next interval does not decrypt it
+    let next_threshold = super::get_share_threshold(&client, interval + 1)
+        .await
+        .unwrap();
+    let next_secret =
+        super::wait_for_secret_aggregated(&client, interval + 1, next_threshold, step_timeout_secs)
+            .await
+            .unwrap_or_else(|e| {
+                panic!("secret of interval {} not aggregated: {}", interval + 1, e)
+            });
+    if let Ok(decrypted) = ibe::decrypt_for_timelock(&next_secret, interval, chain_id, &ciphertext)
+    {
+        assert_ne!(
+            decrypted,
+            BID,
+            "secret of interval {} decrypted a ciphertext of interval {}",
+            interval + 1,
+            interval
+        );
+    }
+}
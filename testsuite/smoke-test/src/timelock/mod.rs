@@ -8,27 +8,138 @@
 //! for sealed bid auctions.
 
 pub mod basic_flow;
+pub mod encrypt_decrypt;
+pub mod validator_restart;
 
+use crate::smoke_test_environment::SwarmBuilder;
 use anyhow::{anyhow, Result};
 use aptos_api_types::ViewFunction;
+use aptos_dkg::ibe;
+use aptos_forge::LocalSwarm;
+use aptos_genesis::builder::TimelockGenesisConfig;
 use aptos_logger::info;
 use aptos_rest_client::Client;
+use aptos_types::{
+    account_address::AccountAddress,
+    contract_event::ContractEvent,
+    dkg::{
+        real_dkg::{RealDKG, RealDKGPublicParams},
+        DKGTrait, PublicKeyPublishedEvent, ShareReceivedEvent, TimelockConfig,
+        TimelockKeyGenConfig, TimelockState,
+    },
+    on_chain_config::{ConfigurationResource, OnChainConfig, ValidatorSet},
+    validator_verifier::ValidatorVerifier,
+};
 use move_core_types::{identifier::Identifier, language_storage::ModuleId};
-use std::{str::FromStr, time::Duration};
+use std::{future::Future, str::FromStr, sync::Arc, time::Duration};
 use tokio::time::{sleep, Instant};
 
-/// Represents the on-chain timelock state.
-#[allow(dead_code)]
-#[derive(Debug, Clone)]
-pub struct TimelockState {
-    pub current_interval: u64,
-    pub last_rotation_time: u64,
+/// How many times a view call is attempted before its error is returned.
+const VIEW_CALL_ATTEMPTS: usize = 3;
+
+/// How long to wait between two attempts of a view call, and between two polls.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Upper bound on the delay between two polls of a helper that backs off.
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(8);
+
+/// Resource type of the on-chain timelock state.
+const TIMELOCK_STATE_RESOURCE: &str = "0x1::timelock::TimelockState";
+
+/// How many transactions are fetched per request when scanning committed transactions.
+const TRANSACTION_PAGE_SIZE: u16 = 100;
+
+/// Run a view call, retrying it if it fails.
+///
+/// View calls can fail transiently while nodes are starting up or catching up, so a failure is
+/// only returned after `VIEW_CALL_ATTEMPTS` attempts.
+async fn retry_view<T, F, Fut>(mut view_call: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match view_call().await {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt < VIEW_CALL_ATTEMPTS => {
+                info!(
+                    "[Timelock Test] View call failed (attempt {}/{}): {}",
+                    attempt, VIEW_CALL_ATTEMPTS, e
+                );
+                attempt += 1;
+                sleep(POLL_INTERVAL).await;
+            },
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Poll `poll` until it returns a value or `timeout_secs` pass, doubling the delay between two
+/// polls from `POLL_INTERVAL` up to `MAX_POLL_INTERVAL`.
+///
+/// # Errors
+/// Returns the last error of `poll`, or a timeout error naming `what`
+async fn poll_with_backoff<T, F, Fut>(what: &str, timeout_secs: u64, mut poll: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>>>,
+{
+    let start = Instant::now();
+    let timeout = Duration::from_secs(timeout_secs);
+    let mut delay = POLL_INTERVAL;
+    loop {
+        if let Some(value) = poll().await? {
+            return Ok(value);
+        }
+        if start.elapsed() > timeout {
+            return Err(anyhow!(
+                "Timeout waiting for {} after {}s",
+                what,
+                timeout_secs
+            ));
+        }
+        sleep(delay.min(timeout.saturating_sub(start.elapsed()))).await;
+        delay = (delay * 2).min(MAX_POLL_INTERVAL);
+    }
+}
+
+/// Start a swarm of `num_validators` validators with validator transactions enabled and a
+/// timelock interval of `interval_secs`.
+pub async fn timelock_swarm(num_validators: usize, interval_secs: u64) -> LocalSwarm {
+    info!(
+        "Building swarm with {} validators and {}-second interval",
+        num_validators, interval_secs
+    );
+    SwarmBuilder::new_local(num_validators)
+        .with_aptos()
+        .with_init_genesis_config(Arc::new(move |conf| {
+            // Enable validator transactions (required for timelock)
+            conf.consensus_config.enable_validator_txns();
+            conf.timelock_config = Some(TimelockGenesisConfig {
+                interval_microseconds: interval_secs * 1_000_000,
+                enabled: true,
+            });
+        }))
+        .build()
+        .await
 }
 
 /// Get current interval number from on-chain state.
 ///
 /// Calls the timelock::get_current_interval() view function.
 pub async fn get_current_interval(client: &Client) -> Result<u64> {
+    get_current_interval_at_version(client, None).await
+}
+
+/// Get the interval number at `version`, or at the latest version if `None`.
+///
+/// Calls the timelock::get_current_interval() view function, retrying it if it fails.
+pub async fn get_current_interval_at_version(client: &Client, version: Option<u64>) -> Result<u64> {
+    retry_view(|| current_interval_view(client, version)).await
+}
+
+async fn current_interval_view(client: &Client, version: Option<u64>) -> Result<u64> {
     let view_function = ViewFunction {
         module: ModuleId::from_str("0x1::timelock").map_err(|e| anyhow!("{}", e))?,
         function: Identifier::from_str("get_current_interval").map_err(|e| anyhow!("{}", e))?,
@@ -37,7 +148,7 @@ pub async fn get_current_interval(client: &Client) -> Result<u64> {
     };
 
     let result: Vec<u64> = client
-        .view_bcs(&view_function, None)
+        .view_bcs(&view_function, version)
         .await
         .map_err(|e| anyhow!("Failed to call get_current_interval: {}", e))?
         .into_inner();
@@ -48,6 +159,28 @@ pub async fn get_current_interval(client: &Client) -> Result<u64> {
         .ok_or_else(|| anyhow!("get_current_interval returned empty result"))
 }
 
+/// Get the full on-chain timelock state, including the last rotation time that no view
+/// function returns.
+///
+/// Reads the `0x1::timelock::TimelockState` resource as BCS, retrying if it fails.
+pub async fn get_timelock_state(client: &Client) -> Result<TimelockState> {
+    retry_view(|| async {
+        let bytes = client
+            .get_account_resource_bytes(AccountAddress::ONE, TIMELOCK_STATE_RESOURCE)
+            .await
+            .map_err(|e| anyhow!("Failed to get {}: {}", TIMELOCK_STATE_RESOURCE, e))?
+            .into_inner();
+        decode_timelock_state(&bytes)
+    })
+    .await
+}
+
+/// Decode the BCS bytes of a `0x1::timelock::TimelockState` resource.
+pub fn decode_timelock_state(bytes: &[u8]) -> Result<TimelockState> {
+    bcs::from_bytes(bytes)
+        .map_err(|e| anyhow!("Failed to decode {}: {}", TIMELOCK_STATE_RESOURCE, e))
+}
+
 /// Check if timelock is initialized on-chain.
 ///
 /// Queries the get_current_interval view function - if it returns successfully,
@@ -58,14 +191,15 @@ pub async fn is_timelock_initialized(client: &Client) -> Result<bool> {
         Err(e) => {
             info!("[Timelock Test] Timelock not initialized: {}", e);
             Ok(false)
-        }
+        },
     }
 }
 
 /// Wait for timelock interval to rotate to target interval.
 ///
 /// Polls the on-chain TimelockState until current_interval >= target_interval
-/// or timeout is reached.
+/// or timeout is reached. Rotating past the target (e.g. when a poll misses a
+/// rotation) counts as reaching it.
 ///
 /// # Arguments
 /// - client: REST client to query blockchain state
@@ -86,17 +220,15 @@ pub async fn wait_for_interval_rotation(
     let timeout = Duration::from_secs(timeout_secs);
 
     loop {
-        let current = get_current_interval(client).await?;
+        let state = get_timelock_state(client).await?;
+        let current = state.current_interval;
 
         if current >= target_interval {
             info!(
                 "[Timelock Test] Reached interval {} (target: {})",
                 current, target_interval
             );
-            return Ok(TimelockState {
-                current_interval: current,
-                last_rotation_time: 0, // Not tracked via view function
-            });
+            return Ok(state);
         }
 
         if start.elapsed() > timeout {
@@ -114,10 +246,64 @@ pub async fn wait_for_interval_rotation(
             start.elapsed().as_secs_f64()
         );
 
-        sleep(Duration::from_secs(1)).await;
+        sleep(POLL_INTERVAL).await;
     }
 }
 
+/// Wait for the public key of `interval` to be published.
+///
+/// Polls the timelock::get_public_key() view function with backoff.
+///
+/// # Errors
+/// Returns error if the public key is not published before the timeout
+pub async fn wait_for_public_key(
+    client: &Client,
+    interval: u64,
+    timeout_secs: u64,
+) -> Result<Vec<u8>> {
+    poll_with_backoff(
+        &format!("the public key of interval {}", interval),
+        timeout_secs,
+        || get_public_key(client, interval),
+    )
+    .await
+}
+
+/// Wait for the decryption key (the aggregated secret) of `interval` to be revealed.
+///
+/// Polls the timelock::get_secret() view function with backoff.
+///
+/// # Errors
+/// Returns error if the secret is not revealed before the timeout
+pub async fn wait_for_decryption_key(
+    client: &Client,
+    interval: u64,
+    timeout_secs: u64,
+) -> Result<Vec<u8>> {
+    poll_with_backoff(
+        &format!("the secret of interval {}", interval),
+        timeout_secs,
+        || get_secret(client, interval),
+    )
+    .await
+}
+
+/// Wait for the secret of `interval` to be aggregated, then verify it with
+/// `verify_secret_aggregated`.
+///
+/// # Errors
+/// Returns error if the secret is not revealed before the timeout, or is not a
+/// valid decryption key for the interval aggregated from `expected_threshold` shares
+pub async fn wait_for_secret_aggregated(
+    client: &Client,
+    interval: u64,
+    expected_threshold: u64,
+    timeout_secs: u64,
+) -> Result<Vec<u8>> {
+    wait_for_decryption_key(client, interval, timeout_secs).await?;
+    verify_secret_aggregated(client, interval, expected_threshold).await
+}
+
 /// Verify public key is published for interval.
 ///
 /// Queries the timelock module to check if a public key (MPK) has been
@@ -134,65 +320,418 @@ pub async fn wait_for_interval_rotation(
 /// # Errors
 /// Returns error if public key is not published
 pub async fn verify_public_key_published(client: &Client, interval: u64) -> Result<Vec<u8>> {
+    get_public_key(client, interval)
+        .await?
+        .ok_or_else(|| anyhow!("Public key not published for interval {}", interval))
+}
+
+/// Get the public key of `interval`, if published.
+///
+/// Calls the timelock::get_public_key() view function, retrying it if it fails.
+pub async fn get_public_key(client: &Client, interval: u64) -> Result<Option<Vec<u8>>> {
+    retry_view(|| async {
+        optional_bytes_view(client, "get_public_key", vec![bcs::to_bytes(&interval)?]).await
+    })
+    .await
+}
+
+/// Get the share `validator` published for `interval`, if any.
+///
+/// Calls the timelock::get_share() view function, retrying it if it fails.
+pub async fn get_share(
+    client: &Client,
+    interval: u64,
+    validator: AccountAddress,
+) -> Result<Option<Vec<u8>>> {
+    retry_view(|| async {
+        let args = vec![bcs::to_bytes(&interval)?, bcs::to_bytes(&validator)?];
+        optional_bytes_view(client, "get_share", args).await
+    })
+    .await
+}
+
+/// Get the revealed secret for `interval`, if any.
+///
+/// Calls the timelock::get_secret() view function, retrying it if it fails.
+pub async fn get_secret(client: &Client, interval: u64) -> Result<Option<Vec<u8>>> {
+    retry_view(|| async {
+        optional_bytes_view(client, "get_secret", vec![bcs::to_bytes(&interval)?]).await
+    })
+    .await
+}
+
+/// Get the shares published for `interval` by the validators of the DKG of `pub_params`, each
+/// paired with the validator's index in the DKG.
+async fn get_key_shares(
+    client: &Client,
+    pub_params: &RealDKGPublicParams,
+    interval: u64,
+) -> Result<Vec<(u64, Vec<u8>)>> {
+    let mut key_shares = vec![];
+    for (index, validator) in pub_params
+        .session_metadata
+        .target_validator_set
+        .iter()
+        .enumerate()
+    {
+        if let Some(share) = get_share(client, interval, validator.addr).await? {
+            key_shares.push((index as u64, share));
+        }
+    }
+    Ok(key_shares)
+}
+
+/// Get the public parameters of the DKG that dealt the key of `interval`.
+///
+/// Rebuilds its session from the on-chain state at the version that published the key, the way
+/// the VM does to verify the DKG transcript.
+pub async fn get_dkg_public_params(client: &Client, interval: u64) -> Result<RealDKGPublicParams> {
+    let (config, epoch, verifier) = get_key_gen_session(client, interval).await?;
+    Ok(RealDKG::new_timelock_public_params(
+        &config.session_metadata(epoch, &verifier),
+        interval,
+    ))
+}
+
+/// Get the number of validator shares the secret of `interval` takes to aggregate: the threshold
+/// of the key generation config its DKG ran with, scaled to the validators of the DKG.
+pub async fn get_share_threshold(client: &Client, interval: u64) -> Result<u64> {
+    let (config, _, _) = get_key_gen_session(client, interval).await?;
+    Ok(config.threshold)
+}
+
+/// Get the key generation config the DKG of `interval` ran with, scaled to its validators, along
+/// with the epoch and the validators of the DKG, as of the version that published its key.
+async fn get_key_gen_session(
+    client: &Client,
+    interval: u64,
+) -> Result<(TimelockConfig, u64, ValidatorVerifier)> {
+    let (version, _) =
+        find_event::<PublicKeyPublishedEvent>(client, 0, |event| event.interval == interval)
+            .await?
+            .ok_or_else(|| anyhow!("Public key not published for interval {}", interval))?;
+    let epoch = get_resource_at_version::<ConfigurationResource>(client, version)
+        .await?
+        .epoch();
+    let verifier =
+        ValidatorVerifier::from(&get_resource_at_version::<ValidatorSet>(client, version).await?);
+    let config = get_resource_at_version::<TimelockKeyGenConfig>(client, version)
+        .await?
+        .config
+        .scaled_to(verifier.len() as u64);
+    Ok((config, epoch, verifier))
+}
+
+/// Get the on-chain config resource `T` at `version`, retrying if it fails.
+async fn get_resource_at_version<T: OnChainConfig>(client: &Client, version: u64) -> Result<T> {
+    let resource_type = T::struct_tag().to_canonical_string();
+    retry_view(|| async {
+        Ok(client
+            .get_account_resource_at_version_bcs::<T>(AccountAddress::ONE, &resource_type, version)
+            .await
+            .map_err(|e| anyhow!("Failed to get {} at {}: {}", resource_type, version, e))?
+            .into_inner())
+    })
+    .await
+}
+
+/// Calls a timelock view function with the BCS `args`, returning `Option<vector<u8>>`.
+async fn optional_bytes_view(
+    client: &Client,
+    function: &str,
+    args: Vec<Vec<u8>>,
+) -> Result<Option<Vec<u8>>> {
     let view_function = ViewFunction {
         module: ModuleId::from_str("0x1::timelock").map_err(|e| anyhow!("{}", e))?,
-        function: Identifier::from_str("get_public_key").map_err(|e| anyhow!("{}", e))?,
+        function: Identifier::from_str(function).map_err(|e| anyhow!("{}", e))?,
         ty_args: vec![],
-        args: vec![bcs::to_bytes(&interval)?],
+        args,
     };
 
     // Result is Option<vector<u8>> which BCS-deserializes as Vec<Option<Vec<u8>>>
     let result: Vec<Option<Vec<u8>>> = client
         .view_bcs(&view_function, None)
         .await
-        .map_err(|e| anyhow!("Failed to call get_public_key: {}", e))?
+        .map_err(|e| anyhow!("Failed to call {}: {}", function, e))?
         .into_inner();
 
-    result
-        .first()
-        .cloned()
-        .flatten()
-        .ok_or_else(|| anyhow!("Public key not published for interval {}", interval))
+    Ok(result.first().cloned().flatten())
+}
+
+/// Find the share of `validator` for `interval` among the committed transactions.
+///
+/// Scans the transactions from `start_version` up to the latest version for the
+/// `ShareReceivedEvent` emitted when the share was accepted, so discarded submissions
+/// of the share are not counted.
+///
+/// # Returns
+/// The version of the transaction that committed the share and its event, if found
+pub async fn find_committed_share(
+    client: &Client,
+    validator: AccountAddress,
+    interval: u64,
+    start_version: u64,
+) -> Result<Option<(u64, ShareReceivedEvent)>> {
+    find_event::<ShareReceivedEvent>(client, start_version, |event| {
+        event.validator == validator && event.interval == interval
+    })
+    .await
+}
+
+/// Find the first event of type `E` matching `matches` among the committed transactions, scanning
+/// them from `start_version` up to the latest version.
+///
+/// # Returns
+/// The version of the transaction that emitted the event and the event, if found
+async fn find_event<E>(
+    client: &Client,
+    start_version: u64,
+    matches: impl Fn(&E) -> bool,
+) -> Result<Option<(u64, E)>>
+where
+    E: for<'a> TryFrom<&'a ContractEvent>,
+{
+    let latest_version = client
+        .get_ledger_information()
+        .await
+        .map_err(|e| anyhow!("Failed to get ledger information: {}", e))?
+        .into_inner()
+        .version;
+
+    let mut version = start_version;
+    while version <= latest_version {
+        let transactions = client
+            .get_transactions_bcs(Some(version), Some(TRANSACTION_PAGE_SIZE))
+            .await
+            .map_err(|e| anyhow!("Failed to get transactions from {}: {}", version, e))?
+            .into_inner();
+        if transactions.is_empty() {
+            break;
+        }
+        for txn in &transactions {
+            let event = txn
+                .events
+                .iter()
+                .filter_map(|event| E::try_from(event).ok())
+                .find(|event| matches(event));
+            if let Some(event) = event {
+                return Ok(Some((txn.version, event)));
+            }
+        }
+        version = transactions.last().unwrap().version + 1;
+    }
+    Ok(None)
+}
+
+/// Wait for the share of `validator` for `interval` to be committed.
+///
+/// Polls `find_committed_share`, resuming each scan where the previous one stopped.
+///
+/// # Errors
+/// Returns error if the share is not committed before the timeout
+pub async fn wait_for_committed_share(
+    client: &Client,
+    validator: AccountAddress,
+    interval: u64,
+    timeout_secs: u64,
+) -> Result<(u64, ShareReceivedEvent)> {
+    let start = Instant::now();
+    let mut start_version = 0;
+    loop {
+        let latest_version = client
+            .get_ledger_information()
+            .await
+            .map_err(|e| anyhow!("Failed to get ledger information: {}", e))?
+            .into_inner()
+            .version;
+        if let Some(share) =
+            find_committed_share(client, validator, interval, start_version).await?
+        {
+            return Ok(share);
+        }
+        if start.elapsed() > Duration::from_secs(timeout_secs) {
+            return Err(anyhow!(
+                "Timeout waiting for the share of validator {} for interval {} after {}s",
+                validator,
+                interval,
+                timeout_secs
+            ));
+        }
+        start_version = latest_version + 1;
+        sleep(POLL_INTERVAL).await;
+    }
 }
 
 /// Verify secret is aggregated for interval.
 ///
-/// Queries the timelock module to check if the aggregated decryption key
-/// has been revealed for the specified interval.
+/// Queries the timelock module for the decryption key aggregated on-chain for the
+/// specified interval and checks that the first `expected_threshold` shares published
+/// for it reconstruct the same key while one share fewer does not. Then checks the
+/// key against the interval's published public key with
+/// `ibe::verify_timelock_key_share` and `verify_timelock_roundtrip`.
 ///
 /// # Arguments
 /// - client: REST client to query blockchain state
 /// - interval: Interval number to check
-/// - _expected_threshold: (unused) Expected number of shares that should be aggregated
+/// - expected_threshold: Number of validator shares the decryption key takes to aggregate (see
+///   `get_share_threshold`)
 ///
 /// # Returns
-/// Aggregated secret key bytes if revealed
+/// Aggregated secret key bytes
 ///
 /// # Errors
-/// Returns error if secret is not revealed
+/// Returns error if the secret is not revealed, if fewer than `expected_threshold` shares are
+/// published, if they do not reconstruct the secret or one fewer does, or if the secret is not a
+/// valid decryption key
 pub async fn verify_secret_aggregated(
     client: &Client,
     interval: u64,
-    _expected_threshold: u64,
+    expected_threshold: u64,
 ) -> Result<Vec<u8>> {
-    let view_function = ViewFunction {
-        module: ModuleId::from_str("0x1::timelock").map_err(|e| anyhow!("{}", e))?,
-        function: Identifier::from_str("get_secret").map_err(|e| anyhow!("{}", e))?,
-        ty_args: vec![],
-        args: vec![bcs::to_bytes(&interval)?],
-    };
+    let secret = get_secret(client, interval)
+        .await?
+        .ok_or_else(|| anyhow!("Secret not aggregated for interval {}", interval))?;
+    let pub_params = get_dkg_public_params(client, interval).await?;
+    let key_shares = get_key_shares(client, &pub_params, interval).await?;
+    let threshold = expected_threshold as usize;
+    if threshold == 0 || key_shares.len() < threshold {
+        return Err(anyhow!(
+            "Secret of interval {} aggregated from {} shares, expected {}",
+            interval,
+            key_shares.len(),
+            expected_threshold
+        ));
+    }
+    let reconstructed = RealDKG::reconstruct_timelock_key(&pub_params, &key_shares[..threshold])
+        .map_err(|e| {
+            anyhow!(
+                "Secret of interval {} not reconstructed from {} shares: {}",
+                interval,
+                threshold,
+                e
+            )
+        })?;
+    if reconstructed != secret {
+        return Err(anyhow!(
+            "Secret aggregated on-chain for interval {} differs from the one its shares reconstruct",
+            interval
+        ));
+    }
+    if RealDKG::reconstruct_timelock_key(&pub_params, &key_shares[..threshold - 1]).is_ok() {
+        return Err(anyhow!(
+            "Secret of interval {} reconstructed from {} shares, below the threshold of {}",
+            interval,
+            threshold - 1,
+            expected_threshold
+        ));
+    }
 
-    // Result is Option<vector<u8>>
-    let result: Vec<Option<Vec<u8>>> = client
-        .view_bcs(&view_function, None)
+    let public_key = verify_public_key_published(client, interval).await?;
+    let chain_id = client
+        .get_ledger_information()
         .await
-        .map_err(|e| anyhow!("Failed to call get_secret: {}", e))?
-        .into_inner();
+        .map_err(|e| anyhow!("Failed to get ledger information: {}", e))?
+        .into_inner()
+        .chain_id;
+    let identity = ibe::compute_timelock_identity(interval, chain_id);
+    ibe::verify_timelock_key_share(
+        &ibe::deserialize_g1(&secret)?,
+        &ibe::deserialize_g2(&public_key)?,
+        &identity,
+    )
+    .map_err(|e| {
+        anyhow!(
+            "Aggregated secret for interval {} is not a valid decryption key: {}",
+            interval,
+            e
+        )
+    })?;
+    verify_timelock_roundtrip(&public_key, &secret, interval, chain_id)?;
 
-    result
-        .first()
-        .cloned()
-        .flatten()
-        .ok_or_else(|| anyhow!("Secret not aggregated for interval {}", interval))
+    Ok(secret)
 }
 
+/// Verify a message encrypted to an interval decrypts with its revealed secret.
+///
+/// Encrypts a probe message with `ibe::encrypt_for_timelock` under the interval's
+/// public key and decrypts it with `ibe::decrypt_for_timelock`.
+///
+/// # Arguments
+/// - public_key: Published public key bytes for the interval
+/// - secret: Aggregated secret key bytes for the interval
+/// - interval: Interval number
+/// - chain_id: Chain ID the interval belongs to
+///
+/// # Errors
+/// Returns error if either key is malformed or the probe does not round-trip
+pub fn verify_timelock_roundtrip(
+    public_key: &[u8],
+    secret: &[u8],
+    interval: u64,
+    chain_id: u8,
+) -> Result<()> {
+    let probe = format!("timelock_smoke_test_interval_{}", interval);
+    let ciphertext = ibe::encrypt_for_timelock(public_key, interval, chain_id, probe.as_bytes())?;
+    let decrypted = ibe::decrypt_for_timelock(secret, interval, chain_id, &ciphertext.to_bytes())?;
+    if decrypted != probe.as_bytes() {
+        return Err(anyhow!(
+            "Timelock round trip for interval {} returned the wrong plaintext",
+            interval
+        ));
+    }
+    Ok(())
+}
+
+/// Test that the `TimelockState` mirror decodes the resource as laid out by the Move module.
+#[test]
+fn test_decode_timelock_state() {
+    // Bytes of the resource as the REST API returns them, one field per line
+    let bytes = hex::decode(concat!(
+        // current_interval
+        "0700000000000000",
+        // last_rotation_time
+        "150d7a1f240a0600",
+        // public_keys
+        "5b1cbd4c5c6cbd3ba8a0bd29ab9cf6eaa8e9f6a4c6ab8e5b0b2f4b1ce1d9bb7a",
+        // revealed_secrets
+        "8c36a85b2f2e0d0a0de2a4bc6df7b13aa1f60c7abf1a8cbe6b9fa3b36e4b3c51",
+        // start_keygen_events: count, creation number, address
+        "0700000000000000",
+        "0900000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        // request_reveal_events: count, creation number, address
+        "0600000000000000",
+        "0a00000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+    ))
+    .unwrap();
+
+    let state = decode_timelock_state(&bytes).unwrap();
+    assert_eq!(state.current_interval, 7);
+    assert_eq!(state.last_rotation_time, 1_700_000_123_456_789);
+    assert_eq!(
+        state.public_keys.0,
+        AccountAddress::from_hex_literal(
+            "0x5b1cbd4c5c6cbd3ba8a0bd29ab9cf6eaa8e9f6a4c6ab8e5b0b2f4b1ce1d9bb7a"
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        state.revealed_secrets.0,
+        AccountAddress::from_hex_literal(
+            "0x8c36a85b2f2e0d0a0de2a4bc6df7b13aa1f60c7abf1a8cbe6b9fa3b36e4b3c51"
+        )
+        .unwrap()
+    );
+    assert_eq!(state.start_keygen_events.count(), 7);
+    assert_eq!(state.start_keygen_events.key().get_creation_number(), 9);
+    assert_eq!(state.request_reveal_events.count(), 6);
+    assert_eq!(
+        state.request_reveal_events.key().get_creator_address(),
+        AccountAddress::ONE
+    );
+    assert_eq!(bcs::to_bytes(&state).unwrap(), bytes);
+
+    // A truncated resource is rejected rather than read with default fields
+    assert!(decode_timelock_state(&bytes[..bytes.len() - 1]).is_err());
+}
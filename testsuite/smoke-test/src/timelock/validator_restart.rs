@@ -0,0 +1,71 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Timelock validator restart E2E test
+//!
+//! A validator keeps its secret share of an interval in secure storage, so it can still reveal
+//! the share after restarting between the key generation and the reveal of the interval.
+
+use aptos_forge::{NodeExt, SwarmExt};
+use aptos_logger::info;
+use std::time::{Duration, Instant};
+
+/// How long a restarted validator has to become healthy again.
+const MAX_HEALTHY_WAIT_SECS: u64 = 60;
+
+/// Test that a validator restarted between the key generation and the reveal of an interval
+/// still reveals its share.
+#[tokio::test]
+async fn test_timelock_share_revealed_after_validator_restart() {
+    // Long enough for the restart to fit between the key generation and the reveal
+    let interval_secs = 30;
+    let step_timeout_secs = interval_secs * 4;
+
+    let mut swarm = super::timelock_swarm(4, interval_secs).await;
+    let client = swarm.validators().next().unwrap().rest_client();
+
+    // The first interval with a key generation
+    let interval = 1;
+    super::wait_for_public_key(&client, interval, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| panic!("key generation of interval {} timed out: {}", interval, e));
+
+    // Restart the last validator, so that `client` keeps pointing at a running one
+    let node = swarm.validators_mut().last().unwrap();
+    let restarted = node.peer_id();
+    info!("Restarting validator {}", restarted);
+    node.stop();
+    node.start().unwrap();
+    node.wait_until_healthy(Instant::now() + Duration::from_secs(MAX_HEALTHY_WAIT_SECS))
+        .await
+        .unwrap_or_else(|e| panic!("validator {} not healthy after restart: {}", restarted, e));
+    let current_interval = super::get_current_interval(&client).await.unwrap();
+    assert_eq!(
+        current_interval, interval,
+        "reveal of interval {} was requested before the restart completed",
+        interval
+    );
+
+    // Wait for the reveal request, with extra time for the restarted validator to catch up
+    super::wait_for_interval_rotation(&client, interval + 1, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| panic!("reveal request of interval {} timed out: {}", interval, e));
+    let threshold = super::get_share_threshold(&client, interval).await.unwrap();
+    super::wait_for_secret_aggregated(&client, interval, threshold, step_timeout_secs)
+        .await
+        .unwrap_or_else(|e| panic!("secret of interval {} not aggregated: {}", interval, e));
+
+    let (version, share) =
+        super::wait_for_committed_share(&client, restarted, interval, step_timeout_secs)
+            .await
+            .unwrap_or_else(|e| {
+                panic!(
+                    "share of restarted validator {} for interval {} not committed: {}",
+                    restarted, interval, e
+                )
+            });
+    info!(
+        "Share of restarted validator {} committed at version {}: {:?}",
+        restarted, version, share
+    );
+}
@@ -31,6 +31,13 @@ impl DKGTrait for DummyDKG {
         dkg_session_metadata.clone()
     }
 
+    fn new_timelock_public_params(
+        dkg_session_metadata: &DKGSessionMetadata,
+        _interval: u64,
+    ) -> Self::PublicParams {
+        dkg_session_metadata.clone()
+    }
+
     fn aggregate_input_secret(secrets: Vec<DummySecret>) -> DummySecret {
         DummySecret::aggregate(secrets)
     }
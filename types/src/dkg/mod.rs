@@ -5,14 +5,22 @@ use self::real_dkg::RealDKG;
 use crate::{
     contract_event::ContractEvent,
     dkg::real_dkg::{rounding::DKGRoundingProfile, Transcripts},
+    event::EventHandle,
     on_chain_config::{OnChainConfig, OnChainRandomnessConfig, RandomnessConfigMoveStruct},
+    state_store::{state_key::StateKey, table::TableHandle},
     validator_verifier::{
         ValidatorConsensusInfo, ValidatorConsensusInfoMoveStruct, ValidatorVerifier,
     },
 };
-use anyhow::{bail, Context, Result};
-use aptos_crypto::Uniform;
+use anyhow::{bail, ensure, Context, Result};
+use aptos_crypto::{
+    bls12381,
+    blstrs::{g1_proj_from_bytes, g2_proj_from_bytes, G1_PROJ_NUM_BYTES, G2_PROJ_NUM_BYTES},
+    SigningKey, Uniform,
+};
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_dkg::ibe::{compute_timelock_identity, verify_timelock_key_share};
+use fixed::types::U64F64;
 use move_core_types::{
     account_address::AccountAddress, ident_str, identifier::IdentStr, language_storage::TypeTag,
     move_resource::MoveStructType,
@@ -187,6 +195,12 @@ pub trait DKGTrait: Debug {
     type NewValidatorDecryptKey: Uniform;
 
     fn new_public_params(dkg_session_metadata: &DKGSessionMetadata) -> Self::PublicParams;
+    /// The public params of the timelock DKG for `interval`, whose transcripts only verify for
+    /// that interval.
+    fn new_timelock_public_params(
+        dkg_session_metadata: &DKGSessionMetadata,
+        interval: u64,
+    ) -> Self::PublicParams;
     fn aggregate_input_secret(secrets: Vec<Self::InputSecret>) -> Self::InputSecret;
     fn dealt_secret_from_input(
         pub_params: &Self::PublicParams,
@@ -235,10 +249,90 @@ pub mod real_dkg;
 
 pub type DefaultDKG = RealDKG;
 
+/// The decryption key share of validator `author` for `interval`, signed with its consensus key.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct TimelockShare {
+    pub author: AccountAddress,
     pub interval: u64,
     pub share: Vec<u8>,
+    pub signature: bls12381::Signature,
+}
+
+/// What the author of a [`TimelockShare`] signs.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, CryptoHasher, BCSCryptoHash)]
+pub struct TimelockShareSignedData {
+    pub interval: u64,
+    pub share: Vec<u8>,
+}
+
+impl TimelockShare {
+    /// The share of `author` for `interval`, signed with `author`'s consensus key `sk`.
+    pub fn sign(
+        author: AccountAddress,
+        interval: u64,
+        share: Vec<u8>,
+        sk: &bls12381::PrivateKey,
+    ) -> Result<Self> {
+        let signature = sk.sign(&TimelockShareSignedData {
+            interval,
+            share: share.clone(),
+        })?;
+        Ok(Self {
+            author,
+            interval,
+            share,
+            signature,
+        })
+    }
+
+    /// Checks that `author` signed the share with its consensus key in `verifier`.
+    pub fn verify_signature(&self, verifier: &ValidatorVerifier) -> Result<()> {
+        let signed_data = TimelockShareSignedData {
+            interval: self.interval,
+            share: self.share.clone(),
+        };
+        verifier
+            .verify(self.author, &signed_data, &self.signature)
+            .context("TimelockShare signature verification failed")
+    }
+
+    /// Checks each decryption key share against the public key share dealt to the author for the
+    /// same sub-share, as published with the interval's key (see
+    /// `aptos_dkg::ibe::verify_timelock_key_share`).
+    ///
+    /// `public_key_shares` are concatenated compressed G2 points, one per sub-share, like the
+    /// compressed G1 points of the share.
+    pub fn verify_key_shares(&self, public_key_shares: &[u8], chain_id: u8) -> Result<()> {
+        ensure!(
+            self.share.len() % G1_PROJ_NUM_BYTES == 0
+                && public_key_shares.len() % G2_PROJ_NUM_BYTES == 0
+                && self.share.len() / G1_PROJ_NUM_BYTES
+                    == public_key_shares.len() / G2_PROJ_NUM_BYTES,
+            "Share of {} bytes does not match the {} bytes of public key shares",
+            self.share.len(),
+            public_key_shares.len()
+        );
+        let identity = compute_timelock_identity(self.interval, chain_id);
+        for (key_share, pk_share) in self
+            .share
+            .chunks_exact(G1_PROJ_NUM_BYTES)
+            .zip(public_key_shares.chunks_exact(G2_PROJ_NUM_BYTES))
+        {
+            verify_timelock_key_share(
+                &g1_proj_from_bytes(key_share)?,
+                &g2_proj_from_bytes(pk_share)?,
+                &identity,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The aggregated transcript of the timelock DKG for `interval`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TimelockDKGResult {
+    pub interval: u64,
+    pub transcript: DKGTranscript,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -247,6 +341,56 @@ pub struct TimelockConfig {
     pub total_validators: u64,
 }
 
+impl TimelockConfig {
+    /// Whether a DKG can run with this config.
+    pub fn is_valid(&self) -> bool {
+        self.threshold > 0 && self.threshold <= self.total_validators
+    }
+
+    /// The config for a validator set of `num_validators`, keeping the threshold ratio (rounded
+    /// up). Unchanged if `total_validators` already matches or the validator set is empty.
+    ///
+    /// NOTE: `total_validators` must be non-zero.
+    pub fn scaled_to(&self, num_validators: u64) -> TimelockConfig {
+        if self.total_validators == num_validators || num_validators == 0 {
+            return self.clone();
+        }
+        // ceil(threshold * num_validators / total), computed without overflow
+        let threshold = (self.threshold as u128 * num_validators as u128)
+            .div_ceil(self.total_validators as u128) as u64;
+        TimelockConfig {
+            threshold,
+            total_validators: num_validators,
+        }
+    }
+
+    /// The session of a timelock DKG run with this config in `epoch`: the validator set of
+    /// `verifier` deals to itself, with a secrecy threshold of `(threshold - 1) / total_validators`
+    /// of the stake and a reconstruction threshold of `threshold / total_validators`. With equal
+    /// voting power, any `threshold` validators reconstruct the secret and fewer learn nothing.
+    ///
+    /// NOTE: used in VM to verify the published transcript, so must stay deterministic.
+    pub fn session_metadata(&self, epoch: u64, verifier: &ValidatorVerifier) -> DKGSessionMetadata {
+        let validator_set: Vec<ValidatorConsensusInfoMoveStruct> = verifier
+            .validator_infos
+            .iter()
+            .cloned()
+            .map(ValidatorConsensusInfoMoveStruct::from)
+            .collect();
+        let total_validators = U64F64::from_num(self.total_validators);
+        DKGSessionMetadata {
+            dealer_epoch: epoch,
+            randomness_config: OnChainRandomnessConfig::new_v1_from_ratios(
+                U64F64::from_num(self.threshold.saturating_sub(1)) / total_validators,
+                U64F64::from_num(self.threshold) / total_validators,
+            )
+            .into(),
+            dealer_validator_set: validator_set.clone(),
+            target_validator_set: validator_set,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StartKeyGenEvent {
     pub interval: u64,
@@ -290,18 +434,348 @@ impl TryFrom<&ContractEvent> for RequestRevealEvent {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SecretRevealedEvent {
+    pub interval: u64,
+}
+
+impl MoveStructType for SecretRevealedEvent {
+    const MODULE_NAME: &'static IdentStr = ident_str!("timelock");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("SecretRevealedEvent");
+}
+
+impl TryFrom<&ContractEvent> for SecretRevealedEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContractEvent) -> Result<Self> {
+        if event.type_tag() != &TypeTag::Struct(Box::new(Self::struct_tag())) {
+            bail!("Expected SecretRevealedEvent tag");
+        }
+        bcs::from_bytes(event.event_data()).context("Failed to deserialize SecretRevealedEvent")
+    }
+}
+
+/// Reflection of Move type `0x1::timelock::PublicKeyPublishedEvent`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct PublicKeyPublishedEvent {
+    pub interval: u64,
+    pub validator: AccountAddress,
+}
+
+impl MoveStructType for PublicKeyPublishedEvent {
+    const MODULE_NAME: &'static IdentStr = ident_str!("timelock");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("PublicKeyPublishedEvent");
+}
+
+impl TryFrom<&ContractEvent> for PublicKeyPublishedEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContractEvent) -> Result<Self> {
+        if event.type_tag() != &TypeTag::Struct(Box::new(Self::struct_tag())) {
+            bail!("Expected PublicKeyPublishedEvent tag");
+        }
+        bcs::from_bytes(event.event_data()).context("Failed to deserialize PublicKeyPublishedEvent")
+    }
+}
+
+/// Reflection of Move type `0x1::timelock::ShareReceivedEvent`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ShareReceivedEvent {
+    pub interval: u64,
+    pub validator: AccountAddress,
+    pub shares_received_so_far: u64,
+    pub threshold: u64,
+    /// Whether the secret of `interval` is aggregated.
+    pub aggregation_complete: bool,
+}
+
+impl MoveStructType for ShareReceivedEvent {
+    const MODULE_NAME: &'static IdentStr = ident_str!("timelock");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("ShareReceivedEvent");
+}
+
+impl TryFrom<&ContractEvent> for ShareReceivedEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContractEvent) -> Result<Self> {
+        if event.type_tag() != &TypeTag::Struct(Box::new(Self::struct_tag())) {
+            bail!("Expected ShareReceivedEvent tag");
+        }
+        bcs::from_bytes(event.event_data()).context("Failed to deserialize ShareReceivedEvent")
+    }
+}
+
+/// Reflection of Move type `0x1::timelock::TimelockState`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TimelockState {
+    pub current_interval: u64,
+    pub last_rotation_time: u64,
+    /// `Table<u64, vector<u8>>` of interval -> public key.
+    pub public_keys: TableHandle,
+    /// `Table<u64, vector<u8>>` of interval -> revealed secret.
+    pub revealed_secrets: TableHandle,
+    pub start_keygen_events: EventHandle,
+    pub request_reveal_events: EventHandle,
+}
+
+impl TimelockState {
+    /// The state key of the public key of `interval`.
+    pub fn public_key_state_key(&self, interval: u64) -> StateKey {
+        StateKey::table_item(
+            &self.public_keys,
+            &bcs::to_bytes(&interval).expect("u64 serialization should not fail"),
+        )
+    }
+}
+
+impl OnChainConfig for TimelockState {
+    const MODULE_IDENTIFIER: &'static str = "timelock";
+    const TYPE_IDENTIFIER: &'static str = "TimelockState";
+}
+
+/// Reflection of Move type `0x1::timelock::KeyGenConfig`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TimelockKeyGenConfig {
+    pub config: TimelockConfig,
+}
+
+impl OnChainConfig for TimelockKeyGenConfig {
+    const MODULE_IDENTIFIER: &'static str = "timelock";
+    const TYPE_IDENTIFIER: &'static str = "KeyGenConfig";
+}
+
+/// Reflection of Move type `0x1::timelock::ShareKey`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TimelockShareKey {
+    pub interval: u64,
+    pub validator: AccountAddress,
+}
+
+/// Reflection of Move type `0x1::timelock::ValidatorShares`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TimelockValidatorShares {
+    /// `Table<ShareKey, vector<u8>>` of (interval, validator) -> share.
+    pub shares: TableHandle,
+    /// `Table<u64, u64>` of interval -> number of decryption key shares received.
+    pub share_counts: TableHandle,
+    /// `Table<ShareKey, vector<u8>>` of (interval, validator) -> public key shares dealt to the
+    /// validator.
+    pub public_key_shares: TableHandle,
+    /// `Table<ShareKey, vector<u8>>` of (interval, validator) -> evaluation points of the shares
+    /// dealt to the validator.
+    pub evaluation_points: TableHandle,
+    /// `Table<u64, u64>` of interval -> number of decryption key shares that aggregate its secret.
+    pub thresholds: TableHandle,
+    /// `Table<u64, vector<address>>` of interval -> validators that published their share.
+    pub validators: TableHandle,
+}
+
+impl TimelockValidatorShares {
+    /// The state key of the share of `validator` for `interval`.
+    pub fn share_state_key(&self, interval: u64, validator: AccountAddress) -> StateKey {
+        Self::table_item(&self.shares, interval, validator)
+    }
+
+    /// The state key of the public key shares the DKG of `interval` dealt to `validator`.
+    pub fn public_key_share_state_key(&self, interval: u64, validator: AccountAddress) -> StateKey {
+        Self::table_item(&self.public_key_shares, interval, validator)
+    }
+
+    fn table_item(handle: &TableHandle, interval: u64, validator: AccountAddress) -> StateKey {
+        let key = TimelockShareKey {
+            interval,
+            validator,
+        };
+        StateKey::table_item(
+            handle,
+            &bcs::to_bytes(&key).expect("TimelockShareKey serialization should not fail"),
+        )
+    }
+}
+
+impl OnChainConfig for TimelockValidatorShares {
+    const MODULE_IDENTIFIER: &'static str = "timelock";
+    const TYPE_IDENTIFIER: &'static str = "ValidatorShares";
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aptos_dkg::{ibe::derive_timelock_key_share, pvss::Player};
 
     #[test]
     fn test_timelock_share_bcs() {
-        let share = TimelockShare {
-            interval: 100,
-            share: vec![1, 2, 3, 4],
-        };
+        let share = TimelockShare::sign(
+            AccountAddress::ONE,
+            100,
+            vec![1, 2, 3, 4],
+            &bls12381::PrivateKey::generate_for_testing(),
+        )
+        .unwrap();
         let bytes = bcs::to_bytes(&share).expect("serialization failed");
         let decoded: TimelockShare = bcs::from_bytes(&bytes).expect("deserialization failed");
         assert_eq!(share, decoded);
     }
+
+    #[test]
+    fn test_timelock_share_signature() {
+        let (signers, verifier) =
+            crate::validator_verifier::random_validator_verifier(2, None, false);
+        let share = TimelockShare::sign(
+            signers[0].author(),
+            100,
+            vec![1, 2, 3],
+            signers[0].private_key(),
+        )
+        .unwrap();
+        assert!(share.verify_signature(&verifier).is_ok());
+
+        let mut other_share = share.clone();
+        other_share.share = vec![4, 5, 6];
+        let mut other_interval = share.clone();
+        other_interval.interval = 101;
+        let mut other_author = share.clone();
+        other_author.author = signers[1].author();
+        let mut not_a_validator = share;
+        not_a_validator.author = AccountAddress::ONE;
+        for share in [other_share, other_interval, other_author, not_a_validator] {
+            assert!(share.verify_signature(&verifier).is_err());
+        }
+    }
+
+    #[test]
+    fn test_timelock_session_metadata() {
+        let (_, verifier) = crate::validator_verifier::random_validator_verifier(4, None, false);
+        let config = TimelockConfig {
+            threshold: 2,
+            total_validators: 3,
+        }
+        .scaled_to(verifier.len() as u64);
+        assert_eq!(config, TimelockConfig {
+            threshold: 3,
+            total_validators: 4,
+        });
+
+        let metadata = config.session_metadata(7, &verifier);
+        assert_eq!(metadata.dealer_epoch, 7);
+        assert_eq!(metadata.dealer_validator_set, metadata.target_validator_set);
+        assert_eq!(
+            metadata.target_validator_consensus_infos_cloned(),
+            verifier.validator_infos
+        );
+        assert_eq!(
+            metadata.randomness_config,
+            RandomnessConfigMoveStruct::from(OnChainRandomnessConfig::new_v1_from_ratios(
+                U64F64::from_num(1) / U64F64::from_num(2),
+                U64F64::from_num(3) / U64F64::from_num(4),
+            ))
+        );
+
+        // Any 3 of the 4 validators, of equal voting power, reconstruct the secret; 2 do not
+        let wconfig = DefaultDKG::new_public_params(&metadata).pvss_config.wconfig;
+        let weight_of = |num_players: usize| -> usize {
+            (0..num_players)
+                .map(|id| wconfig.get_player_weight(&Player { id }))
+                .sum()
+        };
+        assert!(weight_of(3) >= wconfig.get_threshold_weight());
+        assert!(weight_of(2) < wconfig.get_threshold_weight());
+    }
+
+    #[test]
+    fn test_timelock_transcript_bound_to_its_interval() {
+        let (signers, verifier) =
+            crate::validator_verifier::random_validator_verifier(1, None, false);
+        let metadata = TimelockConfig {
+            threshold: 1,
+            total_validators: 1,
+        }
+        .session_metadata(7, &verifier);
+        let pub_params = DefaultDKG::new_timelock_public_params(&metadata, 10);
+        let transcript = RealDKG::sample_secret_and_generate_transcript(
+            &mut rand::thread_rng(),
+            &pub_params,
+            0,
+            signers[0].private_key(),
+        );
+
+        assert!(DefaultDKG::verify_transcript(&pub_params, &transcript).is_ok());
+        assert!(DefaultDKG::verify_transcript(
+            &DefaultDKG::new_timelock_public_params(&metadata, 11),
+            &transcript
+        )
+        .is_err());
+        assert!(DefaultDKG::verify_transcript(
+            &DefaultDKG::new_public_params(&metadata),
+            &transcript
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_timelock_key_shares_verified_against_dealt_public_key_shares() {
+        let (signers, verifier) =
+            crate::validator_verifier::random_validator_verifier(1, None, false);
+        let metadata = TimelockConfig {
+            threshold: 1,
+            total_validators: 1,
+        }
+        .session_metadata(7, &verifier);
+        let pub_params = DefaultDKG::new_timelock_public_params(&metadata, 10);
+        let transcript = RealDKG::sample_secret_and_generate_transcript(
+            &mut rand::thread_rng(),
+            &pub_params,
+            0,
+            signers[0].private_key(),
+        );
+        let dk = real_dkg::maybe_dk_from_bls_sk(signers[0].private_key()).unwrap();
+        let (secret_share, _) =
+            DefaultDKG::decrypt_secret_share_from_transcript(&pub_params, &transcript, 0, &dk)
+                .unwrap();
+        let key_share = |interval: u64| -> Vec<u8> {
+            let identity = compute_timelock_identity(interval, 4);
+            secret_share
+                .main
+                .iter()
+                .flat_map(|sub_share| {
+                    derive_timelock_key_share(sub_share.as_group_element(), &identity)
+                        .to_compressed()
+                })
+                .collect()
+        };
+        let share = |interval: u64, share: Vec<u8>| {
+            TimelockShare::sign(
+                signers[0].author(),
+                interval,
+                share,
+                signers[0].private_key(),
+            )
+            .unwrap()
+        };
+        let pk_shares = transcript.dealt_public_key_share_bytes(&pub_params, 0);
+
+        assert!(share(10, key_share(10))
+            .verify_key_shares(&pk_shares, 4)
+            .is_ok());
+        // For another interval or chain
+        assert!(share(11, key_share(10))
+            .verify_key_shares(&pk_shares, 4)
+            .is_err());
+        assert!(share(10, key_share(10))
+            .verify_key_shares(&pk_shares, 5)
+            .is_err());
+        // Missing a sub-share
+        assert!(share(10, key_share(10)[G1_PROJ_NUM_BYTES..].to_vec())
+            .verify_key_shares(&pk_shares, 4)
+            .is_err());
+    }
+
+    #[test]
+    fn test_randomness_dealer_aux_unchanged() {
+        // Randomness DKG dealers keep signing `(epoch, dealer)`
+        assert_eq!(
+            bcs::to_bytes(&real_dkg::DealerAux::Randomness(7, AccountAddress::ONE)).unwrap(),
+            bcs::to_bytes(&(7u64, AccountAddress::ONE)).unwrap()
+        );
+    }
 }
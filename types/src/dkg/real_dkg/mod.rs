@@ -12,11 +12,11 @@ use crate::{
 use anyhow::{anyhow, bail, ensure, Context};
 #[cfg(any(test, feature = "testing"))]
 use aptos_crypto::Uniform;
-use aptos_crypto::{bls12381, bls12381::PrivateKey};
+use aptos_crypto::{bls12381, bls12381::PrivateKey, blstrs::G1_PROJ_NUM_BYTES};
 use aptos_dkg::{
     pvss,
     pvss::{
-        traits::{Convert, Reconstructable, Transcript},
+        traits::{Convert, Reconstructable, SecretSharingConfig, Transcript},
         Player,
     },
 };
@@ -151,6 +151,50 @@ pub struct RealDKGPublicParams {
     pub session_metadata: DKGSessionMetadata,
     pub pvss_config: DKGPvssConfig,
     pub verifier: Arc<ValidatorVerifier>,
+    /// The timelock interval the DKG runs for, if any, which dealers sign along with their
+    /// transcripts.
+    pub timelock_interval: Option<u64>,
+}
+
+impl RealDKGPublicParams {
+    /// The auxiliary data `dealer` signs along with its transcript.
+    fn dealer_aux(&self, dealer: AccountAddress) -> DealerAux {
+        match self.timelock_interval {
+            Some(interval) => DealerAux::Timelock(self.pvss_config.epoch, dealer, interval),
+            None => DealerAux::Randomness(self.pvss_config.epoch, dealer),
+        }
+    }
+
+    /// The number of sub-shares of the main path it takes to reconstruct the dealt secret.
+    pub fn threshold_weight(&self) -> usize {
+        self.pvss_config.wconfig.get_threshold_weight()
+    }
+
+    /// The evaluation points `x_k` of the sub-shares the main path deals to `player`, as
+    /// concatenated 32-byte little-endian scalars. The timelock interpolates the player's
+    /// decryption key shares at them.
+    pub fn evaluation_point_bytes(&self, player: usize) -> Vec<u8> {
+        let wconfig = &self.pvss_config.wconfig;
+        let dom = wconfig.get_batch_evaluation_domain();
+        wconfig
+            .get_all_virtual_players(&Player { id: player })
+            .iter()
+            .flat_map(|virtual_player| dom.get_root_of_unity(virtual_player.id).to_bytes_le())
+            .collect()
+    }
+}
+
+/// The auxiliary data a dealer signs along with its transcript: the epoch and its address, plus
+/// the interval for a timelock DKG, so that the transcript of an interval does not verify for
+/// another one.
+///
+/// NOTE: serialized as a plain tuple, so that randomness DKG transcripts sign the same
+/// `(epoch, dealer)` as before.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub(crate) enum DealerAux {
+    Randomness(u64, AccountAddress),
+    Timelock(u64, AccountAddress, u64),
 }
 
 impl MayHaveRoundingSummary for RealDKGPublicParams {
@@ -167,6 +211,29 @@ pub struct Transcripts {
     pub fast: Option<WTrx>,
 }
 
+impl Transcripts {
+    /// The compressed (96-byte) dealt public key of the main path, which the timelock publishes as
+    /// its master public key.
+    pub fn dealt_public_key_bytes(&self) -> Vec<u8> {
+        self.main.get_dealt_public_key().to_bytes().to_vec()
+    }
+
+    /// The compressed (96-byte) public key shares `g_2^{f(x_k)}` the main path dealt to `player`,
+    /// one per sub-share, concatenated. The timelock checks the player's decryption key shares
+    /// against them.
+    pub fn dealt_public_key_share_bytes(
+        &self,
+        pub_params: &RealDKGPublicParams,
+        player: usize,
+    ) -> Vec<u8> {
+        self.main
+            .get_public_key_share(&pub_params.pvss_config.wconfig, &Player { id: player })
+            .iter()
+            .flat_map(|pk_share| pk_share.to_bytes())
+            .collect()
+    }
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct DealtPubKeyShares {
     // dealt public key share for main path
@@ -217,6 +284,17 @@ impl DKGTrait for RealDKG {
             session_metadata: dkg_session_metadata.clone(),
             pvss_config,
             verifier: verifier.into(),
+            timelock_interval: None,
+        }
+    }
+
+    fn new_timelock_public_params(
+        dkg_session_metadata: &DKGSessionMetadata,
+        interval: u64,
+    ) -> RealDKGPublicParams {
+        RealDKGPublicParams {
+            timelock_interval: Some(interval),
+            ..Self::new_public_params(dkg_session_metadata)
         }
     }
 
@@ -244,7 +322,7 @@ impl DKGTrait for RealDKG {
     ) -> Self::Transcript {
         let my_index = my_index as usize;
         let my_addr = pub_params.session_metadata.dealer_validator_set[my_index].addr;
-        let aux = (pub_params.session_metadata.dealer_epoch, my_addr);
+        let aux = pub_params.dealer_aux(my_addr);
 
         let wtrx = WTrx::deal(
             &pub_params.pvss_config.wconfig,
@@ -356,7 +434,7 @@ impl DKGTrait for RealDKG {
 
         let aux = dealers_addresses
             .iter()
-            .map(|address| (params.pvss_config.epoch, address))
+            .map(|address| params.dealer_aux(*address))
             .collect::<Vec<_>>();
 
         trx.main.verify(
@@ -505,6 +583,59 @@ impl DKGTrait for RealDKG {
 }
 
 impl RealDKG {
+    /// Interpolates the timelock decryption key of an interval from validators' decryption key
+    /// shares, each paired with the validator's index in the session's validator set.
+    ///
+    /// A key share is the concatenation of the validator's compressed G1 sub-shares of the main
+    /// path, each multiplied by the interval's identity scalar (see
+    /// `aptos_dkg::ibe::derive_timelock_key_share`). That map is linear, so the key shares
+    /// interpolate like the dealt secret does. Returns the 48-byte compressed decryption key.
+    pub fn reconstruct_timelock_key(
+        pub_params: &RealDKGPublicParams,
+        key_shares: &[(u64, Vec<u8>)],
+    ) -> anyhow::Result<Vec<u8>> {
+        let wconfig = &pub_params.pvss_config.wconfig;
+        let mut players = BTreeSet::new();
+        let mut weight = 0;
+        let mut player_share_pairs = Vec::with_capacity(key_shares.len());
+        for (player, key_share) in key_shares {
+            let id = *player as usize;
+            ensure!(
+                id < wconfig.get_total_num_players(),
+                "Unknown player {}",
+                player
+            );
+            ensure!(
+                players.insert(id),
+                "Duplicate key share of player {}",
+                player
+            );
+            let player_weight = wconfig.get_player_weight(&Player { id });
+            ensure!(
+                key_share.len() == player_weight * G1_PROJ_NUM_BYTES,
+                "Key share of player {} has {} bytes, expected {} sub-shares",
+                player,
+                key_share.len(),
+                player_weight
+            );
+            let main = key_share
+                .chunks_exact(G1_PROJ_NUM_BYTES)
+                .map(pvss::dealt_secret_key_share::g1::DealtSecretKeyShare::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| anyhow!("Key share of player {} is malformed: {}", player, e))?;
+            weight += player_weight;
+            player_share_pairs.push((*player, DealtSecretKeyShares { main, fast: None }));
+        }
+        ensure!(
+            weight >= wconfig.get_threshold_weight(),
+            "Not enough key shares: weight {} is below the threshold {}",
+            weight,
+            wconfig.get_threshold_weight()
+        );
+        let key = Self::reconstruct_secret_from_shares(pub_params, player_share_pairs)?;
+        Ok(key.to_bytes().to_vec())
+    }
+
     #[cfg(any(test, feature = "testing"))]
     pub fn sample_secret_and_generate_transcript<R: CryptoRng + RngCore>(
         rng: &mut R,
@@ -544,7 +675,7 @@ impl RealDKG {
         let secret_1 = <RealDKG as DKGTrait>::InputSecret::generate(rng);
         let my_index = my_index as usize;
         let my_addr = pub_params.session_metadata.dealer_validator_set[my_index].addr;
-        let aux = (pub_params.session_metadata.dealer_epoch, my_addr);
+        let aux = pub_params.dealer_aux(my_addr);
 
         let wtrx = WTrx::deal(
             &pub_params.pvss_config.wconfig,
@@ -114,6 +114,14 @@ impl OnChainRandomnessConfig {
         })
     }
 
+    /// A V1 config with the given thresholds, as exact stake ratios.
+    pub fn new_v1_from_ratios(secrecy_threshold: U64F64, reconstruct_threshold: U64F64) -> Self {
+        Self::V1(ConfigV1 {
+            secrecy_threshold: FixedPoint64MoveStruct::from_u64f64(secrecy_threshold),
+            reconstruction_threshold: FixedPoint64MoveStruct::from_u64f64(reconstruct_threshold),
+        })
+    }
+
     pub fn new_v2(
         secrecy_threshold_in_percentage: u64,
         reconstruct_threshold_in_percentage: u64,
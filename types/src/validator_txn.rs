@@ -4,13 +4,12 @@
 #[cfg(any(test, feature = "fuzzing"))]
 use crate::dkg::DKGTranscriptMetadata;
 use crate::{
-    dkg::{DKGTranscript, TimelockShare},
+    dkg::{DKGTranscript, TimelockDKGResult, TimelockShare},
     jwks,
     validator_verifier::ValidatorVerifier,
 };
 use anyhow::Context;
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
-#[cfg(any(test, feature = "fuzzing"))]
 use move_core_types::account_address::AccountAddress;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
@@ -19,7 +18,7 @@ use std::fmt::Debug;
 pub enum ValidatorTransaction {
     DKGResult(DKGTranscript),
     ObservedJWKUpdate(jwks::QuorumCertifiedUpdate),
-    TimelockDKGResult(DKGTranscript),
+    TimelockDKGResult(TimelockDKGResult),
     TimelockShare(TimelockShare),
 }
 
@@ -59,9 +58,10 @@ impl ValidatorTransaction {
                 .context("DKGResult verification failed"),
             ValidatorTransaction::ObservedJWKUpdate(_) => Ok(()),
             ValidatorTransaction::TimelockDKGResult(dkg_result) => dkg_result
+                .transcript
                 .verify(verifier)
                 .context("TimelockDKGResult verification failed"),
-            ValidatorTransaction::TimelockShare(_) => Ok(()),
+            ValidatorTransaction::TimelockShare(share) => share.verify_signature(verifier),
         }
     }
 }
@@ -75,5 +75,9 @@ pub enum Topic {
         issuer: jwks::Issuer,
         kid: jwks::KID,
     },
-    TIMELOCK,
+    /// The timelock decryption key share of a validator for an interval.
+    TIMELOCK_SHARE {
+        author: AccountAddress,
+        interval: u64,
+    },
 }